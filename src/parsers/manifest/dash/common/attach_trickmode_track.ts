@@ -15,6 +15,7 @@
  */
 
 import { SUPPORTED_ADAPTATIONS_TYPE } from "../../../../manifest";
+import arrayIncludes from "../../../../utils/array_includes";
 import type { IParsedAdaptation, IParsedAdaptations } from "../../types";
 
 /**
@@ -51,4 +52,35 @@ function attachTrickModeTrack(
   }
 }
 
+/**
+ * Attach trick mode tracks to adaptations from the links between them already
+ * resolved by the parser, as couples of the index in the Period of a trick
+ * mode AdaptationSet and of the index of an AdaptationSet it is associated to.
+ * @param {Array.<Object|undefined>} adaptationsByIndex - The parsed
+ * adaptation each AdaptationSet of the Period ended up in, if one, by index.
+ * @param {Array.<Object|undefined>} trickModeTracksByIndex - The parsed trick
+ * mode adaptation of each trick mode AdaptationSet of the Period, by index.
+ * @param {Array.<Array.<number>>} links
+ * @returns {void}
+ */
+export function attachTrickModeTrackFromLinks(
+  adaptationsByIndex: Array<IParsedAdaptation | undefined>,
+  trickModeTracksByIndex: Array<IParsedAdaptation | undefined>,
+  links: Array<[number, number]>,
+): void {
+  for (const [trickModeIdx, adaptationIdx] of links) {
+    const trickModeTrack = trickModeTracksByIndex[trickModeIdx];
+    const adaptation = adaptationsByIndex[adaptationIdx];
+    if (trickModeTrack === undefined || adaptation === undefined) {
+      continue;
+    }
+    if (adaptation.trickModeTracks === undefined) {
+      adaptation.trickModeTracks = [];
+    }
+    if (!arrayIncludes(adaptation.trickModeTracks, trickModeTrack)) {
+      adaptation.trickModeTracks.push(trickModeTrack);
+    }
+  }
+}
+
 export default attachTrickModeTrack;
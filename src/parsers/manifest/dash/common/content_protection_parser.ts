@@ -1,5 +1,6 @@
 import log from "../../../../log";
 import arrayFind from "../../../../utils/array_find";
+import areArraysOfNumbersEqual from "../../../../utils/are_arrays_of_numbers_equal";
 import type { IParsedRepresentation } from "../../types";
 import type { IContentProtectionIntermediateRepresentation } from "../node_parser_types";
 
@@ -153,12 +154,23 @@ export default class ContentProtectionParser {
     // Referenced ContentProtection found, let's inherit its attributes
 
     contentProt.children.cencPssh.push(...referenced.children.cencPssh);
+    const referencedPsshInfo = referenced.children.cencPsshInfo;
+    if (referencedPsshInfo !== undefined) {
+      const psshInfo = contentProt.children.cencPsshInfo ?? [];
+      contentProt.children.cencPsshInfo = psshInfo.concat(referencedPsshInfo);
+    }
     if (
       contentProt.attributes.keyId === undefined &&
       referenced.attributes.keyId !== undefined
     ) {
       contentProt.attributes.keyId = referenced.attributes.keyId;
     }
+    if (
+      contentProt.attributes.playReadyKeyIds === undefined &&
+      referenced.attributes.playReadyKeyIds !== undefined
+    ) {
+      contentProt.attributes.playReadyKeyIds = referenced.attributes.playReadyKeyIds;
+    }
     if (
       contentProt.attributes.schemeIdUri === undefined &&
       referenced.attributes.schemeIdUri !== undefined
@@ -210,11 +222,16 @@ function parseContentProtection(
       .replace(/-/g, "")
       .toLowerCase();
   }
-  if (
-    contentProtectionIr.attributes.keyId !== undefined &&
-    contentProtectionIr.attributes.keyId.length > 0
-  ) {
-    const kid = contentProtectionIr.attributes.keyId;
+  const { keyId, playReadyKeyIds } = contentProtectionIr.attributes;
+  const kids = keyId !== undefined && keyId.length > 0 ? [keyId] : [];
+  if (playReadyKeyIds !== undefined) {
+    for (const playReadyKid of playReadyKeyIds) {
+      if (!kids.some((kid) => areArraysOfNumbersEqual(kid, playReadyKid))) {
+        kids.push(playReadyKid);
+      }
+    }
+  }
+  for (const kid of kids) {
     if (representation.contentProtections === undefined) {
       representation.contentProtections = { keyIds: [kid], initData: [] };
     } else if (representation.contentProtections.keyIds === undefined) {
@@ -25,10 +25,13 @@ import isNonEmptyString from "../../../../utils/is_non_empty_string";
 import isNullOrUndefined from "../../../../utils/is_null_or_undefined";
 import type { IParsedAdaptation, IParsedAdaptations } from "../../types";
 import type {
+  IAccessibilityScheme,
   IAdaptationSetIntermediateRepresentation,
   ISegmentTemplateIntermediateRepresentation,
 } from "../node_parser_types";
-import attachTrickModeTrack from "./attach_trickmode_track";
+import attachTrickModeTrack, {
+  attachTrickModeTrackFromLinks,
+} from "./attach_trickmode_track";
 import type ContentProtectionParser from "./content_protection_parser";
 import inferAdaptationType from "./infer_adaptation_type";
 import type { IRepresentationContext } from "./parse_representations";
@@ -77,14 +80,14 @@ interface IAdaptationSwitchingInfos {
  * @param {Object} accessibility
  * @returns {Boolean}
  */
-function isVisuallyImpaired(
-  accessibility:
-    | { schemeIdUri?: string | undefined; value?: string | undefined }
-    | undefined,
-): boolean {
+function isVisuallyImpaired(accessibility: IAccessibilityScheme | undefined): boolean {
   if (accessibility === undefined) {
     return false;
   }
+  if (accessibility.isAudioDescription !== undefined) {
+    // Already decoded by the WebAssembly parser
+    return accessibility.isAudioDescription;
+  }
 
   const isVisuallyImpairedAudioDvbDash =
     accessibility.schemeIdUri === "urn:tva:metadata:cs:AudioPurposeCS:2007" &&
@@ -106,9 +109,7 @@ function isVisuallyImpaired(
  * @returns {Boolean}
  */
 function isCaptionning(
-  accessibilities:
-    | Array<{ schemeIdUri?: string | undefined; value?: string | undefined }>
-    | undefined,
+  accessibilities: IAccessibilityScheme[] | undefined,
   roles:
     | Array<{ schemeIdUri?: string | undefined; value?: string | undefined }>
     | undefined,
@@ -116,8 +117,9 @@ function isCaptionning(
   if (accessibilities !== undefined) {
     const hasDvbClosedCaptionSignaling = accessibilities.some(
       (accessibility) =>
-        accessibility.schemeIdUri === "urn:tva:metadata:cs:AudioPurposeCS:2007" &&
-        accessibility.value === "2",
+        accessibility.isHardOfHearing === true ||
+        (accessibility.schemeIdUri === "urn:tva:metadata:cs:AudioPurposeCS:2007" &&
+          accessibility.value === "2"),
     );
     if (hasDvbClosedCaptionSignaling) {
       return true;
@@ -270,10 +272,19 @@ export default function parseAdaptationSets(
   }> = [];
   const adaptationSwitchingInfos: IAdaptationSwitchingInfos = {};
 
+  // Parsed adaptation of each AdaptationSet, by index in the Period, to attach
+  // trick mode tracks from the links resolved by the parser
+  const adaptationsByIndex: Array<IParsedAdaptation | undefined> = [];
+  const trickModeAdaptationsByIndex: Array<IParsedAdaptation | undefined> = [];
+
   const parsedAdaptationsIDs: string[] = [];
 
   for (let adaptationIdx = 0; adaptationIdx < adaptationsIR.length; adaptationIdx++) {
     const adaptation = adaptationsIR[adaptationIdx];
+    if (adaptation.attributes.unsupportedEssentialProperties !== undefined) {
+      // The parser signaled it (and why) as unsupported
+      continue;
+    }
     const adaptationChildren = adaptation.children;
     const { essentialProperties, roles, label } = adaptationChildren;
 
@@ -299,12 +310,20 @@ export default function parseAdaptationSets(
 
     const adaptationMimeType = adaptation.attributes.mimeType;
     const adaptationCodecs = adaptation.attributes.codecs;
-    const type = inferAdaptationType(
-      representationsIR,
-      isNonEmptyString(adaptationMimeType) ? adaptationMimeType : null,
-      isNonEmptyString(adaptationCodecs) ? adaptationCodecs : null,
-      !isNullOrUndefined(adaptationChildren.roles) ? adaptationChildren.roles : null,
-    );
+    const { inferredType } = adaptation.attributes;
+    let type: "audio" | "video" | "text" | undefined;
+    if (inferredType !== undefined) {
+      // Already inferred by the WebAssembly parser. Images are not handled as
+      // tracks here.
+      type = inferredType === "image" ? undefined : inferredType;
+    } else {
+      type = inferAdaptationType(
+        representationsIR,
+        isNonEmptyString(adaptationMimeType) ? adaptationMimeType : null,
+        isNonEmptyString(adaptationCodecs) ? adaptationCodecs : null,
+        !isNullOrUndefined(adaptationChildren.roles) ? adaptationChildren.roles : null,
+      );
+    }
     if (type === undefined) {
       continue;
     }
@@ -441,6 +460,7 @@ export default function parseAdaptationSets(
         adaptation: parsedAdaptationSet,
         trickModeAttachedAdaptationIds,
       });
+      trickModeAdaptationsByIndex[adaptationIdx] = parsedAdaptationSet;
     } else {
       // look if we have to merge this into another Adaptation
       let mergedIntoIdx = -1;
@@ -461,6 +481,7 @@ export default function parseAdaptationSets(
           ) {
             log.info('DASH Parser: merging "switchable" AdaptationSets', originalID, id);
             mergedInto[0].representations.push(...parsedAdaptationSet.representations);
+            adaptationsByIndex[adaptationIdx] = mergedInto[0];
             mergedInto[1] = {
               priority: Math.max(priority, mergedInto[1].priority),
               isMainAdaptation: isMainAdaptation || mergedInto[1].isMainAdaptation,
@@ -472,6 +493,7 @@ export default function parseAdaptationSets(
       }
 
       if (mergedIntoIdx < 0) {
+        adaptationsByIndex[adaptationIdx] = parsedAdaptationSet;
         parsedAdaptations[type].push([
           parsedAdaptationSet,
           { priority, isMainAdaptation, indexInMpd: adaptationIdx },
@@ -504,7 +526,15 @@ export default function parseAdaptationSets(
     {},
   );
   parsedAdaptations.video.sort(compareAdaptations);
-  attachTrickModeTrack(adaptationsPerType, trickModeAdaptations);
+  if (context.trickModeLinks !== undefined) {
+    attachTrickModeTrackFromLinks(
+      adaptationsByIndex,
+      trickModeAdaptationsByIndex,
+      context.trickModeLinks,
+    );
+  } else {
+    attachTrickModeTrack(adaptationsPerType, trickModeAdaptations);
+  }
   return adaptationsPerType;
 }
 
@@ -561,6 +591,12 @@ export interface IAdaptationSetContext extends IInheritedRepresentationContext {
   unsafelyBaseOnPreviousPeriod: IPeriod | null;
   /** Parses contentProtection elements. */
   contentProtectionParser: ContentProtectionParser;
+  /**
+   * Trick mode AdaptationSets and the AdaptationSets they are associated to,
+   * as couples of their index in the Period, if already resolved by the
+   * parser.
+   */
+  trickModeLinks?: Array<[number, number]> | undefined;
 }
 
 /**
@@ -280,6 +280,7 @@ function parseCompleteIntermediateRepresentation(
     manifestBoundsCalculator,
     manifestProfiles: mpdIR.attributes.profiles,
     receivedTime: args.manifestReceivedTime,
+    resolvedPeriodTimes: mpdIR.attributes.resolvedPeriodTimes,
     unsafelyBaseOnPreviousManifest,
     xlinkInfos,
     xmlNamespaces: mpdIR.attributes.namespaces,
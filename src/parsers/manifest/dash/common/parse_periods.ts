@@ -32,6 +32,7 @@ import type {
 import type {
   IEventStreamIntermediateRepresentation,
   IPeriodIntermediateRepresentation,
+  IResolvedPeriodTime,
 } from "../node_parser_types";
 import type ContentProtectionParser from "./content_protection_parser";
 import flattenOverlappingPeriods from "./flatten_overlapping_periods";
@@ -66,7 +67,18 @@ export default function parsePeriods(
   context: IPeriodContext,
 ): IParsedPeriod[] {
   const parsedPeriods: IParsedPeriod[] = [];
-  const periodsTimeInformation = getPeriodsTimeInformation(periodsIR, context);
+  const resolvedPeriodTimes =
+    context.resolvedPeriodTimes?.length === periodsIR.length
+      ? context.resolvedPeriodTimes
+      : undefined;
+  const periodsTimeInformation =
+    resolvedPeriodTimes !== undefined
+      ? resolvedPeriodTimes.map(({ start, end }) => ({
+          periodStart: start,
+          periodDuration: end !== undefined ? end - start : undefined,
+          periodEnd: end,
+        }))
+      : getPeriodsTimeInformation(periodsIR, context);
   if (periodsTimeInformation.length !== periodsIR.length) {
     throw new Error("MPD parsing error: the time information are incoherent.");
   }
@@ -82,6 +94,10 @@ export default function parsePeriods(
   for (let i = periodsIR.length - 1; i >= 0; i--) {
     const isLastPeriod = i === periodsIR.length - 1;
     const periodIR = periodsIR[i];
+    if (resolvedPeriodTimes?.[i]?.removed === true) {
+      log.warn("DASH: Ignoring a Period entirely overlapped by the following ones.");
+      continue;
+    }
     const xlinkInfos = context.xlinkInfos.get(periodIR);
     const periodBaseURLs = resolveBaseURLs(context.baseURLs, periodIR.children.baseURLs);
 
@@ -124,6 +140,7 @@ export default function parsePeriods(
       receivedTime,
       segmentTemplate,
       start: periodStart,
+      trickModeLinks: periodIR.attributes.trickModeLinks,
       unsafelyBaseOnPreviousPeriod,
     };
     const adaptations = parseAdaptationSets(periodIR.children.adaptations, adapCtxt);
@@ -356,6 +373,11 @@ export interface IPeriodContext extends IInheritedAdaptationContext {
   clockOffset?: number | undefined;
   /** Duration (mediaPresentationDuration) of the whole MPD, in seconds. */
   duration?: number | undefined;
+  /**
+   * Time bounds of each Period, as resolved by the parser. When set, they are
+   * relied on instead of being computed from the Periods' attributes.
+   */
+  resolvedPeriodTimes?: IResolvedPeriodTime[] | undefined;
   /**
    * The parser should take this Manifest - which is a previously parsed
    * Manifest for the same dynamic content - as a base to speed-up the parsing
@@ -22,6 +22,7 @@ import objectAssign from "../../../../utils/object_assign";
 import type { IParsedRepresentation } from "../../types";
 import type {
   IAdaptationSetIntermediateRepresentation,
+  IHdrClassification,
   IRepresentationIntermediateRepresentation,
   IScheme,
 } from "../node_parser_types";
@@ -99,6 +100,43 @@ function getHDRInformation({
   }
 }
 
+/**
+ * Convert the dynamic range classification done by the WebAssembly parser into
+ * the HDR information exposed to the API.
+ * Returns `undefined` for SDR content, or when nothing is known about it.
+ * @param {Object} classification
+ * @returns {Object | undefined}
+ */
+function convertHdrClassification(
+  classification: IHdrClassification,
+): undefined | IHDRInformation {
+  if (classification.dynamicRange === "sdr") {
+    return undefined;
+  }
+  let eotf: string | undefined;
+  switch (classification.transferCharacteristics) {
+    case 16:
+      eotf = "pq";
+      break;
+    case 18:
+      eotf = "hlg";
+      break;
+    default:
+      eotf =
+        classification.dynamicRange === "hdr10" ||
+        classification.dynamicRange === "hdr10+"
+          ? "pq"
+          : undefined;
+  }
+  const colorDepth = classification.bitDepth;
+  const colorSpace = classification.colorSpace === "rec2020" ? "rec2020" : undefined;
+  if (colorDepth === undefined && eotf === undefined && colorSpace === undefined) {
+    // Nothing is known about it (e.g. Dolby Vision without CICP descriptors)
+    return undefined;
+  }
+  return { colorDepth, eotf, colorSpace };
+}
+
 /**
  * Process intermediate representations to create final parsed representations.
  * @param {Array.<Object>} representationsIR
@@ -112,6 +150,11 @@ export default function parseRepresentations(
 ): IParsedRepresentation[] {
   const parsedRepresentations: IParsedRepresentation[] = [];
   for (const representation of representationsIR) {
+    if (representation.attributes.unsupportedEssentialProperties !== undefined) {
+      // The parser signaled it (and why) as unsupported
+      continue;
+    }
+
     // Compute Representation ID
     let representationID =
       representation.attributes.id !== undefined
@@ -210,48 +253,43 @@ export default function parseRepresentations(
       parsedRepresentation.isSpatialAudio = true;
     }
 
+    // Attributes the Representation shares with its AdaptationSet, as already
+    // resolved by the parser if it did
+    const { attributes: reprAttrs } = representation;
+    const { attributes: adapAttrs } = adaptation;
+    const inherited = representation.children.effective?.attributes ?? {
+      codecs: reprAttrs.codecs ?? adapAttrs.codecs,
+      supplementalCodecs: reprAttrs.supplementalCodecs ?? adapAttrs.supplementalCodecs,
+      frameRate: reprAttrs.frameRate ?? adapAttrs.frameRate,
+      height: reprAttrs.height ?? adapAttrs.height,
+      mimeType: reprAttrs.mimeType ?? adapAttrs.mimeType,
+      width: reprAttrs.width ?? adapAttrs.width,
+    };
+
     // Add optional attributes
-    let codecs: string | undefined;
-    if (representation.attributes.codecs !== undefined) {
-      codecs = representation.attributes.codecs;
-    } else if (adaptation.attributes.codecs !== undefined) {
-      codecs = adaptation.attributes.codecs;
-    }
+    let codecs = inherited.codecs;
     if (codecs !== undefined) {
       codecs = codecs === "mp4a.40.02" ? "mp4a.40.2" : codecs;
       parsedRepresentation.codecs = codecs;
     }
 
-    let supplementalCodecs: string | undefined;
-    if (representation.attributes.supplementalCodecs !== undefined) {
-      supplementalCodecs = representation.attributes.supplementalCodecs;
-    } else if (adaptation.attributes.supplementalCodecs !== undefined) {
-      supplementalCodecs = adaptation.attributes.supplementalCodecs;
-    }
-    if (supplementalCodecs !== undefined) {
-      parsedRepresentation.supplementalCodecs =
-        convertSupplementalCodecsToRFC6381(supplementalCodecs);
+    if (inherited.supplementalCodecs !== undefined) {
+      parsedRepresentation.supplementalCodecs = convertSupplementalCodecsToRFC6381(
+        inherited.supplementalCodecs,
+      );
     }
 
-    if (representation.attributes.frameRate !== undefined) {
-      parsedRepresentation.frameRate = representation.attributes.frameRate;
-    } else if (adaptation.attributes.frameRate !== undefined) {
-      parsedRepresentation.frameRate = adaptation.attributes.frameRate;
+    if (inherited.frameRate !== undefined) {
+      parsedRepresentation.frameRate = inherited.frameRate;
     }
-    if (representation.attributes.height !== undefined) {
-      parsedRepresentation.height = representation.attributes.height;
-    } else if (adaptation.attributes.height !== undefined) {
-      parsedRepresentation.height = adaptation.attributes.height;
+    if (inherited.height !== undefined) {
+      parsedRepresentation.height = inherited.height;
     }
-    if (representation.attributes.mimeType !== undefined) {
-      parsedRepresentation.mimeType = representation.attributes.mimeType;
-    } else if (adaptation.attributes.mimeType !== undefined) {
-      parsedRepresentation.mimeType = adaptation.attributes.mimeType;
+    if (inherited.mimeType !== undefined) {
+      parsedRepresentation.mimeType = inherited.mimeType;
     }
-    if (representation.attributes.width !== undefined) {
-      parsedRepresentation.width = representation.attributes.width;
-    } else if (adaptation.attributes.width !== undefined) {
-      parsedRepresentation.width = adaptation.attributes.width;
+    if (inherited.width !== undefined) {
+      parsedRepresentation.width = inherited.width;
     }
 
     // Content Protection parsing
@@ -265,13 +303,17 @@ export default function parseRepresentations(
       }
     }
 
-    parsedRepresentation.hdrInfo = getHDRInformation({
-      adaptationProfiles: adaptation.attributes.profiles,
-      supplementalProperties: adaptation.children.supplementalProperties,
-      essentialProperties: adaptation.children.essentialProperties,
-      manifestProfiles: context.manifestProfiles,
-      codecs,
-    });
+    const { hdrClassification } = representation.attributes;
+    parsedRepresentation.hdrInfo =
+      hdrClassification !== undefined
+        ? convertHdrClassification(hdrClassification)
+        : getHDRInformation({
+            adaptationProfiles: adaptation.attributes.profiles,
+            supplementalProperties: adaptation.children.supplementalProperties,
+            essentialProperties: adaptation.children.essentialProperties,
+            manifestProfiles: context.manifestProfiles,
+            codecs,
+          });
 
     parsedRepresentations.push(parsedRepresentation);
   }
@@ -51,6 +51,12 @@ export interface IMPDChildren {
    * from the first encountered to the last encountered.
    */
   locations: string[];
+  /**
+   * Absolute URLs obtained by resolving `locations` against the URL of the
+   * MPD, when asked to the WebAssembly parser.
+   * Locations which could not be resolved are not part of it.
+   */
+  resolvedLocations?: string[];
   /**
    * Temporal subdivisions in that Manifest.
    *
@@ -105,6 +111,25 @@ export interface IMPDAttributes {
    * parent namespaces to be parsed correctly.
    */
   namespaces?: Array<{ key: string; value: string }>;
+
+  /**
+   * Time bounds of each Period, in document order, as resolved by the parser.
+   * `undefined` if they could not be resolved by it.
+   */
+  resolvedPeriodTimes?: IResolvedPeriodTime[];
+}
+
+/** Time bounds of a Period, as resolved by the parser. */
+export interface IResolvedPeriodTime {
+  /** Time in seconds at which the Period starts. */
+  start: number;
+  /** Time in seconds at which the Period ends, `undefined` if unknown. */
+  end: number | undefined;
+  /**
+   * `true` if the Period is entirely overlapped by the ones following it, and
+   * should thus be ignored.
+   */
+  removed: boolean;
 }
 
 /** Intermediate representation of an encountered Period node. */
@@ -179,6 +204,12 @@ export interface IPeriodAttributes {
    * parent namespaces to be parsed correctly.
    */
   namespaces?: Array<{ key: string; value: string }>;
+
+  /**
+   * Trick mode AdaptationSets and the AdaptationSets they are associated to,
+   * as couples of their index in the Period, as resolved by the parser.
+   */
+  trickModeLinks?: Array<[number, number]>;
 }
 
 /** AdaptationSet once parsed into its intermediate representation. */
@@ -193,11 +224,11 @@ export interface IAdaptationSetChildren {
   representations: IRepresentationIntermediateRepresentation[];
 
   // optional
-  accessibilities?: IScheme[] | undefined;
+  accessibilities?: IAccessibilityScheme[] | undefined;
   contentComponent?: IContentComponentAttributes | undefined;
   /** Encryption-related metadata. */
   contentProtections?: IContentProtectionIntermediateRepresentation[] | undefined;
-  essentialProperties?: IScheme[] | undefined;
+  essentialProperties?: IEssentialPropertyScheme[] | undefined;
   inbandEventStreams?: IScheme[] | undefined;
   roles?: IScheme[];
   supplementalProperties?: IScheme[] | undefined;
@@ -213,35 +244,62 @@ export interface IAdaptationSetAttributes {
   audioSamplingRate?: string;
   bitstreamSwitching?: boolean;
   codecs?: string;
+  /** `codecs`, parsed as RFC 6381 codec strings. */
+  codecsInfo?: ICodecInfo[];
   codingDependency?: boolean;
   contentType?: string;
   frameRate?: number;
+  /** `frameRate` as the ratio it is written as, e.g. `[30000, 1001]`. */
+  frameRateRatio?: [number, number];
   group?: number;
   height?: number;
   id?: string;
   language?: string;
   maxBitrate?: number;
   maxFrameRate?: number;
+  maxFrameRateRatio?: [number, number];
   maxHeight?: number;
   maxPlayoutRate?: number;
   maxWidth?: number;
   maximumSAPPeriod?: number;
   mimeType?: string;
+  /** Parameters of `mimeType`, as `[name, value]` couples. */
+  mimeTypeParameters?: Array<[string, string]>;
   minBitrate?: number;
   minFrameRate?: number;
+  minFrameRateRatio?: [number, number];
   minHeight?: number;
   minWidth?: number;
   par?: string;
+  /** `par` as its width and height, e.g. `[16, 9]`. */
+  parRatio?: [number, number];
   profiles?: string;
+  /** `sar` as its width and height, e.g. `[1, 1]`. */
+  sar?: [number, number];
   selectionPriority?: number;
   segmentAlignment?: number | boolean;
   segmentProfiles?: string;
   subsegmentAlignment?: number | boolean;
   supplementalCodecs?: string;
+  /** `supplementalCodecs`, parsed as RFC 6381 codec strings. */
+  supplementalCodecsInfo?: ICodecInfo[];
   width?: number;
   availabilityTimeComplete?: boolean;
   availabilityTimeOffset?: number;
   label?: string;
+  /**
+   * Type of the AdaptationSet, as inferred by the parser from the `mimeType`,
+   * roles and codecs of it and of its Representations, then from its
+   * `contentType`.
+   */
+  inferredType?: "audio" | "video" | "text" | "image";
+  /** Its DASH roles, normalized and without duplicates. */
+  roles?: IDashRole[];
+  /**
+   * Schemes of its `EssentialProperty` elements which are not supported, in
+   * which case it should be ignored.
+   */
+  unsupportedEssentialProperties?: string[];
 }
 
 export interface IRepresentationIntermediateRepresentation {
@@ -256,11 +314,18 @@ export interface IRepresentationChildren {
   // optional
   /** Encryption-related metadata. */
   contentProtections?: IContentProtectionIntermediateRepresentation[];
+  essentialProperties?: IEssentialPropertyScheme[];
   inbandEventStreams?: IScheme[];
   segmentBase?: ISegmentBaseIntermediateRepresentation;
   segmentList?: ISegmentListIntermediateRepresentation;
   segmentTemplate?: ISegmentTemplateIntermediateRepresentation;
   supplementalProperties?: IScheme[] | undefined;
+  /**
+   * Effective attributes of the Representation and the elements applying to
+   * it, once those inherited from its parents are taken into account.
+   * Only set by the WebAssembly parser, when enabled.
+   */
+  effective?: IRepresentationIntermediateRepresentation;
 }
 
 /* Intermediate representation for A Representation node's attributes. */
@@ -268,20 +333,57 @@ export interface IRepresentationAttributes {
   audioSamplingRate?: string;
   bitrate?: number;
   codecs?: string;
+  /** `codecs`, parsed as RFC 6381 codec strings. */
+  codecsInfo?: ICodecInfo[];
   codingDependency?: boolean;
   frameRate?: number;
+  /** `frameRate` as the ratio it is written as, e.g. `[30000, 1001]`. */
+  frameRateRatio?: [number, number];
   height?: number;
   id?: string;
   maxPlayoutRate?: number;
   maximumSAPPeriod?: number;
   mimeType?: string;
+  /** Parameters of `mimeType`, as `[name, value]` couples. */
+  mimeTypeParameters?: Array<[string, string]>;
   profiles?: string;
   qualityRanking?: number;
+  /** `sar` as its width and height, e.g. `[1, 1]`. */
+  sar?: [number, number];
   segmentProfiles?: string;
   supplementalCodecs?: string;
+  /** `supplementalCodecs`, parsed as RFC 6381 codec strings. */
+  supplementalCodecsInfo?: ICodecInfo[];
   width?: number;
   availabilityTimeComplete?: boolean;
   availabilityTimeOffset?: number;
+  /**
+   * Dynamic range of a video Representation, as classified from its codecs
+   * and CICP descriptors, including those of its parents.
+   */
+  hdrClassification?: IHdrClassification;
+  /**
+   * Codecs which can be enhanced by one of the supplemental codecs, as
+   * `[base layer, enhancement layer]` couples (e.g. HEVC and Dolby Vision).
+   */
+  codecLayers?: Array<[ICodecInfo, ICodecInfo]>;
+  /**
+   * Segments in the time range requested to the parser, for Representations
+   * relying on a SegmentTemplate.
+   */
+  segments?: ISegmentInfo[];
+  /** URL of the initialization segment of those segments, if one. */
+  segmentsInitializationUrl?: string;
+  /**
+   * Thumbnails of the segments requested to the parser, for Representations
+   * with a thumbnail tile grid.
+   */
+  thumbnails?: IThumbnailInfo[];
+  /**
+   * Schemes of its `EssentialProperty` elements which are not supported, in
+   * which case it should be ignored.
+   */
+  unsupportedEssentialProperties?: string[];
 }
 
 export interface ISegmentBaseIntermediateRepresentation {
@@ -325,12 +427,84 @@ export interface IInitializationAttributes {
   media?: string;
 }
 
+/**
+ * Information obtained from a RFC 6381 codec string, such as `avc1.64001f`.
+ * Properties which could not be deduced from it are not set.
+ */
+export interface ICodecInfo {
+  /** The codec string, as found in the MPD. */
+  codec: string;
+  family:
+    | "avc"
+    | "hevc"
+    | "av1"
+    | "vp9"
+    | "vp8"
+    | "mp4a"
+    | "ac-3"
+    | "ec-3"
+    | "ac-4"
+    | "dolby-vision"
+    | "opus"
+    | "flac"
+    | "stpp"
+    | "wvtt"
+    | "unknown";
+  /** Profile indicator, as written in the codec string. */
+  profile?: number;
+  /** Level indicator, as written in the codec string (e.g. `31` for AVC 3.1). */
+  level?: number;
+  tier?: "main" | "high";
+  bitDepth?: number;
+  chromaSubsampling?: "4:0:0" | "4:2:0" | "4:2:2" | "4:4:4";
+  /** Color primaries, as defined by ISO/IEC 23091-4. */
+  colorPrimaries?: number;
+  /** Transfer characteristics, as defined by ISO/IEC 23091-4. */
+  transferCharacteristics?: number;
+  /** Matrix coefficients, as defined by ISO/IEC 23091-4. */
+  matrixCoefficients?: number;
+  fullRange?: boolean;
+  /** `ObjectTypeIndication` of `mp4a` codecs (e.g. `0x40`). */
+  objectTypeIndication?: number;
+  /** MPEG-4 Audio Object Type of `mp4a.40` codecs (e.g. `2` for AAC-LC). */
+  audioObjectType?: number;
+}
+
+/** Values of the `urn:mpeg:dash:role:2011` scheme. */
+export type IDashRole =
+  | "main"
+  | "alternate"
+  | "supplementary"
+  | "commentary"
+  | "dub"
+  | "emergency"
+  | "caption"
+  | "subtitle"
+  | "sign"
+  | "description"
+  | "enhanced-audio-intelligibility"
+  | "forced-subtitle"
+  | "easyreader"
+  | "karaoke"
+  | "metadata";
+
+/** Dynamic range of a video Representation, as classified by the parser. */
+export interface IHdrClassification {
+  dynamicRange: "sdr" | "hdr10" | "hlg" | "dolby-vision" | "hdr10+";
+  colorSpace?: "rec709" | "rec2020";
+  bitDepth?: number;
+  /** Transfer characteristics, as defined by ISO/IEC 23091-4. */
+  transferCharacteristics?: number;
+}
+
 /** The ContentComponent once parsed. */
 export interface IContentComponentAttributes {
   id?: string;
   language?: string;
   contentType?: string;
   par?: string;
+  /** `par` as its width and height, e.g. `[16, 9]`. */
+  parRatio?: [number, number];
 }
 
 export interface IContentProtectionIntermediateRepresentation {
@@ -340,6 +514,20 @@ export interface IContentProtectionIntermediateRepresentation {
 
 export interface IContentProtectionChildren {
   cencPssh: Uint8Array[];
+  /** Fields of each `cenc:pssh` box, as parsed by the WebAssembly parser. */
+  cencPsshInfo?: IPsshInfo[];
+}
+
+/** Fields of a `pssh` box. */
+export interface IPsshInfo {
+  /** Version of the box. */
+  version: number;
+  /** Its SystemID, as 16 bytes. */
+  systemId: Uint8Array;
+  /** Its KIDs, as 16 bytes each. Always empty for version 0 boxes. */
+  keyIds: Uint8Array[];
+  /** The box's data. */
+  data: Uint8Array;
 }
 
 export interface IContentProtectionAttributes {
@@ -348,6 +536,15 @@ export interface IContentProtectionAttributes {
   keyId?: Uint8Array;
   refId?: string;
   ref?: string;
+  /**
+   * License server URLs announced through `dashif:Laurl`, `clearkey:Laurl`
+   * or `ms:laurl` elements, with the type of license they serve if known.
+   */
+  licenseUrls?: Array<{ url: string; licenseType?: string | undefined }>;
+  /** Key IDs found in the PlayReady Header of a `mspr:pro` element. */
+  playReadyKeyIds?: Uint8Array[];
+  /** License server URL found in the PlayReady Header of a `mspr:pro` element. */
+  playReadyLaUrl?: string;
 }
 
 export interface ISegmentTemplateIntermediateRepresentation {
@@ -366,6 +563,15 @@ export interface ISegmentTemplateIntermediateRepresentation {
   initialization?: { media?: string } | undefined;
   timeline?: ISegmentTimelineElement[] | undefined;
   timelineParser?: ITimelineParser | undefined;
+  /**
+   * Entries of the timeline which changed since the one communicated for it
+   * when parsing the previous version of the MPD, with the start of the first
+   * segment in common between both.
+   * Replaced by the complete `timeline` once parsing is done.
+   */
+  timelineUpdate?:
+    | { firstCommonStart: number; entries: ISegmentTimelineElement[] }
+    | undefined;
 }
 
 export interface ISegmentTimelineElement {
@@ -382,6 +588,11 @@ export interface IBaseUrlIntermediateRepresentation {
    * This is the inner content of a BaseURL node.
    */
   value: string;
+  /**
+   * Absolute URLs obtained by resolving `value` against each URL of its
+   * parent elements, when asked to the WebAssembly parser.
+   */
+  resolvedUrls?: string[];
 }
 
 /** Intermediate representation for a Node following a "scheme" format. */
@@ -396,6 +607,66 @@ export interface IScheme {
   value?: string | undefined;
 }
 
+/** Intermediate representation for an `EssentialProperty` node. */
+export interface IEssentialPropertyScheme extends IScheme {
+  /** Grid of thumbnails in each image segment, for the DASH-IF thumbnail scheme. */
+  thumbnailTile?: { columns: number; rows: number };
+}
+
+/** A segment of a Representation, computed by the parser. */
+export interface ISegmentInfo {
+  /** Its number, as used in the SegmentTemplate's `$Number$` token. */
+  number: number;
+  /** Its start, in seconds, relative to its Period. */
+  start: number;
+  /** Its end, in seconds, relative to its Period. */
+  end: number;
+  /** Its URL, once the SegmentTemplate's tokens are replaced. */
+  url: string;
+}
+
+/** A thumbnail of an image Representation, computed by the parser. */
+export interface IThumbnailInfo {
+  /** Number of the segment containing it. */
+  segmentNumber: number;
+  /** Start of the time range it illustrates, in seconds, relative to its Period. */
+  start: number;
+  /** End of that time range, in seconds, relative to its Period. */
+  end: number;
+  /** Crop rectangle of the thumbnail in the segment's image, in pixels. */
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/** Intermediate representation for an `Accessibility` node. */
+export interface IAccessibilityScheme extends IScheme {
+  /** Captions embedded in the video, as decoded from a CEA-608/708 scheme. */
+  captionServices?: ICaptionServices;
+  /** Set to `true` if it signals an audio description. */
+  isAudioDescription?: boolean;
+  /** Set to `true` if it signals content for the hard of hearing. */
+  isHardOfHearing?: boolean;
+}
+
+/** Caption channels embedded in a video track. */
+export interface ICaptionServices {
+  standard: "cea-608" | "cea-708";
+  services: ICaptionService[];
+}
+
+/** A CEA-608 channel or a CEA-708 service. */
+export interface ICaptionService {
+  /** `1` to `4` for `CC1` to `CC4` with CEA-608, the service number for CEA-708. */
+  channel?: number;
+  language?: string;
+  /** CEA-708 captions in "easy reader" format. */
+  easyReader: boolean;
+  /** CEA-708 captions formatted for a 16:9 display. */
+  wideAspectRatio: boolean;
+}
+
 export interface IEventStreamIntermediateRepresentation {
   /** Contains data about the element's children. */
   children: IEventStreamChildren;
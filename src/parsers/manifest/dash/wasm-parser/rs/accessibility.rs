@@ -0,0 +1,250 @@
+use crate::errors::{ParsingError, Result};
+use crate::track_type::DASH_ROLE_SCHEME;
+
+/// `schemeIdUri` of `<Accessibility>` elements describing CEA-608 captions
+/// embedded in the video, as defined by SCTE 214-1.
+pub const CEA_608_SCHEME: &str = "urn:scte:dash:cc:cea-608:2015";
+
+/// `schemeIdUri` of `<Accessibility>` elements describing CEA-708 captions
+/// embedded in the video, as defined by SCTE 214-1.
+pub const CEA_708_SCHEME: &str = "urn:scte:dash:cc:cea-708:2015";
+
+/// `schemeIdUri` of the TV-Anytime AudioPurposeCS classification, used by
+/// DVB-DASH.
+pub const AUDIO_PURPOSE_SCHEME: &str = "urn:tva:metadata:cs:AudioPurposeCS:2007";
+
+/// Flag set when the content is destined to the visually impaired, e.g. an
+/// audio description.
+pub const AUDIO_DESCRIPTION: u8 = 1;
+
+/// Flag set when the content is destined to the hard of hearing, e.g. SDH
+/// subtitles or captions.
+pub const HARD_OF_HEARING: u8 = 2;
+
+/// Standard of captions embedded in a video track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum CaptionStandard {
+    Cea608 = 0,
+    Cea708 = 1,
+}
+
+/// A caption channel (CEA-608) or service (CEA-708) announced by an
+/// `<Accessibility>` element.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaptionService {
+    /// `1` to `4` for the CEA-608 `CC1` to `CC4` channels, the service number
+    /// for CEA-708. `None` when only the language is given.
+    pub channel: Option<u8>,
+
+    /// Language code, as written in the MPD.
+    pub language: Option<String>,
+
+    /// The `er` flag of CEA-708 services: captions in "easy reader" format.
+    pub easy_reader: bool,
+
+    /// The `war` flag of CEA-708 services: captions formatted for a 16:9
+    /// display.
+    pub wide_aspect_ratio: bool,
+}
+
+/// Caption channels or services embedded in a video track.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptionServices {
+    pub standard: CaptionStandard,
+    pub services: Vec<CaptionService>,
+}
+
+impl CaptionServices {
+    /// Decodes the value of an `<Accessibility>` element with the given
+    /// scheme, returning `None` if it does not describe embedded captions.
+    pub fn from_descriptor(scheme_id_uri: &str, value: &str) -> Option<Result<CaptionServices>> {
+        match scheme_id_uri.trim() {
+            CEA_608_SCHEME => Some(CaptionServices::parse(CaptionStandard::Cea608, value)),
+            CEA_708_SCHEME => Some(CaptionServices::parse(CaptionStandard::Cea708, value)),
+            _ => None,
+        }
+    }
+
+    /// Parse a `;`-separated list of caption channels or services, each
+    /// being either a language alone (e.g. `eng`) or a channel and its
+    /// description (e.g. `CC1=eng` for CEA-608, `2=lang:spa,er:1` for
+    /// CEA-708).
+    pub fn parse(standard: CaptionStandard, value: &str) -> Result<CaptionServices> {
+        let mut services = Vec::new();
+        for entry in value.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (channel, description) = match entry.split_once('=') {
+                Some((channel, description)) => {
+                    (Some(parse_channel(standard, channel.trim())?), description)
+                }
+                None => (None, entry),
+            };
+            let mut service = CaptionService {
+                channel,
+                ..CaptionService::default()
+            };
+            match standard {
+                CaptionStandard::Cea608 => service.language = parse_language(description),
+                CaptionStandard::Cea708 => parse_708_description(&mut service, description)?,
+            }
+            services.push(service);
+        }
+        Ok(CaptionServices { standard, services })
+    }
+}
+
+/// Returns the flags (@see `AUDIO_DESCRIPTION` and `HARD_OF_HEARING`) set by
+/// an `<Accessibility>` element, either through the AudioPurposeCS
+/// classification or through the DASH role scheme.
+pub fn purpose_flags(scheme_id_uri: &str, value: &str) -> u8 {
+    match (scheme_id_uri.trim(), value.trim()) {
+        (AUDIO_PURPOSE_SCHEME, "1") | (DASH_ROLE_SCHEME, "description") => AUDIO_DESCRIPTION,
+        (AUDIO_PURPOSE_SCHEME, "2") | (DASH_ROLE_SCHEME, "caption") => HARD_OF_HEARING,
+        _ => 0,
+    }
+}
+
+/// Parse a CEA-608 channel name (`CC1` to `CC4`) or a CEA-708 service number
+/// (`1` to `63`).
+fn parse_channel(standard: CaptionStandard, channel: &str) -> Result<u8> {
+    let parsed = match standard {
+        CaptionStandard::Cea608 => channel
+            .get(..2)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("CC"))
+            .and_then(|_| channel[2..].parse::<u8>().ok())
+            .filter(|number| (1..=4).contains(number)),
+        CaptionStandard::Cea708 => channel
+            .parse::<u8>()
+            .ok()
+            .filter(|number| (1..=63).contains(number)),
+    };
+    parsed.ok_or_else(|| {
+        let expected = match standard {
+            CaptionStandard::Cea608 => "CEA-608 channel",
+            CaptionStandard::Cea708 => "CEA-708 service number",
+        };
+        ParsingError(format!("Invalid {}: \"{}\"", expected, channel))
+    })
+}
+
+/// Parse the description of a CEA-708 service, either a language alone or a
+/// `,`-separated list of `lang`, `war` and `er` parameters.
+fn parse_708_description(service: &mut CaptionService, description: &str) -> Result<()> {
+    if !description.contains(':') {
+        service.language = parse_language(description);
+        return Ok(());
+    }
+    for param in description
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        let (key, value) = param.split_once(':').unwrap_or((param, ""));
+        let value = value.trim();
+        match key.trim() {
+            "lang" => service.language = parse_language(value),
+            "war" => service.wide_aspect_ratio = parse_flag(key, value)?,
+            "er" => service.easy_reader = parse_flag(key, value)?,
+            _ => {
+                return Err(ParsingError(format!(
+                    "Unknown CEA-708 service parameter: \"{}\"",
+                    param
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_flag(key: &str, value: &str) -> Result<bool> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(ParsingError(format!(
+            "Invalid CEA-708 \"{}\" flag: \"{}\"",
+            key.trim(),
+            value
+        ))),
+    }
+}
+
+fn parse_language(language: &str) -> Option<String> {
+    let language = language.trim();
+    if language.is_empty() {
+        None
+    } else {
+        Some(language.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(channel: Option<u8>, language: &str) -> CaptionService {
+        CaptionService {
+            channel,
+            language: Some(language.to_owned()),
+            ..CaptionService::default()
+        }
+    }
+
+    #[test]
+    fn test_cea_608() {
+        let services = CaptionServices::from_descriptor(CEA_608_SCHEME, "CC1=eng;CC3=swe")
+            .unwrap()
+            .unwrap();
+        assert_eq!(services.standard, CaptionStandard::Cea608);
+        assert_eq!(
+            services.services,
+            vec![service(Some(1), "eng"), service(Some(3), "swe")]
+        );
+
+        let services = CaptionServices::parse(CaptionStandard::Cea608, "eng; fra").unwrap();
+        assert_eq!(
+            services.services,
+            vec![service(None, "eng"), service(None, "fra")]
+        );
+
+        assert!(CaptionServices::parse(CaptionStandard::Cea608, "CC5=eng").is_err());
+        assert!(CaptionServices::from_descriptor(AUDIO_PURPOSE_SCHEME, "1").is_none());
+    }
+
+    #[test]
+    fn test_cea_708() {
+        let services =
+            CaptionServices::parse(CaptionStandard::Cea708, "1=lang:eng;2=lang:spa,war:1,er:1")
+                .unwrap();
+        assert_eq!(
+            services.services,
+            vec![
+                service(Some(1), "eng"),
+                CaptionService {
+                    channel: Some(2),
+                    language: Some("spa".to_owned()),
+                    easy_reader: true,
+                    wide_aspect_ratio: true,
+                }
+            ]
+        );
+
+        let services = CaptionServices::parse(CaptionStandard::Cea708, "eng").unwrap();
+        assert_eq!(services.services, vec![service(None, "eng")]);
+
+        assert!(CaptionServices::parse(CaptionStandard::Cea708, "64=lang:eng").is_err());
+        assert!(CaptionServices::parse(CaptionStandard::Cea708, "1=lang:eng,er:2").is_err());
+        assert!(CaptionServices::parse(CaptionStandard::Cea708, "1=foo:bar").is_err());
+    }
+
+    #[test]
+    fn test_purpose_flags() {
+        assert_eq!(purpose_flags(AUDIO_PURPOSE_SCHEME, "1"), AUDIO_DESCRIPTION);
+        assert_eq!(purpose_flags(AUDIO_PURPOSE_SCHEME, "2"), HARD_OF_HEARING);
+        assert_eq!(
+            purpose_flags(DASH_ROLE_SCHEME, "description"),
+            AUDIO_DESCRIPTION
+        );
+        assert_eq!(purpose_flags(DASH_ROLE_SCHEME, "caption"), HARD_OF_HEARING);
+        assert_eq!(purpose_flags(AUDIO_PURPOSE_SCHEME, "3"), 0);
+    }
+}
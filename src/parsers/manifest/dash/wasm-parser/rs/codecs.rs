@@ -0,0 +1,649 @@
+use crate::errors::{ParsingError, Result};
+
+/// Family of a codec, as identified by the first element of its RFC 6381
+/// codec string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum CodecFamily {
+    /// `avc1` and `avc3`
+    Avc = 0,
+    /// `hvc1` and `hev1`
+    Hevc = 1,
+    /// `av01`
+    Av1 = 2,
+    /// `vp09`
+    Vp9 = 3,
+    /// `vp08`
+    Vp8 = 4,
+    /// `mp4a`
+    Mp4a = 5,
+    /// `ac-3`
+    Ac3 = 6,
+    /// `ec-3`
+    Ec3 = 7,
+    /// `ac-4`
+    Ac4 = 8,
+    /// `dvh1`, `dvhe`, `dav1`, `dva1` and `dvav`
+    DolbyVision = 9,
+    /// `Opus`
+    Opus = 10,
+    /// `fLaC`
+    Flac = 11,
+    /// `stpp`
+    Stpp = 12,
+    /// `wvtt`
+    Wvtt = 13,
+    /// Any other codec
+    Unknown = 255,
+}
+
+impl CodecFamily {
+    /// Returns `true` if codecs of that family encode video.
+    pub fn is_video(self) -> bool {
+        matches!(
+            self,
+            CodecFamily::Avc
+                | CodecFamily::Hevc
+                | CodecFamily::Av1
+                | CodecFamily::Vp9
+                | CodecFamily::Vp8
+                | CodecFamily::DolbyVision
+        )
+    }
+}
+
+/// Tier of a video codec (HEVC and AV1).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum Tier {
+    Main = 0,
+    High = 1,
+}
+
+/// Chroma subsampling of a video codec.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ChromaSubsampling {
+    Monochrome = 0,
+    Yuv420 = 1,
+    Yuv422 = 2,
+    Yuv444 = 3,
+}
+
+/// Information obtained from a single RFC 6381 codec string, such as
+/// `avc1.64001f` or `av01.0.04M.10.0.111.09.16.09.0`.
+///
+/// Fields which cannot be deduced from the codec string are set to `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Codec {
+    /// The codec string, as found in the MPD, without compatibility brands.
+    pub codec: String,
+
+    pub family: CodecFamily,
+
+    /// Profile indicator, as written in the codec string: `profile_idc` for
+    /// AVC and HEVC, `seq_profile` for AV1, profile for VP9 and Dolby Vision.
+    pub profile: Option<u32>,
+
+    /// Level indicator, as written in the codec string: `level_idc` for AVC
+    /// (e.g. `31` for level 3.1) and HEVC (e.g. `93` for level 3.1),
+    /// `seq_level_idx` for AV1, level for VP9 (e.g. `31` for level 3.1) and
+    /// Dolby Vision.
+    pub level: Option<u32>,
+
+    pub tier: Option<Tier>,
+
+    /// Bit depth, written in the codec string for AV1 and VP9. For AVC and
+    /// HEVC, this is the maximum bit depth allowed by the profile.
+    pub bit_depth: Option<u32>,
+
+    pub chroma_subsampling: Option<ChromaSubsampling>,
+
+    /// Color primaries, as defined by ISO/IEC 23091-4 (e.g. `9` for BT.2020).
+    pub color_primaries: Option<u32>,
+
+    /// Transfer characteristics, as defined by ISO/IEC 23091-4 (e.g. `16`
+    /// for PQ and `18` for HLG).
+    pub transfer_characteristics: Option<u32>,
+
+    /// Matrix coefficients, as defined by ISO/IEC 23091-4.
+    pub matrix_coefficients: Option<u32>,
+
+    /// `true` if the video uses the full range of values, `false` if it uses
+    /// the "studio" range.
+    pub full_range: Option<bool>,
+
+    /// The `ObjectTypeIndication` of `mp4a` codecs (e.g. `0x40` for MPEG-4
+    /// audio).
+    pub object_type_indication: Option<u32>,
+
+    /// The MPEG-4 Audio Object Type of `mp4a.40` codecs (e.g. `2` for AAC-LC
+    /// and `5` for HE-AAC).
+    pub audio_object_type: Option<u32>,
+}
+
+impl Codec {
+    fn new(codec: &str, family: CodecFamily) -> Codec {
+        Codec {
+            codec: codec.to_owned(),
+            family,
+            profile: None,
+            level: None,
+            tier: None,
+            bit_depth: None,
+            chroma_subsampling: None,
+            color_primaries: None,
+            transfer_characteristics: None,
+            matrix_coefficients: None,
+            full_range: None,
+            object_type_indication: None,
+            audio_object_type: None,
+        }
+    }
+
+    /// Family of the codec a layer encoded with this codec can enhance.
+    ///
+    /// For Dolby Vision, the family of its backward-compatible base layer,
+    /// deduced from its sample entry (e.g. HEVC for `dvh1`).
+    fn base_layer_family(&self) -> CodecFamily {
+        if self.family != CodecFamily::DolbyVision {
+            return self.family;
+        }
+        match self.codec.get(..4) {
+            Some("dvh1" | "dvhe") => CodecFamily::Hevc,
+            Some("dav1") => CodecFamily::Av1,
+            Some("dva1" | "dvav") => CodecFamily::Avc,
+            _ => CodecFamily::Unknown,
+        }
+    }
+
+    /// Parse a single RFC 6381 codec string.
+    ///
+    /// Compatibility brands which may follow a supplemental codec string
+    /// (e.g. `dvh1.08.07/db4h`) are ignored.
+    ///
+    /// Codecs whose family is unknown are not an error, only their family is
+    /// then reported. Returns an error if the codec string of a known family
+    /// is malformed.
+    pub fn parse(codec: &str) -> Result<Codec> {
+        let codec = codec.trim();
+        let codec = codec.split('/').next().unwrap_or_default();
+        let mut elements = codec.split('.');
+        let fourcc = elements.next().unwrap_or_default();
+        let elements: Vec<&str> = elements.collect();
+        let invalid =
+            |reason: &str| ParsingError(format!("Invalid codec string \"{}\": {}", codec, reason));
+        let mut parsed = match fourcc {
+            "avc1" | "avc3" => Codec::new(codec, CodecFamily::Avc),
+            "hvc1" | "hev1" => Codec::new(codec, CodecFamily::Hevc),
+            "av01" => Codec::new(codec, CodecFamily::Av1),
+            "vp09" => Codec::new(codec, CodecFamily::Vp9),
+            "vp08" | "vp8" => return Ok(Codec::new(codec, CodecFamily::Vp8)),
+            "mp4a" => Codec::new(codec, CodecFamily::Mp4a),
+            "ac-3" => return Ok(Codec::new(codec, CodecFamily::Ac3)),
+            "ec-3" => return Ok(Codec::new(codec, CodecFamily::Ec3)),
+            "ac-4" => return Ok(Codec::new(codec, CodecFamily::Ac4)),
+            "dvh1" | "dvhe" | "dav1" | "dva1" | "dvav" => {
+                Codec::new(codec, CodecFamily::DolbyVision)
+            }
+            "Opus" | "opus" => return Ok(Codec::new(codec, CodecFamily::Opus)),
+            "fLaC" | "flac" => return Ok(Codec::new(codec, CodecFamily::Flac)),
+            "stpp" => return Ok(Codec::new(codec, CodecFamily::Stpp)),
+            "wvtt" => return Ok(Codec::new(codec, CodecFamily::Wvtt)),
+            _ => return Ok(Codec::new(codec, CodecFamily::Unknown)),
+        };
+        match parsed.family {
+            CodecFamily::Avc => parse_avc(&mut parsed, &elements),
+            CodecFamily::Hevc => parse_hevc(&mut parsed, &elements),
+            CodecFamily::Av1 => parse_av1(&mut parsed, &elements),
+            CodecFamily::Vp9 => parse_vp9(&mut parsed, &elements),
+            CodecFamily::Mp4a => parse_mp4a(&mut parsed, &elements),
+            _ => parse_dolby_vision(&mut parsed, &elements),
+        }
+        .map_err(invalid)?;
+        Ok(parsed)
+    }
+}
+
+/// Split a list of codec strings, either separated by commas as defined by
+/// RFC 6381 for the `codecs` attribute, or by white spaces as defined by
+/// SCTE 214 for the `scte214:supplementalCodecs` attribute.
+fn split_codec_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(|c: char| c == ',' || c.is_ascii_whitespace())
+        .filter(|c| !c.is_empty())
+}
+
+/// Parse a list of codec strings, such as the `codecs` or the
+/// `scte214:supplementalCodecs` attributes.
+///
+/// Returns the codecs which could be parsed, with the errors encountered for
+/// the other ones.
+pub fn parse_codecs(value: &str) -> (Vec<Codec>, Vec<ParsingError>) {
+    let mut codecs = Vec::new();
+    let mut errors = Vec::new();
+    for codec in split_codec_list(value) {
+        match Codec::parse(codec) {
+            Ok(codec) => codecs.push(codec),
+            Err(err) => errors.push(err),
+        }
+    }
+    (codecs, errors)
+}
+
+/// Convert a list of codec strings, such as the white space-separated
+/// `scte214:supplementalCodecs` attribute, into the comma-separated form of
+/// RFC 6381 which is expected by MSE APIs.
+pub fn normalize_codec_list(value: &str) -> String {
+    split_codec_list(value).collect::<Vec<&str>>().join(", ")
+}
+
+/// Associate each of the `supplemental` codecs to the codec in `codecs` it
+/// enhances, e.g. a Dolby Vision `dvh1` codec to the HEVC codec of its
+/// backward-compatible base layer.
+///
+/// A supplemental codec enhances the first codec of the same media type it is
+/// compatible with: one of the same family, or the codec of the base layer of
+/// a Dolby Vision codec. Supplemental codecs without such codec are ignored.
+pub fn pair_enhancement_layers<'a>(
+    codecs: &'a [Codec],
+    supplemental: &'a [Codec],
+) -> Vec<(&'a Codec, &'a Codec)> {
+    supplemental
+        .iter()
+        .filter_map(|enhancement| {
+            let base_family = enhancement.base_layer_family();
+            codecs
+                .iter()
+                .find(|c| c.family == base_family && c.family != CodecFamily::Unknown)
+                .map(|base| (base, enhancement))
+        })
+        .collect()
+}
+
+/// Result of the parsing of one of the elements of a codec string, with the
+/// reason why it is invalid if it isn't.
+type ElementResult<T> = std::result::Result<T, &'static str>;
+
+fn decimal(element: &str) -> ElementResult<u32> {
+    if element.is_empty() || !element.bytes().all(|c| c.is_ascii_digit()) {
+        return Err("expected a decimal number");
+    }
+    element.parse().map_err(|_| "number too large")
+}
+
+fn full_range_flag(element: &str) -> ElementResult<bool> {
+    match decimal(element)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err("invalid full range flag"),
+    }
+}
+
+fn hexadecimal(element: &str) -> ElementResult<u32> {
+    if element.is_empty() || !element.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err("expected an hexadecimal number");
+    }
+    u32::from_str_radix(element, 16).map_err(|_| "number too large")
+}
+
+/// `avc1.PPCCLL`, with the profile, constraint flags and level in
+/// hexadecimal, or the legacy `avc1.PP.LL` form, in decimal.
+fn parse_avc(codec: &mut Codec, elements: &[&str]) -> ElementResult<()> {
+    let (profile, level) = match elements {
+        [profile_and_level] if profile_and_level.len() == 6 && profile_and_level.is_ascii() => (
+            hexadecimal(&profile_and_level[0..2])?,
+            hexadecimal(&profile_and_level[4..6])?,
+        ),
+        [profile, level] => (decimal(profile)?, decimal(level)?),
+        _ => return Err("expected the profile, constraints and level, e.g. \"avc1.64001f\""),
+    };
+    codec.profile = Some(profile);
+    codec.level = Some(level);
+    let (bit_depth, chroma) = match profile {
+        66 | 77 | 88 | 100 => (Some(8), Some(ChromaSubsampling::Yuv420)),
+        110 => (Some(10), Some(ChromaSubsampling::Yuv420)),
+        122 => (Some(10), Some(ChromaSubsampling::Yuv422)),
+        244 => (Some(14), Some(ChromaSubsampling::Yuv444)),
+        _ => (None, None),
+    };
+    codec.bit_depth = bit_depth;
+    codec.chroma_subsampling = chroma;
+    Ok(())
+}
+
+/// `hvc1.[A-C]P.CCCCCCCC.[LH]LL[.BB...]`: the profile space and profile,
+/// compatibility flags in hexadecimal, tier and level, and constraint bytes.
+fn parse_hevc(codec: &mut Codec, elements: &[&str]) -> ElementResult<()> {
+    if elements.len() < 3 {
+        return Err("expected at least the profile, compatibility flags and tier and level");
+    }
+    let profile = elements[0].trim_start_matches(['A', 'B', 'C']);
+    codec.profile = Some(decimal(profile)?);
+    hexadecimal(elements[1])?;
+    let tier_and_level = elements[2];
+    codec.tier = match tier_and_level.as_bytes().first() {
+        Some(b'L') => Some(Tier::Main),
+        Some(b'H') => Some(Tier::High),
+        _ => return Err("expected the tier to be either \"L\" or \"H\""),
+    };
+    codec.level = Some(decimal(&tier_and_level[1..])?);
+    for constraint in &elements[3..] {
+        hexadecimal(constraint)?;
+    }
+    match codec.profile {
+        Some(1) | Some(3) => {
+            codec.bit_depth = Some(8);
+            codec.chroma_subsampling = Some(ChromaSubsampling::Yuv420);
+        }
+        Some(2) => {
+            codec.bit_depth = Some(10);
+            codec.chroma_subsampling = Some(ChromaSubsampling::Yuv420);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// `av01.P.LLT.DD[.M.CCC.cp.tc.mc.F]`, optional fields taking their default
+/// value when absent.
+fn parse_av1(codec: &mut Codec, elements: &[&str]) -> ElementResult<()> {
+    if elements.len() < 3 || elements.len() > 9 {
+        return Err("expected the profile, level and tier, bit depth and optional color info");
+    }
+    codec.profile = Some(decimal(elements[0])?);
+    let level_and_tier = elements[1];
+    if level_and_tier.len() != 3 || !level_and_tier.is_ascii() {
+        return Err("expected a two-digit level followed by the tier");
+    }
+    codec.level = Some(decimal(&level_and_tier[0..2])?);
+    codec.tier = match &level_and_tier[2..] {
+        "M" => Some(Tier::Main),
+        "H" => Some(Tier::High),
+        _ => return Err("expected the tier to be either \"M\" or \"H\""),
+    };
+    codec.bit_depth = Some(decimal(elements[2])?);
+    let optional = |i: usize, default: &'static str| elements.get(i).copied().unwrap_or(default);
+    let monochrome = decimal(optional(3, "0"))? == 1;
+    codec.chroma_subsampling = Some(match optional(4, "110") {
+        _ if monochrome => ChromaSubsampling::Monochrome,
+        "110" | "111" => ChromaSubsampling::Yuv420,
+        "100" => ChromaSubsampling::Yuv422,
+        "000" => ChromaSubsampling::Yuv444,
+        _ => return Err("invalid chroma subsampling"),
+    });
+    codec.color_primaries = Some(decimal(optional(5, "1"))?);
+    codec.transfer_characteristics = Some(decimal(optional(6, "1"))?);
+    codec.matrix_coefficients = Some(decimal(optional(7, "1"))?);
+    codec.full_range = Some(full_range_flag(optional(8, "0"))?);
+    Ok(())
+}
+
+/// `vp09.PP.LL.DD[.CC.cp.tc.mc.FF]`, optional fields taking their default
+/// value when absent.
+fn parse_vp9(codec: &mut Codec, elements: &[&str]) -> ElementResult<()> {
+    if elements.len() < 3 || elements.len() > 8 {
+        return Err("expected the profile, level, bit depth and optional color info");
+    }
+    codec.profile = Some(decimal(elements[0])?);
+    codec.level = Some(decimal(elements[1])?);
+    codec.bit_depth = Some(decimal(elements[2])?);
+    let optional = |i: usize, default: &'static str| elements.get(i).copied().unwrap_or(default);
+    codec.chroma_subsampling = Some(match decimal(optional(3, "01"))? {
+        0 | 1 => ChromaSubsampling::Yuv420,
+        2 => ChromaSubsampling::Yuv422,
+        3 => ChromaSubsampling::Yuv444,
+        _ => return Err("invalid chroma subsampling"),
+    });
+    codec.color_primaries = Some(decimal(optional(4, "01"))?);
+    codec.transfer_characteristics = Some(decimal(optional(5, "01"))?);
+    codec.matrix_coefficients = Some(decimal(optional(6, "01"))?);
+    codec.full_range = Some(full_range_flag(optional(7, "00"))?);
+    Ok(())
+}
+
+/// `mp4a.OO[.A]`, with the `ObjectTypeIndication` in hexadecimal and the
+/// Audio Object Type in decimal.
+fn parse_mp4a(codec: &mut Codec, elements: &[&str]) -> ElementResult<()> {
+    match elements {
+        [oti] => codec.object_type_indication = Some(hexadecimal(oti)?),
+        [oti, aot] => {
+            codec.object_type_indication = Some(hexadecimal(oti)?);
+            codec.audio_object_type = Some(decimal(aot)?);
+        }
+        _ => return Err("expected the object type indication and audio object type"),
+    }
+    Ok(())
+}
+
+/// `dvh1.PP.LL`, with the Dolby Vision profile and level in decimal.
+fn parse_dolby_vision(codec: &mut Codec, elements: &[&str]) -> ElementResult<()> {
+    match elements {
+        [profile, level] => {
+            codec.profile = Some(decimal(profile)?);
+            codec.level = Some(decimal(level)?);
+            Ok(())
+        }
+        _ => Err("expected the Dolby Vision profile and level"),
+    }
+}
+
+/// A media type, such as the `mimeType` attribute, split into its essence
+/// (`type/subtype`) and its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaType {
+    /// The `type/subtype` part, in lower case.
+    pub essence: String,
+
+    /// Parameters, with their name in lower case and their value unquoted.
+    pub parameters: Vec<(String, String)>,
+}
+
+impl MediaType {
+    /// Parse a media type as defined by RFC 6838, e.g.
+    /// `video/mp4; codecs="avc1.64001f"; profiles=cmfc`.
+    pub fn parse(value: &str) -> Result<MediaType> {
+        let invalid =
+            |reason: &str| ParsingError(format!("Invalid media type \"{}\": {}", value, reason));
+        let (essence, mut rest) = match value.find(';') {
+            Some(i) => (&value[..i], &value[i + 1..]),
+            None => (value, ""),
+        };
+        let essence = essence.trim().to_ascii_lowercase();
+        let is_token = |s: &str| {
+            !s.is_empty()
+                && s.bytes()
+                    .all(|c| c.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&c))
+        };
+        match essence.split_once('/') {
+            Some((main_type, subtype)) if is_token(main_type) && is_token(subtype) => {}
+            _ => return Err(invalid("expected a \"type/subtype\" essence")),
+        }
+
+        let mut parameters = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            let name_end = rest
+                .find('=')
+                .ok_or_else(|| invalid("expected a \"name=value\" parameter"))?;
+            let name = rest[..name_end].trim().to_ascii_lowercase();
+            if !is_token(&name) {
+                return Err(invalid("invalid parameter name"));
+            }
+            rest = &rest[name_end + 1..];
+            let mut param_value = String::new();
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let mut chars = quoted.char_indices();
+                let mut end = None;
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            if let Some((_, escaped)) = chars.next() {
+                                param_value.push(escaped);
+                            }
+                        }
+                        '"' => {
+                            end = Some(i);
+                            break;
+                        }
+                        c => param_value.push(c),
+                    }
+                }
+                let end = end.ok_or_else(|| invalid("unterminated quoted parameter value"))?;
+                rest = quoted[end + 1..].trim_start();
+                match rest.strip_prefix(';') {
+                    Some(next) => rest = next,
+                    None if rest.is_empty() => {}
+                    None => return Err(invalid("expected \";\" after a parameter")),
+                }
+            } else {
+                let value_end = rest.find(';').unwrap_or(rest.len());
+                param_value.push_str(rest[..value_end].trim());
+                rest = rest.get(value_end + 1..).unwrap_or("");
+            }
+            parameters.push((name, param_value));
+        }
+        Ok(MediaType {
+            essence,
+            parameters,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_video_codecs() {
+        let avc = Codec::parse("avc1.64001f").unwrap();
+        assert_eq!(avc.family, CodecFamily::Avc);
+        assert_eq!((avc.profile, avc.level), (Some(100), Some(31)));
+        assert_eq!(avc.bit_depth, Some(8));
+        let legacy = Codec::parse("avc1.66.30").unwrap();
+        assert_eq!((legacy.profile, legacy.level), (Some(66), Some(30)));
+
+        let hevc = Codec::parse("hev1.2.4.H153.B0").unwrap();
+        assert_eq!(hevc.family, CodecFamily::Hevc);
+        assert_eq!((hevc.profile, hevc.level), (Some(2), Some(153)));
+        assert_eq!(hevc.tier, Some(Tier::High));
+        assert_eq!(hevc.bit_depth, Some(10));
+        assert_eq!(Codec::parse("hvc1.A1.6.L93").unwrap().profile, Some(1));
+
+        assert!(Codec::parse("av01.0.04M.10.0.112.09.16.09.0").is_err());
+        let av1 = Codec::parse("av01.0.04M.10.0.110.09.16.09.1").unwrap();
+        assert_eq!((av1.profile, av1.level), (Some(0), Some(4)));
+        assert_eq!(av1.tier, Some(Tier::Main));
+        assert_eq!(av1.bit_depth, Some(10));
+        assert_eq!(av1.chroma_subsampling, Some(ChromaSubsampling::Yuv420));
+        assert_eq!(av1.color_primaries, Some(9));
+        assert_eq!(av1.transfer_characteristics, Some(16));
+        assert_eq!(av1.matrix_coefficients, Some(9));
+        assert_eq!(av1.full_range, Some(true));
+        let av1 = Codec::parse("av01.1.08H.08").unwrap();
+        assert_eq!(av1.tier, Some(Tier::High));
+        assert_eq!(av1.color_primaries, Some(1));
+        assert_eq!(av1.full_range, Some(false));
+
+        let vp9 = Codec::parse("vp09.02.10.10.01.09.18.09.01").unwrap();
+        assert_eq!((vp9.profile, vp9.level), (Some(2), Some(10)));
+        assert_eq!(vp9.transfer_characteristics, Some(18));
+        assert_eq!(vp9.full_range, Some(true));
+        assert_eq!(
+            Codec::parse("vp09.00.41.08").unwrap().chroma_subsampling,
+            Some(ChromaSubsampling::Yuv420)
+        );
+
+        let dv = Codec::parse("dvh1.08.07").unwrap();
+        assert_eq!(dv.family, CodecFamily::DolbyVision);
+        assert_eq!((dv.profile, dv.level), (Some(8), Some(7)));
+
+        assert!(Codec::parse("avc1.64001").is_err());
+        assert!(Codec::parse("avc1.6400zz").is_err());
+        assert!(Codec::parse("hvc1.1.6.X93").is_err());
+        assert!(Codec::parse("av01.0.04X.10").is_err());
+        assert!(Codec::parse("vp09.00.41").is_err());
+        assert!(Codec::parse("dvh1.08").is_err());
+
+        // Non-ASCII characters are rejected instead of splitting them
+        assert!(Codec::parse("avc1.aééx").is_err());
+        assert!(Codec::parse("avc1.6é01f").is_err());
+        assert!(Codec::parse("av01.0.aé.10").is_err());
+        assert!(Codec::parse("av01.0.0é.10").is_err());
+        assert!(Codec::parse("hvc1.1.6.Lé").is_err());
+    }
+
+    #[test]
+    fn test_parse_other_codecs() {
+        let aac = Codec::parse(" mp4a.40.5 ").unwrap();
+        assert_eq!(aac.codec, "mp4a.40.5");
+        assert_eq!(aac.object_type_indication, Some(0x40));
+        assert_eq!(aac.audio_object_type, Some(5));
+        assert_eq!(
+            Codec::parse("mp4a.6B").unwrap().object_type_indication,
+            Some(0x6b)
+        );
+        assert!(Codec::parse("mp4a.40.A").is_err());
+        assert_eq!(Codec::parse("ec-3").unwrap().family, CodecFamily::Ec3);
+        assert_eq!(
+            Codec::parse("ac-4.02.01.01").unwrap().family,
+            CodecFamily::Ac4
+        );
+        assert_eq!(
+            Codec::parse("stpp.ttml.im1t").unwrap().family,
+            CodecFamily::Stpp
+        );
+        assert_eq!(Codec::parse("wvtt").unwrap().family, CodecFamily::Wvtt);
+        assert_eq!(Codec::parse("foo.1").unwrap().family, CodecFamily::Unknown);
+
+        let (codecs, errors) = parse_codecs("avc1.4d401e, mp4a.40.2,hvc1");
+        assert_eq!(codecs.len(), 2);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_supplemental_codecs() {
+        assert_eq!(
+            normalize_codec_list("  dvh1.08.07/db4h  av01.0.04M.10 ,hvc1.2.4.L93 "),
+            "dvh1.08.07/db4h, av01.0.04M.10, hvc1.2.4.L93"
+        );
+        assert_eq!(normalize_codec_list(" "), "");
+
+        let dv = Codec::parse("dvh1.08.07/db4h").unwrap();
+        assert_eq!(dv.codec, "dvh1.08.07");
+        assert_eq!((dv.profile, dv.level), (Some(8), Some(7)));
+
+        let (codecs, _) = parse_codecs("hvc1.2.4.L153.B0,mp4a.40.2");
+        let (supplemental, _) = parse_codecs("dvh1.08.07/db4h dav1.10.09");
+        let layers = pair_enhancement_layers(&codecs, &supplemental);
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].0.codec, "hvc1.2.4.L153.B0");
+        assert_eq!(layers[0].1.codec, "dvh1.08.07");
+    }
+
+    #[test]
+    fn test_parse_media_type() {
+        let media_type =
+            MediaType::parse("Video/MP4; codecs=\"avc1.64001f, mp4a.40.2\";profiles=cmfc").unwrap();
+        assert_eq!(media_type.essence, "video/mp4");
+        assert_eq!(
+            media_type.parameters,
+            vec![
+                ("codecs".to_owned(), "avc1.64001f, mp4a.40.2".to_owned()),
+                ("profiles".to_owned(), "cmfc".to_owned())
+            ]
+        );
+        assert!(MediaType::parse("audio/mp4").unwrap().parameters.is_empty());
+        assert_eq!(
+            MediaType::parse("a/b; x=\"\\\"y\\\"\"").unwrap().parameters,
+            vec![("x".to_owned(), "\"y\"".to_owned())]
+        );
+
+        assert!(MediaType::parse("video").is_err());
+        assert!(MediaType::parse("video/mp4; codecs").is_err());
+        assert!(MediaType::parse("video/mp4; codecs=\"avc1").is_err());
+    }
+}
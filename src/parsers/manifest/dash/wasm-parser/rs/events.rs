@@ -89,6 +89,20 @@ pub enum TagName {
 
     /// Indicate a <Label> node
     Label = 21,
+
+    /// Not an element of the MPD: reported just before a <Representation>
+    /// is closed when the `ResolveInheritance` parser option is enabled, it
+    /// contains the effective attributes of that <Representation> and the
+    /// <BaseURL>, <SegmentTemplate>, <SegmentBase> and <ContentProtection>
+    /// elements applying to it, including those defined on its parents.
+    EffectiveAttributes = 22,
+
+    /// Not an element of the MPD: reported just before the <MPD> is closed,
+    /// once for each <Representation> whose segments depend on the end of a
+    /// <Period> which has no `duration` attribute. It contains the
+    /// `SegmentsTarget` attribute identifying that <Representation>, followed
+    /// by its `Segments` and, if it has a thumbnail tile grid, `Thumbnails`.
+    DeferredSegments = 23,
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -142,7 +156,7 @@ pub enum AttributeName {
     // ContentProtection
     ContentProtectionValue = 13,    // String
     ContentProtectionKeyId = 14,    // ArrayBuffer
-    ContentProtectionCencPSSH = 15, // ArrayBuffer
+    ContentProtectionCencPSSH = 15, // ArrayBuffer (decoded pssh box)
     ContentProtectionRef = 78,      // String
     ContentProtectionRefId = 79,    // String
 
@@ -153,7 +167,7 @@ pub enum AttributeName {
     SchemeValue = 17, // String
 
     // SegmentURL
-    MediaRange = 18, // [f64, f64]
+    MediaRange = 18, // ByteRange
 
     // SegmentTimeline
     SegmentTimeline = 19, // Vec<SElement>
@@ -176,20 +190,20 @@ pub enum AttributeName {
     Index = 28, // String
 
     // Initialization
-    InitializationRange = 29, // [f64, f64]
+    InitializationRange = 29, // ByteRange
 
     // SegmentURL + SegmentTemplate + SegmentBase + Initialization
     Media = 30,      // String
-    IndexRange = 31, // [f64, f64]
+    IndexRange = 31, // ByteRange
 
     // Period + AdaptationSet + SegmentTemplate
     BitstreamSwitching = 32, // u8 (bool)
 
     // MPD
     Type = 33,                       // String
-    AvailabilityStartTime = 34,      // f64
-    AvailabilityEndTime = 35,        // f64
-    PublishTime = 36,                // f64
+    AvailabilityStartTime = 34,      // f64 (seconds since the Unix epoch)
+    AvailabilityEndTime = 35,        // f64 (seconds since the Unix epoch)
+    PublishTime = 36,                // f64 (seconds since the Unix epoch)
     MinimumUpdatePeriod = 37,        // f64
     MinBufferTime = 38,              // f64
     TimeShiftBufferDepth = 39,       // f64
@@ -284,7 +298,304 @@ pub enum AttributeName {
     // SegmentTemplate
     EndNumber = 76, // f64
 
+    /// Codecs of the "scte214:supplementalCodecs" attribute, converted from
+    /// their white space-separated form into the comma-separated form of
+    /// RFC 6381.
     SupplementalCodecs = 77, // string
+
+    /// Describes the start, in the corresponding timescale, of the first
+    /// segment in common between a `<SegmentTimeline>` and the timeline
+    /// communicated for it through `add_previous_timeline`.
+    ///
+    /// It is reported as an f64, just before the corresponding
+    /// `SegmentTimelineUpdate` attribute.
+    SegmentTimelineFirstCommonStart = 80,
+
+    /// Reported instead of `SegmentTimeline` when a `<SegmentTimeline>` could
+    /// be merged with the timeline communicated for it through
+    /// `add_previous_timeline`. It never is when it starts before that
+    /// timeline.
+    ///
+    /// It has the same format than `SegmentTimeline` but only contains the
+    /// entries which are new or which changed since the previous timeline.
+    ///
+    /// The merged timeline can be obtained by:
+    ///   1. removing from the previous timeline all segments starting before
+    ///      `SegmentTimelineFirstCommonStart`
+    ///   2. removing the entries starting at or after the start of the first
+    ///      entry reported here
+    ///   3. adding all entries reported here at its end
+    SegmentTimelineUpdate = 81,
+
+    /// Describes the segments of a `<Representation>`, computed from the
+    /// `<SegmentTemplate>` applying to it, in the time range communicated
+    /// through `set_segment_range`.
+    ///
+    /// It is reported just before the `<Representation>` is closed or, when
+    /// those segments depend on the end of a `<Period>` which has no
+    /// `duration` attribute, in a `DeferredSegments` tag once the whole MPD
+    /// has been read. It is reported as the concatenation of each segment's
+    /// information:
+    ///
+    ///   - Its number, as an f64
+    ///
+    ///   - Its start in seconds relative to the start of the Period, as an
+    ///     f64
+    ///
+    ///   - Its end in seconds relative to the start of the Period, as an f64
+    ///
+    ///   - The length of its URL, as a big endian unsigned 32 bit integer. `0`
+    ///     if the `<SegmentTemplate>` has no `media` attribute.
+    ///
+    ///   - Its URL, with all identifiers replaced, as an UTF-8 encoded string.
+    ///     The length of this URL is indicated by the preceding four bytes.
+    Segments = 82,
+
+    /// Describes the URL of the initialization segment of a
+    /// `<Representation>`, computed from the `initialization` attribute of the
+    /// `<SegmentTemplate>` applying to it with all identifiers replaced.
+    ///
+    /// It is reported as an UTF-8 sequence of bytes, with the `Segments`
+    /// attribute.
+    SegmentsInitializationUrl = 83,
+
+    /// Describes the absolute URLs obtained by resolving the text of a
+    /// `<BaseURL>` element against each URL of its parent elements, when the
+    /// `ResolveBaseUrls` parser option is enabled.
+    ///
+    /// It is reported just before the `<BaseURL>` is closed, as the
+    /// concatenation of, for each URL, its length as a big endian unsigned 32
+    /// bit integer followed by the URL as an UTF-8 encoded string.
+    ///
+    /// URLs which stay relative, e.g. because the URL of the MPD is unknown,
+    /// are not part of it. It is not reported if none is absolute.
+    ResolvedBaseUrls = 84,
+
+    /// Describes the text of a `<Location>` element resolved against the URL
+    /// of the MPD, when the `ResolveBaseUrls` parser option is enabled.
+    ///
+    /// It is reported as an UTF-8 sequence of bytes, just after the
+    /// corresponding `Location` attribute, unless it stays relative (e.g.
+    /// because the URL of the MPD is unknown).
+    ResolvedLocation = 85,
+
+    /// Describes the fields of the `pssh` box found in a `<cenc:pssh>`
+    /// element, reported just after the corresponding
+    /// `ContentProtectionCencPSSH` attribute when that box could be parsed.
+    ///
+    /// It is reported as the concatenation of:
+    ///
+    ///   - The version of the box, as an u8
+    ///
+    ///   - Its SystemID, as 16 bytes
+    ///
+    ///   - The number of KIDs in it (always `0` for version `0` boxes), as a
+    ///     big endian unsigned 32 bit integer
+    ///
+    ///   - Each of those KIDs, as 16 bytes
+    ///
+    ///   - The size of the box's data, as a big endian unsigned 32 bit integer
+    ///
+    ///   - The box's data itself
+    ContentProtectionPssh = 86,
+
+    /// Describes the key IDs found in the PlayReady Header of a `<mspr:pro>`
+    /// element, converted to big endian UUIDs.
+    ///
+    /// It is reported as the concatenation of those key IDs, as 16 bytes
+    /// each.
+    ContentProtectionPlayReadyKeyIds = 87,
+
+    /// Describes the license server URL (`LA_URL`) found in the PlayReady
+    /// Header of a `<mspr:pro>` element.
+    ///
+    /// It is reported as an UTF-8 sequence of bytes.
+    ContentProtectionPlayReadyLaUrl = 88,
+
+    /// Describes the URL of a license server announced in a
+    /// `<ContentProtection>` through a `<dashif:Laurl>`, `<clearkey:Laurl>`
+    /// or `<ms:laurl>` element.
+    ///
+    /// It is reported as an UTF-8 sequence of bytes.
+    ContentProtectionLicenseUrl = 89,
+
+    /// Describes the type of license served by the license server announced
+    /// through the next `ContentProtectionLicenseUrl` attribute, if known.
+    ///
+    /// It is reported as an UTF-8 sequence of bytes, just before that
+    /// attribute, and only applies to it.
+    ContentProtectionLicenseType = 90,
+
+    /// Reported just before a `<ContentProtection>` element referencing
+    /// another one through its `ref` attribute is closed, once the
+    /// information it inherits from it has been reported as if it was defined
+    /// on the element itself.
+    ///
+    /// It is not reported when the referenced element is defined after it in
+    /// the `<Period>`, that reference then having to be resolved once the
+    /// whole `<Period>` is known.
+    ///
+    /// It is reported as a single byte set to `1`.
+    ContentProtectionRefResolved = 91,
+
+    /// Describes the "frameRate" attribute of `<AdaptationSet>` and
+    /// `<Representation>` elements as the ratio it is written as, so it can
+    /// be compared exactly (`FrameRate` being its value as a float).
+    ///
+    /// A frame rate written as a decimal number is converted into a division
+    /// by a power of ten.
+    ///
+    /// It is reported as its numerator and denominator, both as f64.
+    FrameRateRatio = 92,
+
+    /// Describes the "minFrameRate" attribute of `<AdaptationSet>` elements,
+    /// in the same way than `FrameRateRatio`.
+    MinFrameRateRatio = 93,
+
+    /// Describes the "maxFrameRate" attribute of `<AdaptationSet>` elements,
+    /// in the same way than `FrameRateRatio`.
+    MaxFrameRateRatio = 94,
+
+    /// Describes the "par" attribute of `<AdaptationSet>` and
+    /// `<ContentComponent>` elements, the picture aspect ratio, as parsed from
+    /// its `W:H` form (`Par` being the original string).
+    ///
+    /// It is reported as its width and height, both as f64.
+    ParRatio = 95,
+
+    /// Describes the "sar" attribute of `<AdaptationSet>` and
+    /// `<Representation>` elements, the sample aspect ratio, as parsed from
+    /// its `W:H` form.
+    ///
+    /// It is reported as its width and height, both as f64.
+    Sar = 96,
+
+    /// Describes the codecs of the "codecs" attribute of `<AdaptationSet>`
+    /// and `<Representation>` elements, parsed as RFC 6381 codec strings.
+    /// Codec strings which could not be parsed are not included.
+    ///
+    /// It is reported as the concatenation of, for each codec:
+    ///
+    ///   - The length of its codec string, as a big endian u32, followed by
+    ///     that string in UTF-8
+    ///
+    ///   - Its family, as an u8 (@see `codecs::CodecFamily`)
+    ///
+    ///   - Its profile, level, tier, bit depth, chroma subsampling, color
+    ///     primaries, transfer characteristics, matrix coefficients, full
+    ///     range flag, object type indication and audio object type, in that
+    ///     order, each as a big endian u32 set to `0xFFFFFFFF` if unknown
+    ///     (@see `codecs::Codec`)
+    ParsedCodecs = 97,
+
+    /// Describes the codecs of the "scte214:supplementalCodecs" attribute, in
+    /// the same way than `ParsedCodecs`.
+    ParsedSupplementalCodecs = 98,
+
+    /// Describes the parameters of the "mimeType" attribute of
+    /// `<AdaptationSet>` and `<Representation>` elements (e.g. `codecs` in
+    /// `video/mp4; codecs="avc1.64001f"`), with their name in lower case and
+    /// their value unquoted.
+    ///
+    /// It is reported as the concatenation of each parameter's name then
+    /// value, each prefixed by its length as a big endian u32.
+    MimeTypeParameters = 99,
+
+    /// Describes the dynamic range of a video `<Representation>`, as deduced
+    /// from its codecs and from the CICP `EssentialProperty` and
+    /// `SupplementalProperty` descriptors found on it or on its parents.
+    /// It is reported just before that `<Representation>` is closed.
+    ///
+    /// It is reported as four u8: its dynamic range (@see
+    /// `hdr::DynamicRange`), its color space (@see `hdr::ColorSpace`), its
+    /// bit depth and its transfer characteristics, each but the first set to
+    /// `255` if unknown.
+    HdrInformation = 100,
+
+    /// Describes the codecs of a `<Representation>` which can be enhanced by
+    /// one of its supplemental codecs, e.g. an HEVC base layer enhanced by a
+    /// Dolby Vision layer, taking into account the "codecs" and
+    /// "scte214:supplementalCodecs" attributes of its parents.
+    /// It is reported just before that `<Representation>` is closed.
+    ///
+    /// It is reported in the same way than `ParsedCodecs`, each base layer's
+    /// codec being followed by the codec of its enhancement layer.
+    CodecLayers = 101,
+
+    /// Type inferred for an `<AdaptationSet>` from the `mimeType` and
+    /// `codecs` attributes of it and of its `<Representation>` children, its
+    /// roles and, as a last resort, its `contentType` attribute, as a single
+    /// byte (@see `track_type::TrackType`).
+    ///
+    /// Not reported if no type could be inferred.
+    InferredTrackType = 102,
+
+    /// Roles of an `<AdaptationSet>` with the `urn:mpeg:dash:role:2011`
+    /// scheme, normalized, sorted and without duplicates, each as a single
+    /// byte (@see `track_type::Role`).
+    NormalizedRoles = 103,
+
+    /// Caption channels announced by an `<Accessibility>` element with the
+    /// CEA-608 or CEA-708 scheme of SCTE 214-1, with their language and
+    /// flags (@see `accessibility::CaptionServices`).
+    CaptionServices = 104,
+
+    /// Flags set by an `<Accessibility>` element through the AudioPurposeCS
+    /// classification or the DASH role scheme, as a single byte (@see
+    /// `accessibility::AUDIO_DESCRIPTION` and
+    /// `accessibility::HARD_OF_HEARING`).
+    ///
+    /// Only reported if one of them is set.
+    AccessibilityPurpose = 105,
+
+    /// Grid of thumbnails in each image segment, announced by an
+    /// `<EssentialProperty>` element with the DASH-IF `thumbnail_tile`
+    /// scheme, as its number of columns and rows.
+    ThumbnailTile = 106,
+
+    /// Thumbnails contained in the segments reported through `Segments`, for
+    /// `<Representation>` elements having a thumbnail tile grid (@see
+    /// `thumbnails::Thumbnail`).
+    ///
+    /// As their timing comes from those segments, they are only reported when
+    /// a time range has been set through `set_segment_range` and a
+    /// `<SegmentTemplate>` applies to that `<Representation>`. An error is
+    /// reported in the latter case. The grid itself is always reported
+    /// through `ThumbnailTile`.
+    Thumbnails = 107,
+
+    /// Links between the trick mode `<AdaptationSet>` elements of a
+    /// `<Period>` and the `<AdaptationSet>` elements they are associated to,
+    /// reported just before the `<Period>` is closed, as couples of their
+    /// index in the `<Period>`, each as a big endian u32.
+    TrickModeLinks = 108,
+
+    /// Schemes of the `<EssentialProperty>` elements of an `<AdaptationSet>`
+    /// or `<Representation>` which are not supported by the host, reported
+    /// just before it is closed. That element should then be ignored.
+    ///
+    /// Each scheme is reported in document order, as its length in bytes as a
+    /// big endian u32 followed by its UTF-8 bytes.
+    ///
+    /// Only reported when supported schemes have been registered. The trick
+    /// mode and thumbnail tile schemes, understood by the parser, are always
+    /// considered as supported.
+    UnsupportedEssentialProperties = 109,
+
+    /// Time bounds of each `<Period>`, in document order, reported just
+    /// before the `<MPD>` is closed (@see `periods::ResolvedPeriod`).
+    ///
+    /// Overlapping `<Period>` elements are only flattened when the
+    /// `FlattenOverlappingPeriods` option is enabled. Not reported if the
+    /// start of a `<Period>` cannot be known or if a `<Period>` has to be
+    /// fetched through an xlink.
+    ResolvedPeriodTimes = 110,
+
+    /// The `<Representation>` to which the segments reported in a
+    /// `DeferredSegments` tag belong, as the index of its `<Period>` in the
+    /// MPD, of its `<AdaptationSet>` in that `<Period>` and its own index in
+    /// that `<AdaptationSet>`, each as a big endian u32.
+    SegmentsTarget = 111,
 }
 
 impl TagName {
@@ -305,8 +616,11 @@ impl TagName {
     }
 }
 
+use crate::codecs::{self, MediaType};
 use crate::reportable::ReportableAttribute;
+use crate::url_template::UrlTemplate;
 use crate::utils;
+use std::borrow::Cow;
 
 impl AttributeName {
     #[inline(always)]
@@ -321,6 +635,36 @@ impl AttributeName {
         }
     }
 
+    /// Report the attribute as a string after checking that it is a valid URL
+    /// template.
+    ///
+    /// The original string is reported even if the template is invalid, the
+    /// corresponding error being reported first.
+    ///
+    /// Returns the compiled template if it is valid.
+    pub fn try_report_as_url_template(
+        self,
+        attr: &quick_xml::events::attributes::Attribute,
+    ) -> Option<UrlTemplate> {
+        match attr.unescape_value() {
+            Ok(val) => {
+                let compiled = match UrlTemplate::compile(&val) {
+                    Ok(compiled) => Some(compiled),
+                    Err(error) => {
+                        error.report_err();
+                        None
+                    }
+                };
+                self.report(val);
+                compiled
+            }
+            Err(_) => {
+                ParsingError("Could not escape original value".to_owned()).report_err();
+                None
+            }
+        }
+    }
+
     pub fn try_report_as_f64(self, attr: &quick_xml::events::attributes::Attribute) {
         match utils::parse_f64(&attr.value) {
             Ok(val) => self.report(val),
@@ -335,13 +679,118 @@ impl AttributeName {
         }
     }
 
-    pub fn try_report_as_maybe_division(self, attr: &quick_xml::events::attributes::Attribute) {
-        match utils::parse_maybe_division(&attr.value) {
+    /// Report the attribute as the number of seconds since the Unix epoch
+    /// corresponding to the ISO 8601 date-time it contains.
+    ///
+    /// `position` is the position in the MPD of the end of the opening tag of
+    /// the element it is in, used to locate invalid values.
+    pub fn try_report_as_date_time(
+        self,
+        attr: &quick_xml::events::attributes::Attribute,
+        position: usize,
+    ) {
+        match utils::parse_date_time(&attr.value) {
             Ok(val) => self.report(val),
+            Err(error) => ParsingError(format!(
+                "Invalid {} in the element ending at position {}: {}",
+                String::from_utf8_lossy(attr.key.as_ref()),
+                position,
+                error.0
+            ))
+            .report_err(),
+        }
+    }
+
+    /// Report the attribute as a frame rate: as a float under this
+    /// `AttributeName` and as the ratio it is written as under `ratio_name`.
+    pub fn try_report_as_frame_rate(
+        self,
+        ratio_name: AttributeName,
+        attr: &quick_xml::events::attributes::Attribute,
+    ) {
+        match utils::parse_frame_rate(&attr.value) {
+            Ok(val) => {
+                self.report(val.as_f64());
+                ratio_name.report((val.numerator as f64, val.denominator as f64));
+            }
             Err(error) => error.report_err(),
         }
     }
 
+    pub fn try_report_as_aspect_ratio(self, attr: &quick_xml::events::attributes::Attribute) {
+        match utils::parse_aspect_ratio(&attr.value) {
+            Ok(val) => self.report((val.numerator as f64, val.denominator as f64)),
+            Err(error) => error.report_err(),
+        }
+    }
+
+    /// Report the attribute as a string under this `AttributeName`, then as
+    /// a list of parsed RFC 6381 codec strings under `parsed_name`.
+    pub fn try_report_as_codecs(
+        self,
+        parsed_name: AttributeName,
+        attr: &quick_xml::events::attributes::Attribute,
+    ) {
+        match attr.unescape_value() {
+            Ok(val) => {
+                let (codecs, errors) = codecs::parse_codecs(&val);
+                errors.iter().for_each(ParsingError::report_err);
+                self.report(val);
+                parsed_name.report(codecs.as_slice());
+            }
+            Err(_) => ParsingError("Could not escape original value".to_owned()).report_err(),
+        }
+    }
+
+    /// Report the attribute, a SCTE 214 list of supplemental codecs, as a
+    /// comma-separated list of codec strings under this `AttributeName`, then
+    /// as a list of parsed codec strings under `parsed_name`.
+    pub fn try_report_as_supplemental_codecs(
+        self,
+        parsed_name: AttributeName,
+        attr: &quick_xml::events::attributes::Attribute,
+    ) {
+        match attr.unescape_value() {
+            Ok(val) => {
+                let (codecs, errors) = codecs::parse_codecs(&val);
+                errors.iter().for_each(ParsingError::report_err);
+                self.report(Cow::<str>::Owned(codecs::normalize_codec_list(&val)));
+                parsed_name.report(codecs.as_slice());
+            }
+            Err(_) => ParsingError("Could not escape original value".to_owned()).report_err(),
+        }
+    }
+
+    /// Report the attribute as a string under this `AttributeName`, then the
+    /// parameters of the media type it contains under `parameters_name`, if
+    /// it has any.
+    pub fn try_report_as_media_type(
+        self,
+        parameters_name: AttributeName,
+        attr: &quick_xml::events::attributes::Attribute,
+    ) {
+        match attr.unescape_value() {
+            Ok(val) => {
+                let parameters = match MediaType::parse(&val) {
+                    Ok(media_type) => media_type.parameters,
+                    Err(err) => {
+                        err.report_err();
+                        Vec::new()
+                    }
+                };
+                self.report(val);
+                if !parameters.is_empty() {
+                    let flattened: Vec<String> = parameters
+                        .into_iter()
+                        .flat_map(|(name, value)| [name, value])
+                        .collect();
+                    parameters_name.report(flattened.as_slice());
+                }
+            }
+            Err(_) => ParsingError("Could not escape original value".to_owned()).report_err(),
+        }
+    }
+
     pub fn try_report_as_u64(self, attr: &quick_xml::events::attributes::Attribute) {
         match utils::parse_u64(&attr.value) {
             Ok(val) => self.report(val as f64),
@@ -0,0 +1,320 @@
+use crate::codecs::{Codec, CodecFamily};
+use crate::errors::{ParsingError, Result};
+
+/// `schemeIdUri` of the descriptors announcing the color primaries of a
+/// Representation, as defined by ISO/IEC 23091-4.
+const CICP_COLOUR_PRIMARIES: &str = "urn:mpeg:mpegB:cicp:ColourPrimaries";
+
+/// `schemeIdUri` of the descriptors announcing the transfer characteristics
+/// of a Representation.
+const CICP_TRANSFER_CHARACTERISTICS: &str = "urn:mpeg:mpegB:cicp:TransferCharacteristics";
+
+/// `schemeIdUri` of the descriptors announcing the matrix coefficients of a
+/// Representation.
+const CICP_MATRIX_COEFFICIENTS: &str = "urn:mpeg:mpegB:cicp:MatrixCoefficients";
+
+/// `schemeIdUri` of the DVB descriptors announcing HDR dynamic metadata,
+/// `ST2094-40` being the value used for HDR10+.
+const HDR_DYNAMIC_METADATA: &str = "urn:dvb:dash:hdr-dmi";
+const HDR10_PLUS_METADATA: &str = "ST2094-40";
+
+/// DASH-IF profile for HEVC HDR10 content.
+const DASH_IF_HEVC_HDR_PQ10: &str = "http://dashif.org/guidelines/dash-if-uhd#hevc-hdr-pq10";
+
+/// Transfer characteristics of the Perceptual Quantizer (SMPTE ST 2084).
+const TRANSFER_PQ: u32 = 16;
+
+/// Transfer characteristics of the Hybrid Log-Gamma (ARIB STD-B67).
+const TRANSFER_HLG: u32 = 18;
+
+/// Color primaries and matrix coefficients of BT.709 and BT.2020.
+const CICP_BT709: u32 = 1;
+const CICP_BT2020: u32 = 9;
+const CICP_BT2020_CONSTANT_LUMINANCE: u32 = 10;
+
+/// Dynamic range format of a video Representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum DynamicRange {
+    Sdr = 0,
+    Hdr10 = 1,
+    Hlg = 2,
+    DolbyVision = 3,
+    Hdr10Plus = 4,
+}
+
+/// Color space of a video Representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ColorSpace {
+    Rec709 = 0,
+    Rec2020 = 1,
+}
+
+/// Result of the classification of a video Representation's dynamic range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrInformation {
+    pub dynamic_range: DynamicRange,
+
+    /// `None` when the color space is neither BT.709 nor BT.2020, or if it is
+    /// unknown.
+    pub color_space: Option<ColorSpace>,
+
+    pub bit_depth: Option<u32>,
+
+    /// Transfer characteristics, as defined by ISO/IEC 23091-4.
+    pub transfer_characteristics: Option<u32>,
+}
+
+/// Information relevant to the dynamic range of a Representation, found
+/// either on the `<MPD>`, on an `<AdaptationSet>` or on a `<Representation>`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VideoDescriptors {
+    /// Codecs of the `codecs` attribute.
+    pub codecs: Vec<Codec>,
+
+    /// Codecs of the `scte214:supplementalCodecs` attribute.
+    pub supplemental_codecs: Vec<Codec>,
+
+    /// `true` if the `mimeType` attribute announces video, `None` without
+    /// that attribute.
+    pub video_mime_type: Option<bool>,
+
+    /// Values of the CICP `EssentialProperty` and `SupplementalProperty`
+    /// descriptors.
+    pub color_primaries: Option<u32>,
+    pub transfer_characteristics: Option<u32>,
+    pub matrix_coefficients: Option<u32>,
+
+    /// `true` if an HDR10+ dynamic metadata descriptor has been found.
+    pub hdr10_plus_metadata: bool,
+
+    /// `true` if the `profiles` attribute contains the DASH-IF HEVC HDR10
+    /// profile.
+    pub hevc_hdr_pq10_profile: bool,
+}
+
+impl VideoDescriptors {
+    /// Takes into account a `mimeType` attribute.
+    pub fn set_mime_type(&mut self, mime_type: &str) {
+        self.video_mime_type = Some(mime_type.trim_start().starts_with("video/"));
+    }
+
+    /// Takes into account a `profiles` attribute.
+    pub fn set_profiles(&mut self, profiles: &str) {
+        self.hevc_hdr_pq10_profile = profiles
+            .split(',')
+            .any(|profile| profile.trim() == DASH_IF_HEVC_HDR_PQ10);
+    }
+
+    /// Takes into account an `<EssentialProperty>` or `<SupplementalProperty>`
+    /// descriptor. Descriptors with another scheme are ignored.
+    ///
+    /// Returns an error if a CICP descriptor has an invalid value.
+    pub fn add_property(&mut self, scheme_id_uri: &str, value: Option<&str>) -> Result<()> {
+        let field = match scheme_id_uri {
+            CICP_COLOUR_PRIMARIES => &mut self.color_primaries,
+            CICP_TRANSFER_CHARACTERISTICS => &mut self.transfer_characteristics,
+            CICP_MATRIX_COEFFICIENTS => &mut self.matrix_coefficients,
+            HDR_DYNAMIC_METADATA => {
+                if value.map(str::trim) == Some(HDR10_PLUS_METADATA) {
+                    self.hdr10_plus_metadata = true;
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        let value = value.unwrap_or_default();
+        match value.trim().parse::<u8>() {
+            Ok(parsed) => {
+                *field = Some(parsed as u32);
+                Ok(())
+            }
+            Err(_) => Err(ParsingError(format!(
+                "Invalid value for the {} descriptor: \"{}\"",
+                scheme_id_uri, value
+            ))),
+        }
+    }
+
+    /// Combines those descriptors with those of a `parent` element, the
+    /// ones defined here taking precedence.
+    pub fn inherit(&self, parent: &VideoDescriptors) -> VideoDescriptors {
+        let pick = |own: &Vec<Codec>, inherited: &Vec<Codec>| {
+            if own.is_empty() {
+                inherited.clone()
+            } else {
+                own.clone()
+            }
+        };
+        VideoDescriptors {
+            codecs: pick(&self.codecs, &parent.codecs),
+            supplemental_codecs: pick(&self.supplemental_codecs, &parent.supplemental_codecs),
+            video_mime_type: self.video_mime_type.or(parent.video_mime_type),
+            color_primaries: self.color_primaries.or(parent.color_primaries),
+            transfer_characteristics: self
+                .transfer_characteristics
+                .or(parent.transfer_characteristics),
+            matrix_coefficients: self.matrix_coefficients.or(parent.matrix_coefficients),
+            hdr10_plus_metadata: self.hdr10_plus_metadata || parent.hdr10_plus_metadata,
+            hevc_hdr_pq10_profile: self.hevc_hdr_pq10_profile || parent.hevc_hdr_pq10_profile,
+        }
+    }
+
+    /// Classifies the dynamic range of a Representation described by those
+    /// descriptors, once inherited from its parents.
+    ///
+    /// CICP descriptors take precedence over the color information of codec
+    /// strings. Returns `None` if it does not seem to be a video
+    /// Representation.
+    pub fn classify(&self) -> Option<HdrInformation> {
+        let all_codecs = || self.codecs.iter().chain(self.supplemental_codecs.iter());
+        let has_video_codec = all_codecs().any(|c| c.family.is_video());
+        if !has_video_codec && self.video_mime_type != Some(true) {
+            return None;
+        }
+        let hdr10_profile = self.hevc_hdr_pq10_profile
+            && self
+                .codecs
+                .iter()
+                .any(|c| c.family == CodecFamily::Hevc && c.bit_depth == Some(10));
+        let transfer_characteristics = self
+            .transfer_characteristics
+            .or_else(|| all_codecs().find_map(|c| c.transfer_characteristics))
+            .or(if hdr10_profile {
+                Some(TRANSFER_PQ)
+            } else {
+                None
+            });
+        let color_primaries = self
+            .color_primaries
+            .or_else(|| all_codecs().find_map(|c| c.color_primaries))
+            .or(if hdr10_profile {
+                Some(CICP_BT2020)
+            } else {
+                None
+            });
+        let matrix_coefficients = self
+            .matrix_coefficients
+            .or_else(|| all_codecs().find_map(|c| c.matrix_coefficients));
+
+        let dynamic_range = if all_codecs().any(|c| c.family == CodecFamily::DolbyVision) {
+            DynamicRange::DolbyVision
+        } else {
+            match transfer_characteristics {
+                Some(TRANSFER_PQ) if self.hdr10_plus_metadata => DynamicRange::Hdr10Plus,
+                Some(TRANSFER_PQ) => DynamicRange::Hdr10,
+                Some(TRANSFER_HLG) => DynamicRange::Hlg,
+                _ => DynamicRange::Sdr,
+            }
+        };
+        let color_space = match color_primaries.or(matrix_coefficients) {
+            Some(CICP_BT709) => Some(ColorSpace::Rec709),
+            Some(CICP_BT2020 | CICP_BT2020_CONSTANT_LUMINANCE) => Some(ColorSpace::Rec2020),
+            _ => None,
+        };
+        Some(HdrInformation {
+            dynamic_range,
+            color_space,
+            bit_depth: all_codecs().find_map(|c| c.bit_depth),
+            transfer_characteristics,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs;
+
+    fn descriptors(codecs: &str, properties: &[(&str, &str)]) -> VideoDescriptors {
+        let mut descriptors = VideoDescriptors {
+            codecs: codecs::parse_codecs(codecs).0,
+            ..Default::default()
+        };
+        for (scheme_id_uri, value) in properties {
+            descriptors
+                .add_property(scheme_id_uri, Some(value))
+                .unwrap();
+        }
+        descriptors
+    }
+
+    #[test]
+    fn test_classify_from_codecs() {
+        let sdr = descriptors("avc1.64001f", &[]).classify().unwrap();
+        assert_eq!(sdr.dynamic_range, DynamicRange::Sdr);
+        assert_eq!((sdr.color_space, sdr.bit_depth), (None, Some(8)));
+
+        let hlg = descriptors("vp09.02.10.10.01.09.18.09.00", &[])
+            .classify()
+            .unwrap();
+        assert_eq!(hlg.dynamic_range, DynamicRange::Hlg);
+        assert_eq!(hlg.color_space, Some(ColorSpace::Rec2020));
+        assert_eq!(hlg.bit_depth, Some(10));
+
+        let dv = descriptors("dvh1.08.07", &[]).classify().unwrap();
+        assert_eq!(dv.dynamic_range, DynamicRange::DolbyVision);
+
+        assert_eq!(descriptors("mp4a.40.2", &[]).classify(), None);
+    }
+
+    #[test]
+    fn test_classify_from_cicp_descriptors() {
+        let cicp = [
+            (CICP_COLOUR_PRIMARIES, "9"),
+            (CICP_TRANSFER_CHARACTERISTICS, "16"),
+        ];
+        let hdr10 = descriptors("hvc1.2.4.L153.B0", &cicp).classify().unwrap();
+        assert_eq!(hdr10.dynamic_range, DynamicRange::Hdr10);
+        assert_eq!(hdr10.color_space, Some(ColorSpace::Rec2020));
+        assert_eq!(hdr10.transfer_characteristics, Some(16));
+
+        let mut hdr10_plus = descriptors("hvc1.2.4.L153.B0", &cicp);
+        hdr10_plus
+            .add_property(HDR_DYNAMIC_METADATA, Some("ST2094-40"))
+            .unwrap();
+        let hdr10_plus = hdr10_plus.classify().unwrap();
+        assert_eq!(hdr10_plus.dynamic_range, DynamicRange::Hdr10Plus);
+
+        // CICP descriptors override codec strings
+        let sdr = descriptors(
+            "av01.0.04M.10.0.111.09.16.09.0",
+            &[(CICP_TRANSFER_CHARACTERISTICS, "1")],
+        );
+        assert_eq!(sdr.classify().unwrap().dynamic_range, DynamicRange::Sdr);
+
+        let mut invalid = VideoDescriptors::default();
+        assert!(invalid
+            .add_property(CICP_COLOUR_PRIMARIES, Some("BT.2020"))
+            .is_err());
+        assert!(invalid
+            .add_property(CICP_MATRIX_COEFFICIENTS, None)
+            .is_err());
+        assert!(invalid.add_property("urn:other", Some("BT.2020")).is_ok());
+    }
+
+    #[test]
+    fn test_inherit_descriptors() {
+        let mut adaptation_set =
+            descriptors("hvc1.2.4.L153.B0", &[(CICP_TRANSFER_CHARACTERISTICS, "18")]);
+        adaptation_set.set_mime_type("video/mp4");
+        let representation = descriptors("", &[(CICP_COLOUR_PRIMARIES, "1")]);
+        let hlg = representation.inherit(&adaptation_set).classify().unwrap();
+        assert_eq!(hlg.dynamic_range, DynamicRange::Hlg);
+        assert_eq!(hlg.color_space, Some(ColorSpace::Rec709));
+
+        let mut mpd = VideoDescriptors::default();
+        mpd.set_profiles(&format!(
+            "urn:mpeg:dash:profile:isoff-live:2011, {}",
+            DASH_IF_HEVC_HDR_PQ10
+        ));
+        let representation = descriptors("hvc1.2.4.L153.B0", &[]);
+        let pq10 = representation.inherit(&mpd).classify().unwrap();
+        assert_eq!(pq10.dynamic_range, DynamicRange::Hdr10);
+        assert_eq!(
+            (pq10.color_space, pq10.bit_depth),
+            (Some(ColorSpace::Rec2020), Some(10))
+        );
+    }
+}
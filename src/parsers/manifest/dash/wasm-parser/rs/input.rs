@@ -0,0 +1,25 @@
+use std::cell::RefCell;
+
+thread_local! {
+    /// Buffer in which the JS-side can write data which has to be communicated
+    /// to the parser, for the functions which need more than a few integers.
+    static INPUT_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reset the input buffer to `len` zeroed bytes and return a pointer to its
+/// first byte, in WebAssembly's linear memory.
+///
+/// That pointer stays valid until the next `reserve` or `take` call.
+pub fn reserve(len: usize) -> *mut u8 {
+    INPUT_BUFFER.with(|buf| {
+        let mut buf = buf.borrow_mut();
+        buf.clear();
+        buf.resize(len, 0);
+        buf.as_mut_ptr()
+    })
+}
+
+/// Move the current content of the input buffer out of it, leaving it empty.
+pub fn take() -> Vec<u8> {
+    INPUT_BUFFER.with(|buf| std::mem::take(&mut *buf.borrow_mut()))
+}
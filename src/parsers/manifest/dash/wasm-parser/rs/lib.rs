@@ -1,11 +1,24 @@
 extern crate core;
 extern crate quick_xml;
 
+mod accessibility;
+mod codecs;
 mod errors;
 mod events;
+mod hdr;
+mod input;
+mod options;
+mod periods;
+mod playready;
 mod processor;
+mod pssh;
 mod reader;
 mod reportable;
+mod segment_index;
+mod thumbnails;
+mod track_type;
+mod url;
+mod url_template;
 mod utils;
 
 pub use errors::{ParsingError, Result};
@@ -40,10 +53,10 @@ extern "C" {
     /// # Arguments
     ///
     /// * `attr_name` - u8 describing the name of the attribute that has just
-    /// been encountered.
+    ///   been encountered.
     ///
     /// * `ptr` - Pointer to the beginning of the corresponding data in the
-    /// WebAssembly's linear memory.
+    ///   WebAssembly's linear memory.
     ///
     /// * `len` - Length of the data - starting at `ptr` - in bytes.
     fn onAttribute(attr_name: AttributeName, ptr: *const u8, len: usize);
@@ -55,7 +68,7 @@ extern "C" {
     /// * `evt_type` - Identify the type of event that is wanted.
     ///
     /// * `ptr` - Pointer to the beginning of the corresponding data in the
-    /// WebAssembly's linear memory.
+    ///   WebAssembly's linear memory.
     ///
     /// * `len` - Length of the data - starting at `ptr` - in bytes.
     fn onCustomEvent(evt_type: CustomEventType, ptr: *const u8, len: usize);
@@ -69,17 +82,163 @@ extern "C" {
     /// # Arguments
     ///
     /// * `ptr` - Pointer to where the MPD data should be set, in WebAssembly's
-    /// linear memory.
+    ///   linear memory.
     ///
     /// * `size` - Optimal length of data that is wanted, in bytes.
-    /// Less data (but not more) can be read. The true read length is returned
-    /// by this function.
+    ///   Less data (but not more) can be read. The true read length is returned
+    ///   by this function.
     fn readNext(ptr: *const u8, size: usize) -> usize;
 }
 
 #[no_mangle]
 pub extern "C" fn parse() {
     let buf_read = BufReader::new(MPDReader {});
-    let mut processor = MPDProcessor::new(buf_read);
+    let mut processor = MPDProcessor::new(buf_read, options::for_next_parse());
     processor.process_tags();
 }
+
+/// Configure one of the optional processing steps of the parser, for all
+/// subsequent `parse` calls.
+///
+/// # Arguments
+///
+/// * `option` - u8 describing the option to update, as defined by the
+///   `ParserOption` enum (@see options).
+///
+/// * `value` - New value for that option. For options which can only be
+///   enabled or disabled, `0` disables it and any other value enables it.
+#[no_mangle]
+pub extern "C" fn set_parser_option(option: u8, value: u32) {
+    match options::ParserOption::from_u8(option) {
+        Ok(option) => options::set(option, value),
+        Err(err) => err.report_err(),
+    }
+}
+
+/// Ask for the segments of each `<Representation>` relying on a
+/// `<SegmentTemplate>` to be reported (@see `AttributeName::Segments`) for
+/// all subsequent `parse` calls, as well as the thumbnails they contain
+/// (@see `AttributeName::Thumbnails`).
+///
+/// Only segments overlapping the given time range are reported.
+/// Setting `from` to a value greater than `to` (or to `NaN`) disables it.
+///
+/// Segments depending on the end of a `<Period>` which has no `duration`
+/// attribute are reported once the whole MPD has been read (@see
+/// `TagName::DeferredSegments`).
+///
+/// # Arguments
+///
+/// * `from` - Start of the wanted time range (included), in seconds relative
+///   to the start of the Period.
+///
+/// * `to` - End of the wanted time range (not included), in seconds relative
+///   to the start of the Period. Can be set to `Infinity`.
+#[no_mangle]
+pub extern "C" fn set_segment_range(from: f64, to: f64) {
+    if from <= to {
+        options::set_segment_range(Some((from, to)));
+    } else {
+        options::set_segment_range(None);
+    }
+}
+
+/// Reserve `len` bytes in which the JS-side can write the data needed by the
+/// next call of a function taking its input from that buffer (for example
+/// `add_previous_timeline`).
+///
+/// Returns a pointer to the first of those bytes in WebAssembly's linear
+/// memory.
+///
+/// # Arguments
+///
+/// * `len` - Length of the data that will be written, in bytes.
+#[no_mangle]
+pub extern "C" fn reserve_input_buffer(len: usize) -> *mut u8 {
+    input::reserve(len)
+}
+
+/// Communicate the timeline obtained for a `<SegmentTimeline>` when parsing
+/// the previous version of the MPD, so the next `parse` call only reports the
+/// entries which changed since (@see `AttributeName::SegmentTimelineUpdate`).
+///
+/// The data must have been written beforehand through the buffer returned by
+/// `reserve_input_buffer`.
+/// It begins by the key identifying the `<SegmentTimeline>`, as an UTF-8
+/// string made of the identifiers of its parent `<Period>`, `<AdaptationSet>`
+/// and `<Representation>` elements separated by a `/` character. Each is the
+/// `id` attribute of the element or, if not set, its index among the elements
+/// of the same type in its parent prefixed by a `#` character (e.g. `#0`).
+/// It is empty for elements which are not a parent of the `<SegmentTimeline>`
+/// (@see `options::timeline_key`).
+/// The timeline follows, in the same format than the one reported through the
+/// `AttributeName::SegmentTimeline` attribute.
+///
+/// # Arguments
+///
+/// * `key_len` - Length of the key at the beginning of the input buffer, in
+///   bytes.
+#[no_mangle]
+pub extern "C" fn add_previous_timeline(key_len: usize) {
+    let data = input::take();
+    if key_len > data.len() {
+        ParsingError("Invalid previous timeline: key longer than the data".to_owned()).report_err();
+        return;
+    }
+    let key = match std::str::from_utf8(&data[..key_len]) {
+        Ok(key) => key.to_owned(),
+        Err(err) => {
+            ParsingError::from(err).report_err();
+            return;
+        }
+    };
+    match processor::SegmentObject::from_bytes(&data[key_len..]) {
+        Ok(timeline) => options::add_previous_timeline(key, timeline),
+        Err(err) => err.report_err(),
+    }
+}
+
+/// Communicate the URL at which the MPD parsed by the next `parse` call was
+/// fetched, so its `<BaseURL>` and `<Location>` elements can be resolved
+/// against it (@see `ParserOption::ResolveBaseUrls`).
+///
+/// That URL must have been written beforehand as an UTF-8 string through the
+/// buffer returned by `reserve_input_buffer`.
+#[no_mangle]
+pub extern "C" fn set_mpd_url() {
+    match String::from_utf8(input::take()) {
+        Ok(url) => options::set_mpd_url(url),
+        Err(err) => ParsingError::from(err).report_err(),
+    }
+}
+
+/// Register the `<EssentialProperty>` schemes supported by the host, for all
+/// subsequent `parse` calls. `<AdaptationSet>` and `<Representation>`
+/// elements having an `<EssentialProperty>` with another scheme are then
+/// flagged (@see `AttributeName::UnsupportedEssentialProperties`), or skipped
+/// if the `SkipUnsupportedSets` parser option is enabled.
+///
+/// The schemes must have been written beforehand as an UTF-8 string through
+/// the buffer returned by `reserve_input_buffer`, separated by line feeds.
+/// Writing an empty string means that only the schemes understood by the
+/// parser itself are supported.
+#[no_mangle]
+pub extern "C" fn set_supported_essential_properties() {
+    match String::from_utf8(input::take()) {
+        Ok(schemes) if schemes.is_empty() => {
+            options::set_supported_essential_properties(Some(Vec::new()))
+        }
+        Ok(schemes) => options::set_supported_essential_properties(Some(
+            schemes.split('\n').map(str::to_owned).collect(),
+        )),
+        Err(err) => ParsingError::from(err).report_err(),
+    }
+}
+
+/// Stop evaluating `<EssentialProperty>` elements against the schemes
+/// supported by the host, for all subsequent `parse` calls (@see
+/// `set_supported_essential_properties`). It is the default behavior.
+#[no_mangle]
+pub extern "C" fn disable_essential_property_evaluation() {
+    options::set_supported_essential_properties(None);
+}
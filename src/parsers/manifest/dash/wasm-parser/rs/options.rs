@@ -0,0 +1,210 @@
+use crate::errors::{ParsingError, Result};
+use crate::processor::SegmentObject;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// `ParserOption` enumerates the optional processing steps of the parser which
+/// can be configured by the JS-side through the `set_parser_option` function.
+///
+/// All of them are disabled by default, so the default output of the parser
+/// stays as close as possible to the original MPD.
+#[derive(PartialEq, Clone, Copy)]
+#[repr(C)]
+pub enum ParserOption {
+    /// When enabled (set to a non-zero value), contiguous `<S>` elements of a
+    /// `<SegmentTimeline>` which share the same duration are folded into a
+    /// single entry with the corresponding repeat count.
+    ///
+    /// Gaps and overlaps between consecutive `<S>` elements are then also
+    /// reported, through the same channel as parsing errors. Those are only
+    /// warnings, which can be told apart by their message, always starting
+    /// with either `SegmentTimeline gap` or `SegmentTimeline overlap` and
+    /// giving the position in the MPD of the `<S>` element concerned.
+    NormalizeSegmentTimeline = 0,
+
+    /// When enabled (set to a non-zero value), the text of each `<BaseURL>`
+    /// and `<Location>` element is also resolved against the URL of the MPD
+    /// (@see `set_mpd_url`) and the `<BaseURL>` elements of its parents, and
+    /// reported as absolute URLs.
+    /// URLs which cannot be made absolute that way, e.g. because the URL of
+    /// the MPD is unknown, are not reported.
+    ResolveBaseUrls = 1,
+
+    /// When enabled (set to a non-zero value), the effective attributes of
+    /// each `<Representation>` are also reported just before it is closed,
+    /// after taking into account those inherited from its parent elements
+    /// (@see `TagName::EffectiveAttributes`).
+    ///
+    /// `<SegmentTemplate>` and `<SegmentBase>` elements are merged attribute
+    /// by attribute, the ones of the closest element taking precedence.
+    ResolveInheritance = 2,
+
+    /// When enabled (set to a non-zero value), the remaining content of each
+    /// `<AdaptationSet>` and `<Representation>` having an
+    /// `<EssentialProperty>` whose scheme is not supported (@see
+    /// `set_supported_essential_properties`) is skipped instead of being
+    /// reported.
+    ///
+    /// Those elements are flagged in both cases, through the
+    /// `UnsupportedEssentialProperties` attribute.
+    SkipUnsupportedSets = 3,
+
+    /// When enabled (set to a non-zero value), the `<Period>` time bounds
+    /// reported through the `ResolvedPeriodTimes` attribute are updated so
+    /// they do not overlap: a `<Period>` ending after the start of the next
+    /// one is shortened, and removed if it is left with no duration.
+    ///
+    /// Each update is also reported as an error.
+    FlattenOverlappingPeriods = 4,
+}
+
+impl ParserOption {
+    /// Convert the raw value communicated by the JS-side into the
+    /// corresponding `ParserOption`.
+    pub fn from_u8(value: u8) -> Result<ParserOption> {
+        match value {
+            0 => Ok(ParserOption::NormalizeSegmentTimeline),
+            1 => Ok(ParserOption::ResolveBaseUrls),
+            2 => Ok(ParserOption::ResolveInheritance),
+            3 => Ok(ParserOption::SkipUnsupportedSets),
+            4 => Ok(ParserOption::FlattenOverlappingPeriods),
+            x => Err(ParsingError(format!("Unknown parser option: {}", x))),
+        }
+    }
+}
+
+/// Options currently applied by the parser.
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    /// @see ParserOption::NormalizeSegmentTimeline
+    pub normalize_segment_timeline: bool,
+
+    /// @see ParserOption::ResolveBaseUrls
+    pub resolve_base_urls: bool,
+
+    /// @see ParserOption::ResolveInheritance
+    pub resolve_inheritance: bool,
+
+    /// @see ParserOption::SkipUnsupportedSets
+    pub skip_unsupported_sets: bool,
+
+    /// @see ParserOption::FlattenOverlappingPeriods
+    pub flatten_overlapping_periods: bool,
+
+    /// URL at which the MPD was fetched, against which its `<BaseURL>` and
+    /// `<Location>` elements are resolved.
+    pub mpd_url: Option<String>,
+
+    /// Timelines parsed from the previous version of the MPD, which the
+    /// parser will try to merge with the new ones.
+    ///
+    /// Keys are constructed by `timeline_key`.
+    pub previous_timelines: HashMap<String, Vec<SegmentObject>>,
+
+    /// Time range, in seconds relative to the start of the Period, for which
+    /// the segments of each `<Representation>` relying on a
+    /// `<SegmentTemplate>` should be reported.
+    ///
+    /// `None` if segments should not be reported.
+    pub segment_range: Option<(f64, f64)>,
+
+    /// `<EssentialProperty>` schemes supported by the host. The
+    /// `<AdaptationSet>` and `<Representation>` elements having one with
+    /// another scheme are flagged as unsupported.
+    ///
+    /// `None` if `<EssentialProperty>` elements should not be evaluated.
+    pub supported_essential_properties: Option<Vec<String>>,
+}
+
+thread_local! {
+    /// Options set by the JS-side, which will be applied to the next parsing
+    /// operations.
+    static CURRENT_OPTIONS: RefCell<ParserOptions> = RefCell::new(ParserOptions::default());
+}
+
+/// Construct the key identifying a `<SegmentTimeline>` in the MPD, from the
+/// identifiers of its parent `<Period>`, `<AdaptationSet>` and
+/// `<Representation>` elements separated by a `/` character.
+///
+/// Each identifier is the `id` attribute of the element or, if not set, its
+/// index among the elements of the same type in its parent prefixed by a `#`
+/// character (e.g. `#0`). It is an empty string if the `<SegmentTimeline>` is
+/// not in such element.
+pub fn timeline_key(period: &str, adaptation_set: &str, representation: &str) -> String {
+    format!("{}/{}/{}", period, adaptation_set, representation)
+}
+
+/// Register the timeline which was parsed from the previous version of the
+/// MPD for the `<SegmentTimeline>` identified by `key` (@see `timeline_key`).
+///
+/// That timeline will be considered by the next parsing operation only.
+pub fn add_previous_timeline(key: String, timeline: Vec<SegmentObject>) {
+    CURRENT_OPTIONS.with(|opts| {
+        opts.borrow_mut().previous_timelines.insert(key, timeline);
+    })
+}
+
+/// Set the URL of the MPD which will be parsed by the next parsing operation.
+pub fn set_mpd_url(url: String) {
+    CURRENT_OPTIONS.with(|opts| {
+        opts.borrow_mut().mpd_url = Some(url);
+    })
+}
+
+/// Set the time range for which segments should be reported, for all future
+/// parsing operations (@see `ParserOptions::segment_range`).
+pub fn set_segment_range(range: Option<(f64, f64)>) {
+    CURRENT_OPTIONS.with(|opts| {
+        opts.borrow_mut().segment_range = range;
+    })
+}
+
+/// Set the `<EssentialProperty>` schemes supported by the host, for all
+/// future parsing operations (@see
+/// `ParserOptions::supported_essential_properties`).
+pub fn set_supported_essential_properties(schemes: Option<Vec<String>>) {
+    CURRENT_OPTIONS.with(|opts| {
+        opts.borrow_mut().supported_essential_properties = schemes;
+    })
+}
+
+/// Update the value of the given option for all future parsing operations.
+pub fn set(option: ParserOption, value: u32) {
+    CURRENT_OPTIONS.with(|opts| {
+        let mut opts = opts.borrow_mut();
+        match option {
+            ParserOption::NormalizeSegmentTimeline => {
+                opts.normalize_segment_timeline = value != 0;
+            }
+            ParserOption::ResolveBaseUrls => {
+                opts.resolve_base_urls = value != 0;
+            }
+            ParserOption::ResolveInheritance => {
+                opts.resolve_inheritance = value != 0;
+            }
+            ParserOption::SkipUnsupportedSets => {
+                opts.skip_unsupported_sets = value != 0;
+            }
+            ParserOption::FlattenOverlappingPeriods => {
+                opts.flatten_overlapping_periods = value != 0;
+            }
+        }
+    })
+}
+
+/// Returns the options that should be applied to a new parsing operation.
+///
+/// Previous timelines and the MPD URL are only considered for one parsing
+/// operation, as such they are moved out of the current options by this call.
+pub fn for_next_parse() -> ParserOptions {
+    CURRENT_OPTIONS.with(|opts| {
+        let mut opts = opts.borrow_mut();
+        let previous_timelines = std::mem::take(&mut opts.previous_timelines);
+        let mpd_url = opts.mpd_url.take();
+        ParserOptions {
+            previous_timelines,
+            mpd_url,
+            ..opts.clone()
+        }
+    })
+}
@@ -0,0 +1,243 @@
+use crate::errors::{ParsingError, Result};
+
+/// Attributes of the `<MPD>` element needed to resolve the time bounds of its
+/// `<Period>` elements.
+#[derive(Debug, Clone, Default)]
+pub struct MpdTimeInfo {
+    /// `true` if its `type` attribute is `dynamic`.
+    pub is_dynamic: bool,
+
+    /// Its `availabilityStartTime` attribute, in seconds since the Unix epoch.
+    pub availability_start_time: Option<f64>,
+
+    /// Its `mediaPresentationDuration` attribute, in seconds.
+    pub media_presentation_duration: Option<f64>,
+}
+
+/// Attributes of a `<Period>` element needed to resolve its time bounds.
+#[derive(Debug, Clone, Default)]
+pub struct PeriodTimeInfo {
+    /// Its `id` attribute, empty if not set.
+    pub id: String,
+
+    /// Its `start` attribute, in seconds.
+    pub start: Option<f64>,
+
+    /// Its `duration` attribute, in seconds.
+    pub duration: Option<f64>,
+}
+
+/// Time bounds of a `<Period>`, once those of the whole MPD are known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedPeriod {
+    /// Time in seconds at which it starts.
+    pub start: f64,
+
+    /// Time in seconds at which it ends, `None` if unknown.
+    pub end: Option<f64>,
+
+    /// `true` if it is entirely overlapped by the `<Period>` elements
+    /// following it, and should thus be ignored.
+    pub removed: bool,
+}
+
+/// Resolves the time bounds of the given `<Period>` elements, in document
+/// order:
+///
+/// - a `<Period>` starts at its `start` attribute or else at the end of the
+///   previous one. The first one starts at the `availabilityStartTime` of a
+///   dynamic MPD, if set, or else at `0`.
+///
+/// - it lasts for its `duration` attribute or else until the start of the
+///   next one. The last one lasts for the `mediaPresentationDuration` of the
+///   MPD.
+///
+/// Fails if the start of a `<Period>` cannot be known.
+pub fn resolve(mpd: &MpdTimeInfo, periods: &[PeriodTimeInfo]) -> Result<Vec<ResolvedPeriod>> {
+    let mut resolved: Vec<ResolvedPeriod> = Vec::with_capacity(periods.len());
+    for (i, period) in periods.iter().enumerate() {
+        let start = match (period.start, resolved.last()) {
+            (Some(start), _) => start,
+            (None, None) if mpd.is_dynamic => mpd.availability_start_time.unwrap_or(0.),
+            (None, None) => 0.,
+            (None, Some(previous)) => previous.end.ok_or_else(|| {
+                ParsingError(format!(
+                    "Cannot resolve the start of Period {}: the previous one has no known end",
+                    describe(periods, i)
+                ))
+            })?,
+        };
+        let duration = match (period.duration, periods.get(i + 1)) {
+            (Some(duration), _) => Some(duration),
+            (None, None) => mpd.media_presentation_duration,
+            (None, Some(next)) => next.start.map(|next_start| next_start - start),
+        };
+        resolved.push(ResolvedPeriod {
+            start,
+            end: duration.map(|duration| start + duration),
+            removed: false,
+        });
+    }
+    Ok(resolved)
+}
+
+/// Prevents resolved `<Period>` elements from overlapping, by ending each one
+/// at the start of the next one if it was ending later or if its end is
+/// unknown.
+///
+/// `<Period>` elements which would then be left with no duration are marked
+/// as removed, the one preceding them being compared to the next one in their
+/// place.
+///
+/// Returns a diagnostic for each `<Period>` which has been updated.
+pub fn flatten(periods: &[PeriodTimeInfo], resolved: &mut [ResolvedPeriod]) -> Vec<ParsingError> {
+    let mut diagnostics = Vec::new();
+
+    // Indexes in `resolved` of the `<Period>` elements kept until now.
+    let mut kept: Vec<usize> = Vec::with_capacity(resolved.len());
+    for i in 0..resolved.len() {
+        let next_start = resolved[i].start;
+        while let Some(&last_idx) = kept.last() {
+            let last = &mut resolved[last_idx];
+            if last.end.is_some_and(|end| end <= next_start) {
+                break;
+            }
+            last.end = Some(next_start);
+            if next_start > last.start {
+                diagnostics.push(ParsingError(format!(
+                    "Period {} overlaps Period {}: its end was set to {}",
+                    describe(periods, last_idx),
+                    describe(periods, i),
+                    next_start
+                )));
+                break;
+            }
+            last.removed = true;
+            diagnostics.push(ParsingError(format!(
+                "Period {} is entirely overlapped by Period {}: it was removed",
+                describe(periods, last_idx),
+                describe(periods, i)
+            )));
+            kept.pop();
+        }
+        kept.push(i);
+    }
+    diagnostics
+}
+
+/// Designates a `<Period>` in diagnostics, by its `id` if it has one or else
+/// by its position in the MPD.
+fn describe(periods: &[PeriodTimeInfo], idx: usize) -> String {
+    match periods.get(idx) {
+        Some(period) if !period.id.is_empty() => format!("\"{}\"", period.id),
+        _ => format!("#{}", idx + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(id: &str, start: Option<f64>, duration: Option<f64>) -> PeriodTimeInfo {
+        PeriodTimeInfo {
+            id: id.to_owned(),
+            start,
+            duration,
+        }
+    }
+
+    fn bounds(resolved: &[ResolvedPeriod]) -> Vec<(f64, Option<f64>, bool)> {
+        resolved
+            .iter()
+            .map(|period| (period.start, period.end, period.removed))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mpd = MpdTimeInfo {
+            is_dynamic: false,
+            availability_start_time: Some(1000.),
+            media_presentation_duration: Some(100.),
+        };
+        let periods = [
+            period("1", None, Some(30.)),
+            period("2", None, None),
+            period("3", Some(50.), None),
+        ];
+        assert_eq!(
+            bounds(&resolve(&mpd, &periods).unwrap()),
+            vec![
+                (0., Some(30.), false),
+                (30., Some(50.), false),
+                (50., Some(150.), false)
+            ]
+        );
+
+        let mpd = MpdTimeInfo {
+            is_dynamic: true,
+            availability_start_time: Some(1000.),
+            media_presentation_duration: None,
+        };
+        let periods = [period("", None, Some(10.)), period("", None, None)];
+        assert_eq!(
+            bounds(&resolve(&mpd, &periods).unwrap()),
+            vec![(1000., Some(1010.), false), (1010., None, false)]
+        );
+
+        let mpd = MpdTimeInfo {
+            is_dynamic: true,
+            ..MpdTimeInfo::default()
+        };
+        assert_eq!(
+            bounds(&resolve(&mpd, &[period("", None, None)]).unwrap()),
+            vec![(0., None, false)]
+        );
+
+        let periods = [period("", Some(0.), None), period("live", None, None)];
+        let err = resolve(&MpdTimeInfo::default(), &periods).unwrap_err();
+        assert_eq!(
+            err.0,
+            "Cannot resolve the start of Period \"live\": the previous one has no known end"
+        );
+    }
+
+    #[test]
+    fn test_flatten() {
+        let periods = [
+            period("content", Some(0.), Some(60.)),
+            period("ad", Some(40.), Some(10.)),
+            period("", Some(45.), Some(5.)),
+            period("", Some(45.), None),
+        ];
+        let mut resolved = resolve(&MpdTimeInfo::default(), &periods).unwrap();
+        let diagnostics = flatten(&periods, &mut resolved);
+        assert_eq!(
+            bounds(&resolved),
+            vec![
+                (0., Some(40.), false),
+                (40., Some(45.), false),
+                (45., Some(45.), true),
+                (45., None, false)
+            ]
+        );
+        let messages: Vec<String> = diagnostics.into_iter().map(|err| err.0).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Period \"content\" overlaps Period \"ad\": its end was set to 40",
+                "Period \"ad\" overlaps Period #3: its end was set to 45",
+                "Period #3 is entirely overlapped by Period #4: it was removed",
+            ]
+        );
+
+        // Periods which do not overlap are left untouched
+        let periods = [period("", Some(0.), Some(10.)), period("", Some(10.), None)];
+        let mut resolved = resolve(&MpdTimeInfo::default(), &periods).unwrap();
+        assert!(flatten(&periods, &mut resolved).is_empty());
+        assert_eq!(
+            bounds(&resolved),
+            vec![(0., Some(10.), false), (10., None, false)]
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::errors::{ParsingError, Result};
+use crate::utils;
+use quick_xml::events::Event;
+
+/// Type of a PlayReady Object record containing a PlayReady Header.
+const WRM_HEADER_RECORD_TYPE: u16 = 1;
+
+/// Information extracted from the PlayReady Header (`WRMHEADER`) of a
+/// PlayReady Object.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayReadyHeader {
+    /// The `version` attribute of the `WRMHEADER` element, e.g. `4.3.0.0`.
+    pub version: String,
+
+    /// Key IDs announced in the header, converted from the little endian GUID
+    /// form used by PlayReady to big endian UUID bytes.
+    pub key_ids: Vec<[u8; 16]>,
+
+    /// Content of the `LA_URL` element, the URL of the license server, if
+    /// present.
+    pub la_url: Option<String>,
+}
+
+/// Parse a PlayReady Object, as found in base64 in a `<mspr:pro>` element,
+/// and return the content of the PlayReady Headers it contains.
+///
+/// A PlayReady Object is made of a little endian u32 length, a little endian
+/// u16 record count and of those records, each made of a little endian u16
+/// type, a little endian u16 length and its value.
+pub fn parse_pro(data: &[u8]) -> Result<Vec<PlayReadyHeader>> {
+    if data.len() < 6 {
+        return Err(ParsingError(
+            "Invalid PlayReady Object: too short to contain its header".to_owned(),
+        ));
+    }
+    let length = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    if length != data.len() {
+        return Err(ParsingError(format!(
+            "Invalid PlayReady Object: its length field is {} but {} bytes were found",
+            length,
+            data.len()
+        )));
+    }
+    let record_count = u16::from_le_bytes([data[4], data[5]]);
+    let mut offset = 6;
+    let mut headers = Vec::new();
+    for _ in 0..record_count {
+        if offset + 4 > data.len() {
+            return Err(ParsingError(
+                "Invalid PlayReady Object: truncated record header".to_owned(),
+            ));
+        }
+        let record_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let record_len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        offset += 4;
+        if offset + record_len > data.len() {
+            return Err(ParsingError(format!(
+                "Invalid PlayReady Object: record of {} bytes truncated at offset {}",
+                record_len, offset
+            )));
+        }
+        if record_type == WRM_HEADER_RECORD_TYPE {
+            let xml = decode_utf16_le(&data[offset..offset + record_len])?;
+            headers.push(parse_wrm_header(&xml)?);
+        }
+        offset += record_len;
+    }
+    Ok(headers)
+}
+
+/// Decode the given UTF-16LE string, ignoring its byte order mark if one.
+fn decode_utf16_le(data: &[u8]) -> Result<String> {
+    if data.len() % 2 != 0 {
+        return Err(ParsingError(
+            "Invalid PlayReady Header: odd number of bytes in an UTF-16 string".to_owned(),
+        ));
+    }
+    let code_units = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .skip_while(|c| *c == 0xfeff);
+    char::decode_utf16(code_units)
+        .collect::<std::result::Result<String, _>>()
+        .map_err(|err| ParsingError(format!("Invalid PlayReady Header: {}", err)))
+}
+
+/// Parse the XML of a PlayReady Header, in versions `4.0.0.0` to `4.3.0.0`.
+///
+/// Key IDs are found in the text of the `KID` element in version `4.0.0.0`,
+/// and in the `VALUE` attribute of `KID` elements in later versions.
+pub fn parse_wrm_header(xml: &str) -> Result<PlayReadyHeader> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut header = PlayReadyHeader::default();
+
+    // Name of the innermost element whose text we're interested in
+    let mut current_text_elt: Option<&[u8]> = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(tag) | Event::Empty(tag) => match tag.name().as_ref() {
+                b"WRMHEADER" => {
+                    if let Some(version) = tag.try_get_attribute("version")? {
+                        header.version = version.unescape_value()?.into_owned();
+                    }
+                    if !matches!(
+                        header.version.as_str(),
+                        "4.0.0.0" | "4.1.0.0" | "4.2.0.0" | "4.3.0.0"
+                    ) {
+                        return Err(ParsingError(format!(
+                            "Unsupported PlayReady Header version: \"{}\"",
+                            header.version
+                        )));
+                    }
+                }
+                b"KID" => match tag.try_get_attribute("VALUE")? {
+                    Some(value) => header.key_ids.push(decode_kid(&value.value)?),
+                    None => current_text_elt = Some(b"KID"),
+                },
+                b"LA_URL" => current_text_elt = Some(b"LA_URL"),
+                _ => current_text_elt = None,
+            },
+            Event::Text(text) => match current_text_elt {
+                Some(b"KID") => header.key_ids.push(decode_kid(text.as_ref())?),
+                Some(b"LA_URL") => header.la_url = Some(text.unescape()?.into_owned()),
+                _ => {}
+            },
+            Event::End(_) => current_text_elt = None,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if header.version.is_empty() {
+        return Err(ParsingError(
+            "Invalid PlayReady Header: no WRMHEADER element".to_owned(),
+        ));
+    }
+    Ok(header)
+}
+
+/// Decode a base64 PlayReady KID, and convert it from its little endian GUID
+/// form to big endian UUID bytes.
+fn decode_kid(base64: &[u8]) -> Result<[u8; 16]> {
+    let decoded = utils::decode_base64(base64)?;
+    if decoded.len() != 16 {
+        return Err(ParsingError(format!(
+            "Invalid PlayReady KID: expected 16 bytes, got {}",
+            decoded.len()
+        )));
+    }
+    let mut kid = [0u8; 16];
+    kid.copy_from_slice(&decoded);
+    kid[0..4].reverse();
+    kid[4..6].reverse();
+    kid[6..8].reverse();
+    Ok(kid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base64 of the little endian GUID form of the
+    /// `10000000-1111-2222-3333-444444444444` UUID.
+    const KID_B64: &str = "AAAAEBERIiIzM0RERERERA==";
+
+    const KID_UUID: [u8; 16] = [
+        0x10, 0x00, 0x00, 0x00, 0x11, 0x11, 0x22, 0x22, 0x33, 0x33, 0x44, 0x44, 0x44, 0x44, 0x44,
+        0x44,
+    ];
+
+    fn build_pro(xml: &str) -> Vec<u8> {
+        let record: Vec<u8> = xml.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+        let mut pro = ((record.len() + 10) as u32).to_le_bytes().to_vec();
+        pro.extend(1u16.to_le_bytes());
+        pro.extend(WRM_HEADER_RECORD_TYPE.to_le_bytes());
+        pro.extend((record.len() as u16).to_le_bytes());
+        pro.extend(record);
+        pro
+    }
+
+    #[test]
+    fn test_parse_pro_v4_0() {
+        let xml = format!(
+            "<WRMHEADER xmlns=\"http://schemas.microsoft.com/DRM/2007/03/PlayReadyHeader\" \
+             version=\"4.0.0.0\"><DATA><PROTECTINFO><KEYLEN>16</KEYLEN>\
+             <ALGID>AESCTR</ALGID></PROTECTINFO><KID>{}</KID>\
+             <LA_URL>https://example.com/rightsmanager.asmx?a=1&amp;b=2</LA_URL>\
+             <CUSTOMATTRIBUTES><IIS_DRM_VERSION>8</IIS_DRM_VERSION></CUSTOMATTRIBUTES>\
+             </DATA></WRMHEADER>",
+            KID_B64
+        );
+        let headers = parse_pro(&build_pro(&xml)).unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].version, "4.0.0.0");
+        assert_eq!(headers[0].key_ids, vec![KID_UUID]);
+        assert_eq!(
+            headers[0].la_url.as_deref(),
+            Some("https://example.com/rightsmanager.asmx?a=1&b=2")
+        );
+    }
+
+    #[test]
+    fn test_parse_wrm_header_v4_3() {
+        let xml = format!(
+            "<WRMHEADER version=\"4.3.0.0\"><DATA><PROTECTINFO><KIDS>\
+             <KID ALGID=\"AESCBC\" VALUE=\"{}\"></KID><KID VALUE=\"{}\"/>\
+             </KIDS></PROTECTINFO></DATA></WRMHEADER>",
+            KID_B64, KID_B64
+        );
+        let header = parse_wrm_header(&xml).unwrap();
+        assert_eq!(header.key_ids, vec![KID_UUID, KID_UUID]);
+        assert!(header.la_url.is_none());
+    }
+
+    #[test]
+    fn test_parse_pro_errors() {
+        let xml = "<WRMHEADER version=\"4.0.0.0\"><DATA></DATA></WRMHEADER>";
+        let mut pro = build_pro(xml);
+        pro.pop();
+        assert!(parse_pro(&pro).is_err());
+        assert!(parse_pro(&[0, 0]).is_err());
+        assert!(parse_wrm_header("<WRMHEADER version=\"5.0.0.0\"></WRMHEADER>").is_err());
+        assert!(parse_wrm_header(
+            "<WRMHEADER version=\"4.1.0.0\"><KID VALUE=\"AAAA\"/></WRMHEADER>"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::codecs;
+use crate::errors::ParsingError;
+use crate::track_type::{self, ElementHints, Role, TrackType, TrackTypeHints};
+
+use super::attributes;
+use super::namespaces::canonical_attr_name;
+use quick_xml::NsReader;
+
+/// Collects the information needed to infer the type of the current
+/// `<AdaptationSet>` and to normalize its roles.
+#[derive(Default)]
+pub struct AdaptationTypeTracker {
+    /// Hints gathered on the current `<AdaptationSet>` and its children.
+    adaptation_set: Option<TrackTypeHints>,
+
+    /// Number of `<AdaptationSet>` elements opened in the current `<Period>`,
+    /// used to designate them in diagnostics.
+    adaptation_set_count: usize,
+}
+
+impl AdaptationTypeTracker {
+    /// Signal that a `<Period>` element has been opened.
+    pub fn open_period(&mut self) {
+        self.adaptation_set = None;
+        self.adaptation_set_count = 0;
+    }
+
+    /// Signal that an `<AdaptationSet>` element has been opened.
+    pub fn open_adaptation_set<R>(
+        &mut self,
+        tag: &quick_xml::events::BytesStart,
+        reader: &NsReader<R>,
+    ) {
+        let mut hints = TrackTypeHints::default();
+        for attr in tag.attributes().flatten() {
+            if canonical_attr_name(reader, attr.key).as_ref() == b"contentType" {
+                hints.content_type = attr.unescape_value().ok().map(|v| v.into_owned());
+            }
+        }
+        hints.elements.push(element_hints(tag, reader));
+        self.adaptation_set = Some(hints);
+        self.adaptation_set_count += 1;
+    }
+
+    /// Signal that a `<Representation>` element has been opened inside the
+    /// current `<AdaptationSet>`.
+    pub fn open_representation<R>(
+        &mut self,
+        tag: &quick_xml::events::BytesStart,
+        reader: &NsReader<R>,
+    ) {
+        if let Some(hints) = self.adaptation_set.as_mut() {
+            hints.elements.push(element_hints(tag, reader));
+        }
+    }
+
+    /// Takes into account a `<Role>` element of the current `<AdaptationSet>`.
+    /// Those with another scheme than the DASH one are ignored, as are those
+    /// found outside of an `<AdaptationSet>`.
+    pub fn add_role(&mut self, tag: &quick_xml::events::BytesStart) {
+        let hints = match self.adaptation_set.as_mut() {
+            Some(hints) => hints,
+            None => return,
+        };
+        let (scheme_id_uri, value) = attributes::scheme_attrs(tag);
+        if scheme_id_uri.as_deref().map(str::trim) != Some(track_type::DASH_ROLE_SCHEME) {
+            return;
+        }
+        match value.as_deref().and_then(Role::parse) {
+            Some(role) => hints.add_role(role),
+            None => ParsingError(format!(
+                "AdaptationSet {}: unknown value for the DASH role scheme: \"{}\"",
+                self.adaptation_set_count,
+                value.unwrap_or_default()
+            ))
+            .report_err(),
+        }
+    }
+
+    /// Signal that the current `<AdaptationSet>` element has been closed,
+    /// returning its inferred type, if one, and its sorted roles.
+    ///
+    /// The contradictions between the hints it contained are reported as
+    /// errors.
+    pub fn close_adaptation_set(&mut self) -> Option<(Option<TrackType>, Vec<Role>)> {
+        let hints = self.adaptation_set.take()?;
+        let inference = hints.infer();
+        for contradiction in inference.contradictions {
+            ParsingError(format!(
+                "AdaptationSet {}: {}",
+                self.adaptation_set_count, contradiction
+            ))
+            .report_err();
+        }
+        Some((inference.track_type, hints.roles))
+    }
+}
+
+/// Extracts the `mimeType` and `codecs` attributes of an `<AdaptationSet>` or
+/// `<Representation>` element.
+///
+/// Invalid attributes are ignored here, as they are already signaled when
+/// reported.
+fn element_hints<R>(tag: &quick_xml::events::BytesStart, reader: &NsReader<R>) -> ElementHints {
+    let mut hints = ElementHints::default();
+    for attr in tag.attributes().flatten() {
+        let value = match attr.unescape_value() {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        match canonical_attr_name(reader, attr.key).as_ref() {
+            b"mimeType" => hints.mime_type = Some(value.into_owned()),
+            b"codecs" => hints.codecs = codecs::parse_codecs(&value).0,
+            _ => {}
+        }
+    }
+    hints
+}
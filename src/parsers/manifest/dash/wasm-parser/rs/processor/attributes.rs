@@ -1,16 +1,31 @@
+use crate::accessibility;
 use crate::errors::ParsingError;
 use crate::events::AttributeName::*;
+use crate::segment_index::SegmentTemplateUrls;
+use crate::thumbnails::TileGrid;
+use crate::utils;
+use std::borrow::Cow;
 
-pub fn report_mpd_attrs(e: &quick_xml::events::BytesStart) {
+use super::license_url::LicenseUrlElement;
+use super::namespaces::canonical_attr_name;
+use quick_xml::NsReader;
+
+/// Report attributes encountered in the `<MPD>` element, whose opening tag
+/// ends at `position` in the MPD.
+pub fn report_mpd_attrs(e: &quick_xml::events::BytesStart, position: usize) {
     for res_attr in e.attributes() {
         match res_attr {
             Ok(attr) => match attr.key.as_ref() {
                 b"id" => Id.try_report_as_string(&attr),
                 b"profiles" => Profiles.try_report_as_string(&attr),
                 b"type" => Type.try_report_as_string(&attr),
-                b"availabilityStartTime" => AvailabilityStartTime.try_report_as_string(&attr),
-                b"availabilityEndTime" => AvailabilityEndTime.try_report_as_string(&attr),
-                b"publishTime" => PublishTime.try_report_as_string(&attr),
+                b"availabilityStartTime" => {
+                    AvailabilityStartTime.try_report_as_date_time(&attr, position)
+                }
+                b"availabilityEndTime" => {
+                    AvailabilityEndTime.try_report_as_date_time(&attr, position)
+                }
+                b"publishTime" => PublishTime.try_report_as_date_time(&attr, position),
                 b"mediaPresentationDuration" => {
                     MediaPresentationDuration.try_report_as_iso_8601_duration(&attr)
                 }
@@ -39,10 +54,10 @@ pub fn report_mpd_attrs(e: &quick_xml::events::BytesStart) {
     }
 }
 
-pub fn report_period_attrs(tag_bs: &quick_xml::events::BytesStart) {
+pub fn report_period_attrs<R>(tag_bs: &quick_xml::events::BytesStart, reader: &NsReader<R>) {
     for res_attr in tag_bs.attributes() {
         match res_attr {
-            Ok(attr) => match attr.key.as_ref() {
+            Ok(attr) => match canonical_attr_name(reader, attr.key).as_ref() {
                 b"id" => Id.try_report_as_string(&attr),
                 b"start" => Start.try_report_as_iso_8601_duration(&attr),
                 b"duration" => Duration.try_report_as_iso_8601_duration(&attr),
@@ -64,35 +79,40 @@ pub fn report_period_attrs(tag_bs: &quick_xml::events::BytesStart) {
     }
 }
 
-pub fn report_adaptation_set_attrs(e: &quick_xml::events::BytesStart) {
+pub fn report_adaptation_set_attrs<R>(e: &quick_xml::events::BytesStart, reader: &NsReader<R>) {
     for res_attr in e.attributes() {
         match res_attr {
-            Ok(attr) => match attr.key.as_ref() {
+            Ok(attr) => match canonical_attr_name(reader, attr.key).as_ref() {
                 b"id" => Id.try_report_as_string(&attr),
                 b"group" => Group.try_report_as_u64(&attr),
                 b"lang" => Language.try_report_as_string(&attr),
                 b"contentType" => ContentType.try_report_as_string(&attr),
-                b"par" => Par.try_report_as_string(&attr),
+                b"par" => {
+                    Par.try_report_as_string(&attr);
+                    ParRatio.try_report_as_aspect_ratio(&attr);
+                }
                 b"minBandwidth" => MinBandwidth.try_report_as_u64(&attr),
                 b"maxBandwidth" => MaxBandwidth.try_report_as_u64(&attr),
                 b"minWidth" => MinWidth.try_report_as_u64(&attr),
                 b"maxWidth" => MaxWidth.try_report_as_u64(&attr),
                 b"minHeight" => MinHeight.try_report_as_u64(&attr),
                 b"maxHeight" => MaxHeight.try_report_as_u64(&attr),
-                b"minFrameRate" => MinFrameRate.try_report_as_maybe_division(&attr),
-                b"maxFrameRate" => MaxFrameRate.try_report_as_maybe_division(&attr),
+                b"minFrameRate" => MinFrameRate.try_report_as_frame_rate(MinFrameRateRatio, &attr),
+                b"maxFrameRate" => MaxFrameRate.try_report_as_frame_rate(MaxFrameRateRatio, &attr),
                 b"selectionPriority" => SelectionPriority.try_report_as_u64(&attr),
                 b"segmentAlignment" => SegmentAlignment.try_report_as_u64_or_bool(&attr),
                 b"subsegmentAlignment" => SubsegmentAlignment.try_report_as_u64_or_bool(&attr),
                 b"bitstreamSwitching" => BitstreamSwitching.try_report_as_bool(&attr),
                 b"audioSamplingRate" => AudioSamplingRate.try_report_as_string(&attr),
-                b"codecs" => Codecs.try_report_as_string(&attr),
-                b"scte214:supplementalCodecs" => SupplementalCodecs.try_report_as_string(&attr),
+                b"codecs" => Codecs.try_report_as_codecs(ParsedCodecs, &attr),
+                b"scte214:supplementalCodecs" => SupplementalCodecs
+                    .try_report_as_supplemental_codecs(ParsedSupplementalCodecs, &attr),
                 b"profiles" => Profiles.try_report_as_string(&attr),
                 b"segmentProfiles" => SegmentProfiles.try_report_as_string(&attr),
-                b"mimeType" => MimeType.try_report_as_string(&attr),
+                b"mimeType" => MimeType.try_report_as_media_type(MimeTypeParameters, &attr),
                 b"codingDependency" => CodingDependency.try_report_as_bool(&attr),
-                b"frameRate" => FrameRate.try_report_as_maybe_division(&attr),
+                b"frameRate" => FrameRate.try_report_as_frame_rate(FrameRateRatio, &attr),
+                b"sar" => Sar.try_report_as_aspect_ratio(&attr),
                 b"height" => Height.try_report_as_u64(&attr),
                 b"width" => Width.try_report_as_u64(&attr),
                 b"maxPlayoutRate" => MaxPlayoutRate.try_report_as_f64(&attr),
@@ -109,22 +129,27 @@ pub fn report_adaptation_set_attrs(e: &quick_xml::events::BytesStart) {
     }
 }
 
-pub fn report_representation_attrs(tag_bs: &quick_xml::events::BytesStart) {
+pub fn report_representation_attrs<R>(
+    tag_bs: &quick_xml::events::BytesStart,
+    reader: &NsReader<R>,
+) {
     for res_attr in tag_bs.attributes() {
         match res_attr {
-            Ok(attr) => match attr.key.as_ref() {
+            Ok(attr) => match canonical_attr_name(reader, attr.key).as_ref() {
                 b"id" => Id.try_report_as_string(&attr),
                 b"audioSamplingRate" => AudioSamplingRate.try_report_as_string(&attr),
                 b"bandwidth" => Bitrate.try_report_as_u64(&attr),
-                b"codecs" => Codecs.try_report_as_string(&attr),
-                b"scte214:supplementalCodecs" => SupplementalCodecs.try_report_as_string(&attr),
+                b"codecs" => Codecs.try_report_as_codecs(ParsedCodecs, &attr),
+                b"scte214:supplementalCodecs" => SupplementalCodecs
+                    .try_report_as_supplemental_codecs(ParsedSupplementalCodecs, &attr),
                 b"codingDependency" => CodingDependency.try_report_as_bool(&attr),
-                b"frameRate" => FrameRate.try_report_as_maybe_division(&attr),
+                b"frameRate" => FrameRate.try_report_as_frame_rate(FrameRateRatio, &attr),
+                b"sar" => Sar.try_report_as_aspect_ratio(&attr),
                 b"height" => Height.try_report_as_u64(&attr),
                 b"width" => Width.try_report_as_u64(&attr),
                 b"maxPlayoutRate" => MaxPlayoutRate.try_report_as_f64(&attr),
                 b"maxSAPPeriod" => MaxSAPPeriod.try_report_as_f64(&attr),
-                b"mimeType" => MimeType.try_report_as_string(&attr),
+                b"mimeType" => MimeType.try_report_as_media_type(MimeTypeParameters, &attr),
                 b"profiles" => Profiles.try_report_as_string(&attr),
                 b"qualityRanking" => QualityRanking.try_report_as_u64(&attr),
                 b"segmentProfiles" => SegmentProfiles.try_report_as_string(&attr),
@@ -153,12 +178,23 @@ pub fn report_base_url_attrs(tag_bs: &quick_xml::events::BytesStart) {
     }
 }
 
-pub fn report_segment_template_attrs(tag_bs: &quick_xml::events::BytesStart) {
+/// Report attributes encountered in a `<SegmentTemplate>` element.
+///
+/// Returns its `media` and `initialization` URL templates, compiled while
+/// checking them.
+pub fn report_segment_template_attrs(
+    tag_bs: &quick_xml::events::BytesStart,
+) -> SegmentTemplateUrls {
+    let mut urls = SegmentTemplateUrls::default();
     for res_attr in tag_bs.attributes() {
         match res_attr {
             Ok(attr) => match attr.key.as_ref() {
-                b"initialization" => InitializationMedia.try_report_as_string(&attr),
-                b"index" => Index.try_report_as_string(&attr),
+                b"initialization" => {
+                    urls.initialization = InitializationMedia.try_report_as_url_template(&attr)
+                }
+                b"index" => {
+                    Index.try_report_as_url_template(&attr);
+                }
                 b"timescale" => TimeScale.try_report_as_u64(&attr),
                 b"presentationTimeOffset" => PresentationTimeOffset.try_report_as_f64(&attr),
                 b"indexRange" => IndexRange.try_report_as_range(&attr),
@@ -171,13 +207,14 @@ pub fn report_segment_template_attrs(tag_bs: &quick_xml::events::BytesStart) {
                 b"duration" => Duration.try_report_as_u64(&attr),
                 b"startNumber" => StartNumber.try_report_as_u64(&attr),
                 b"endNumber" => EndNumber.try_report_as_u64(&attr),
-                b"media" => Media.try_report_as_string(&attr),
+                b"media" => urls.media = Media.try_report_as_url_template(&attr),
                 b"bitstreamSwitching" => BitstreamSwitching.try_report_as_bool(&attr),
                 _ => {}
             },
             Err(err) => ParsingError::from(err).report_err(),
         };
     }
+    urls
 }
 
 pub fn report_segment_base_attrs(tag_bs: &quick_xml::events::BytesStart) {
@@ -210,7 +247,10 @@ pub fn report_content_component_attrs(tag_bs: &quick_xml::events::BytesStart) {
                 b"id" => Id.try_report_as_string(&attr),
                 b"lang" => Language.try_report_as_string(&attr),
                 b"contentType" => ContentType.try_report_as_string(&attr),
-                b"par" => Par.try_report_as_string(&attr),
+                b"par" => {
+                    Par.try_report_as_string(&attr);
+                    ParRatio.try_report_as_aspect_ratio(&attr);
+                }
                 _ => {}
             },
             Err(err) => ParsingError::from(err).report_err(),
@@ -218,17 +258,55 @@ pub fn report_content_component_attrs(tag_bs: &quick_xml::events::BytesStart) {
     }
 }
 
-pub fn report_content_protection_attrs(tag_bs: &quick_xml::events::BytesStart) {
+/// Report attributes encountered in a `<ContentProtection>` element.
+///
+/// `position` is the position in bytes in the MPD of the end of that element's
+/// opening tag, used in diagnostics.
+pub fn report_content_protection_attrs<R>(
+    tag_bs: &quick_xml::events::BytesStart,
+    reader: &NsReader<R>,
+    position: usize,
+) {
     for res_attr in tag_bs.attributes() {
         match res_attr {
-            Ok(attr) => match attr.key.as_ref() {
+            Ok(attr) => match canonical_attr_name(reader, attr.key).as_ref() {
                 b"schemeIdUri" => SchemeIdUri.try_report_as_string(&attr),
                 b"value" => ContentProtectionValue.try_report_as_string(&attr),
                 b"ref" => ContentProtectionRef.try_report_as_string(&attr),
                 b"refId" => ContentProtectionRefId.try_report_as_string(&attr),
+                b"cenc:default_KID" => match utils::parse_uuid(&attr.value) {
+                    Ok(kid) => ContentProtectionKeyId.report(Cow::Borrowed(&kid[..])),
+                    Err(err) => ParsingError(format!(
+                        "Invalid cenc:default_KID in the ContentProtection ending at position {}: {}",
+                        position, err.0
+                    ))
+                    .report_err(),
+                },
+                _ => {}
+            },
+            Err(err) => ParsingError::from(err).report_err(),
+        };
+    }
+}
 
-                // TODO convert hex to bytes here?
-                b"cenc:default_KID" => ContentProtectionKeyId.try_report_as_string(&attr),
+/// Report attributes encountered in an element announcing a license server
+/// URL in a `<ContentProtection>`.
+///
+/// The type of license served is only checked here, it is reported with each
+/// URL it applies to (@see `LicenseUrlElement::license_type`).
+pub fn report_license_url_attrs(tag_bs: &quick_xml::events::BytesStart, elt: LicenseUrlElement) {
+    for res_attr in tag_bs.attributes() {
+        match res_attr {
+            Ok(attr) => match (attr.key.as_ref(), elt) {
+                (b"licenseUrl", LicenseUrlElement::LegacyPlayReady) => {
+                    ContentProtectionLicenseUrl.try_report_as_string(&attr)
+                }
+                (b"licenseType", LicenseUrlElement::DashIf)
+                | (b"Lic_type", LicenseUrlElement::ClearKey) => {
+                    if let Err(err) = attr.unescape_value() {
+                        ParsingError::from(err).report_err();
+                    }
+                }
                 _ => {}
             },
             Err(err) => ParsingError::from(err).report_err(),
@@ -269,6 +347,61 @@ pub fn report_scheme_attrs(tag_bs: &quick_xml::events::BytesStart) {
     }
 }
 
+/// Returns the unescaped `schemeIdUri` and `value` attributes of a descriptor
+/// element, such as an `<EssentialProperty>` or a `<Role>`.
+///
+/// Invalid attributes are ignored here, as they are already signaled when
+/// reported (@see `report_scheme_attrs`).
+pub fn scheme_attrs(tag_bs: &quick_xml::events::BytesStart) -> (Option<String>, Option<String>) {
+    let mut scheme_id_uri = None;
+    let mut value = None;
+    for attr in tag_bs.attributes().flatten() {
+        match attr.key.as_ref() {
+            b"schemeIdUri" => scheme_id_uri = attr.unescape_value().ok().map(Cow::into_owned),
+            b"value" => value = attr.unescape_value().ok().map(Cow::into_owned),
+            _ => {}
+        }
+    }
+    (scheme_id_uri, value)
+}
+
+/// Report the attributes of an `<EssentialProperty>` element, followed by the
+/// thumbnail tile grid it describes, if one, which is then returned.
+pub fn report_essential_property_attrs(tag_bs: &quick_xml::events::BytesStart) -> Option<TileGrid> {
+    report_scheme_attrs(tag_bs);
+    let (scheme_id_uri, value) = scheme_attrs(tag_bs);
+    match TileGrid::from_descriptor(&scheme_id_uri?, value.as_deref())? {
+        Ok(grid) => {
+            ThumbnailTile.report((grid.columns as f64, grid.rows as f64));
+            Some(grid)
+        }
+        Err(err) => {
+            err.report_err();
+            None
+        }
+    }
+}
+
+/// Report the attributes of an `<Accessibility>` element, followed by the
+/// captions or purpose it describes when its scheme is a known one.
+pub fn report_accessibility_attrs(tag_bs: &quick_xml::events::BytesStart) {
+    report_scheme_attrs(tag_bs);
+    let (scheme_id_uri, value) = scheme_attrs(tag_bs);
+    let (scheme_id_uri, value) = match (scheme_id_uri, value) {
+        (Some(scheme_id_uri), Some(value)) => (scheme_id_uri, value),
+        _ => return,
+    };
+    match accessibility::CaptionServices::from_descriptor(&scheme_id_uri, &value) {
+        Some(Ok(services)) => CaptionServices.report(&services),
+        Some(Err(err)) => err.report_err(),
+        None => {}
+    }
+    let flags = accessibility::purpose_flags(&scheme_id_uri, &value);
+    if flags != 0 {
+        AccessibilityPurpose.report(Cow::Borrowed(&[flags][..]));
+    }
+}
+
 pub fn report_segment_url_attrs(tag_bs: &quick_xml::events::BytesStart) {
     for res_attr in tag_bs.attributes() {
         match res_attr {
@@ -0,0 +1,292 @@
+use crate::errors::ParsingError;
+use crate::utils;
+use std::collections::HashMap;
+
+use super::namespaces::canonical_attr_name;
+use quick_xml::NsReader;
+
+/// Information of a `<ContentProtection>` element which can be inherited by
+/// the descriptors referencing it through their `ref` attribute.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentProtectionDescriptor {
+    /// The `schemeIdUri` attribute.
+    pub scheme_id_uri: Option<String>,
+
+    /// The `value` attribute.
+    pub value: Option<String>,
+
+    /// The `cenc:default_KID` attribute, decoded.
+    pub key_id: Option<[u8; 16]>,
+
+    /// The `ref` attribute, identifying the descriptor this one references.
+    pub reference: Option<String>,
+
+    /// The `refId` attribute, identifying this descriptor so it can be
+    /// referenced.
+    pub ref_id: Option<String>,
+
+    /// Decoded content of each `<cenc:pssh>` child.
+    pub pssh: Vec<Vec<u8>>,
+
+    /// Decoded content of each `<mspr:pro>` child.
+    pub playready_objects: Vec<Vec<u8>>,
+
+    /// License server URLs announced by its children, with the type of
+    /// license they serve if known.
+    pub license_urls: Vec<(String, Option<String>)>,
+
+    /// Position in bytes in the MPD of the end of its opening tag.
+    pub position: usize,
+}
+
+impl ContentProtectionDescriptor {
+    /// Creates a new `ContentProtectionDescriptor` from the attributes of a
+    /// `<ContentProtection>` element.
+    ///
+    /// Its content has to be added separately, as its children are parsed.
+    /// Invalid attributes are ignored here, as they are already signaled when
+    /// reported.
+    pub fn from_element<R>(
+        tag: &quick_xml::events::BytesStart,
+        reader: &NsReader<R>,
+        position: usize,
+    ) -> ContentProtectionDescriptor {
+        let mut descriptor = ContentProtectionDescriptor {
+            position,
+            ..Default::default()
+        };
+        for attr in tag.attributes().flatten() {
+            let value = match attr.unescape_value() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            match canonical_attr_name(reader, attr.key).as_ref() {
+                b"schemeIdUri" => descriptor.scheme_id_uri = Some(value.into_owned()),
+                b"value" => descriptor.value = Some(value.into_owned()),
+                b"cenc:default_KID" => descriptor.key_id = utils::parse_uuid(&attr.value).ok(),
+                b"ref" => descriptor.reference = Some(value.into_owned()),
+                b"refId" => descriptor.ref_id = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        descriptor
+    }
+
+    /// Returns the information this descriptor inherits from the `referenced`
+    /// one: attributes it does not define itself, all `pssh` boxes and
+    /// PlayReady Objects, and license server URLs if it announces none.
+    pub fn inherited_from(
+        &self,
+        referenced: &ContentProtectionDescriptor,
+    ) -> ContentProtectionDescriptor {
+        ContentProtectionDescriptor {
+            scheme_id_uri: match self.scheme_id_uri {
+                None => referenced.scheme_id_uri.clone(),
+                Some(_) => None,
+            },
+            value: match self.value {
+                None => referenced.value.clone(),
+                Some(_) => None,
+            },
+            key_id: match self.key_id {
+                None => referenced.key_id,
+                Some(_) => None,
+            },
+            reference: None,
+            ref_id: None,
+            pssh: referenced.pssh.clone(),
+            playready_objects: referenced.playready_objects.clone(),
+            license_urls: if self.license_urls.is_empty() {
+                referenced.license_urls.clone()
+            } else {
+                Vec::new()
+            },
+            position: self.position,
+        }
+    }
+
+    /// Returns this descriptor completed with what it inherits from the one
+    /// it references (@see `inherited_from`).
+    pub fn completed_with(
+        &self,
+        inherited: &ContentProtectionDescriptor,
+    ) -> ContentProtectionDescriptor {
+        let mut completed = self.clone();
+        completed.scheme_id_uri = self
+            .scheme_id_uri
+            .clone()
+            .or_else(|| inherited.scheme_id_uri.clone());
+        completed.value = self.value.clone().or_else(|| inherited.value.clone());
+        completed.key_id = self.key_id.or(inherited.key_id);
+        completed.pssh.extend(inherited.pssh.iter().cloned());
+        completed
+            .playready_objects
+            .extend(inherited.playready_objects.iter().cloned());
+        completed
+            .license_urls
+            .extend(inherited.license_urls.iter().cloned());
+        completed
+    }
+}
+
+/// Descriptors defined in the current `<Period>` which can be referenced by
+/// other `<ContentProtection>` elements, indexed by their `refId` attribute.
+///
+/// As a descriptor may reference one defined after it, references which are
+/// not resolved when their element is closed are only considered as dangling
+/// once the whole `<Period>` is known.
+#[derive(Default)]
+pub struct ContentProtectionRefs {
+    in_period: HashMap<String, ContentProtectionDescriptor>,
+
+    /// References which could not be resolved when their element was closed,
+    /// with the position of that element.
+    unresolved: Vec<(String, usize)>,
+}
+
+impl ContentProtectionRefs {
+    /// Forget all descriptors, to call when the `<Period>` they are defined
+    /// in is closed, or when the first `<Period>` is opened for those defined
+    /// directly in the `<MPD>`.
+    ///
+    /// Returns an error for each reference to a descriptor which was not
+    /// found in it.
+    pub fn close_period(&mut self) -> Vec<ParsingError> {
+        let in_period = &self.in_period;
+        let errors = self
+            .unresolved
+            .drain(..)
+            .filter(|(reference, _)| !in_period.contains_key(reference))
+            .map(|(reference, position)| {
+                ParsingError(format!(
+                    "Dangling ContentProtection ref \"{}\" at position {}: no ContentProtection \
+                     with that refId was found in the Period",
+                    reference, position
+                ))
+            })
+            .collect();
+        self.in_period.clear();
+        errors
+    }
+
+    /// Register a descriptor which has just been entirely parsed.
+    ///
+    /// Returns an error if another descriptor of the same Period already had
+    /// the same `refId`, in which case it is replaced.
+    pub fn add(&mut self, descriptor: ContentProtectionDescriptor) -> Result<(), ParsingError> {
+        let ref_id = match &descriptor.ref_id {
+            Some(ref_id) => ref_id.clone(),
+            None => return Ok(()),
+        };
+        let position = descriptor.position;
+        match self.in_period.insert(ref_id.clone(), descriptor) {
+            Some(_) => Err(ParsingError(format!(
+                "Duplicate ContentProtection refId \"{}\" in the ContentProtection ending \
+                 at position {}",
+                ref_id, position
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the information the given descriptor inherits from the one it
+    /// references, or `None` if it references nothing or a descriptor which
+    /// has not been found yet in the current Period.
+    ///
+    /// In the latter case, the reference is checked again once the Period is
+    /// closed (@see `close_period`).
+    pub fn resolve(
+        &mut self,
+        descriptor: &ContentProtectionDescriptor,
+    ) -> Option<ContentProtectionDescriptor> {
+        let reference = descriptor.reference.as_ref()?;
+        match self.in_period.get(reference) {
+            Some(referenced) => Some(descriptor.inherited_from(referenced)),
+            None => {
+                self.unresolved
+                    .push((reference.clone(), descriptor.position));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let mut refs = ContentProtectionRefs::default();
+        let defined = ContentProtectionDescriptor {
+            scheme_id_uri: Some("urn:mpeg:dash:mp4protection:2011".to_owned()),
+            value: Some("cenc".to_owned()),
+            key_id: Some([1; 16]),
+            ref_id: Some("1".to_owned()),
+            pssh: vec![vec![0, 1]],
+            license_urls: vec![("https://example.com".to_owned(), None)],
+            ..Default::default()
+        };
+        refs.add(defined.clone()).unwrap();
+        assert!(refs.add(defined).is_err());
+
+        let referencing = ContentProtectionDescriptor {
+            value: Some("cbcs".to_owned()),
+            reference: Some("1".to_owned()),
+            pssh: vec![vec![2]],
+            ..Default::default()
+        };
+        let inherited = refs.resolve(&referencing).unwrap();
+        assert_eq!(
+            inherited.scheme_id_uri.as_deref(),
+            Some("urn:mpeg:dash:mp4protection:2011")
+        );
+        assert!(inherited.value.is_none());
+        assert_eq!(inherited.key_id, Some([1; 16]));
+        assert_eq!(inherited.pssh, vec![vec![0, 1]]);
+        assert_eq!(inherited.license_urls.len(), 1);
+
+        let with_kid = ContentProtectionDescriptor {
+            key_id: Some([2; 16]),
+            ..referencing.clone()
+        };
+        assert!(refs.resolve(&with_kid).unwrap().key_id.is_none());
+
+        assert!(refs
+            .resolve(&ContentProtectionDescriptor::default())
+            .is_none());
+        let dangling = ContentProtectionDescriptor {
+            reference: Some("2".to_owned()),
+            ..referencing
+        };
+        assert!(refs.resolve(&dangling).is_none());
+        assert_eq!(refs.close_period().len(), 1);
+
+        assert!(refs.resolve(&with_kid).is_none());
+        assert_eq!(refs.close_period().len(), 1);
+        assert!(refs.close_period().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_forward_reference() {
+        let mut refs = ContentProtectionRefs::default();
+        let referencing = ContentProtectionDescriptor {
+            reference: Some("1".to_owned()),
+            position: 10,
+            ..Default::default()
+        };
+        assert!(refs.resolve(&referencing).is_none());
+        refs.add(ContentProtectionDescriptor {
+            ref_id: Some("1".to_owned()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(refs.close_period().is_empty());
+
+        // Not found in the next Period
+        assert!(refs.resolve(&referencing).is_none());
+        let errors = refs.close_period();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].0.contains("at position 10"));
+    }
+}
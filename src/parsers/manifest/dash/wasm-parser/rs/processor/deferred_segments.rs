@@ -0,0 +1,117 @@
+use crate::errors::Result;
+use crate::periods::ResolvedPeriod;
+use crate::segment_index::{RepresentationContext, Segment, SegmentTemplate};
+use crate::thumbnails::TileGrid;
+
+use super::hierarchy::PERIOD;
+
+/// Segments of a `<Representation>` which could only be computed once the
+/// end of its `<Period>` is known, that is once the whole MPD has been read.
+///
+/// This is the case when that `<Period>` has no `duration` attribute, its end
+/// then being the start of the next `<Period>` or, for the last one, the end
+/// of the presentation.
+#[derive(Debug, Clone)]
+pub struct DeferredSegments {
+    /// Index of its `<Period>` in the MPD, of its `<AdaptationSet>` in that
+    /// `<Period>` and of the `<Representation>` in that `<AdaptationSet>`.
+    pub positions: [usize; 3],
+
+    /// The `<SegmentTemplate>` applying to it.
+    pub template: SegmentTemplate,
+
+    /// Its `id` attribute, empty if not set.
+    pub id: String,
+
+    /// Its `bandwidth` attribute.
+    pub bandwidth: Option<u64>,
+
+    /// The thumbnail tile grid applying to it, if one, with the width and
+    /// height of its images if known.
+    pub thumbnails: Option<(TileGrid, Option<(u32, u32)>)>,
+}
+
+impl DeferredSegments {
+    /// Computes its segments overlapping the time range going from `from`
+    /// (included) to `to` (not included), given the resolved time bounds of
+    /// the `<Period>` elements of the MPD, if those could be resolved.
+    ///
+    /// If the end of its `<Period>` is still unknown, segments are computed
+    /// as if it was not bounded.
+    pub fn get_segments(
+        &self,
+        periods: Option<&[ResolvedPeriod]>,
+        from: f64,
+        to: f64,
+    ) -> Result<Vec<Segment>> {
+        let period_duration = periods
+            .and_then(|periods| periods.get(self.positions[PERIOD]))
+            .and_then(|period| period.end.map(|end| end - period.start));
+        let ctx = RepresentationContext {
+            id: &self.id,
+            bandwidth: self.bandwidth,
+            period_duration,
+        };
+        self.template.get_segments(&ctx, from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::periods::{self, MpdTimeInfo, PeriodTimeInfo};
+    use crate::url_template::UrlTemplate;
+
+    fn deferred(period_idx: usize) -> DeferredSegments {
+        DeferredSegments {
+            positions: [period_idx, 0, 0],
+            template: SegmentTemplate {
+                media: UrlTemplate::compile("$Number$.m4s").ok(),
+                timescale: Some(1000),
+                duration: Some(4000),
+                ..Default::default()
+            },
+            id: "a".to_owned(),
+            bandwidth: None,
+            thumbnails: None,
+        }
+    }
+
+    #[test]
+    fn test_duration_less_periods() {
+        // A single Period ending with the presentation
+        let mpd = MpdTimeInfo {
+            media_presentation_duration: Some(10.),
+            ..MpdTimeInfo::default()
+        };
+        let resolved = periods::resolve(&mpd, &[PeriodTimeInfo::default()]).unwrap();
+        let segments = deferred(0)
+            .get_segments(Some(&resolved), 0., f64::INFINITY)
+            .unwrap();
+        let bounds: Vec<(f64, f64)> = segments.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(bounds, vec![(0., 4.), (4., 8.), (8., 10.)]);
+        assert_eq!(segments[2].url.as_deref(), Some("3.m4s"));
+
+        // A Period ending at the start of the next one
+        let periods = [
+            PeriodTimeInfo::default(),
+            PeriodTimeInfo {
+                start: Some(6.),
+                ..PeriodTimeInfo::default()
+            },
+        ];
+        let resolved = periods::resolve(&MpdTimeInfo::default(), &periods).unwrap();
+        let segments = deferred(0)
+            .get_segments(Some(&resolved), 0., f64::INFINITY)
+            .unwrap();
+        let bounds: Vec<(f64, f64)> = segments.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(bounds, vec![(0., 4.), (4., 6.)]);
+
+        // Still unbounded when the end of the presentation is unknown
+        assert!(deferred(1)
+            .get_segments(Some(&resolved), 0., f64::INFINITY)
+            .is_err());
+        assert!(deferred(0).get_segments(None, 0., f64::INFINITY).is_err());
+        assert_eq!(deferred(0).get_segments(None, 0., 8.).unwrap().len(), 2);
+    }
+}
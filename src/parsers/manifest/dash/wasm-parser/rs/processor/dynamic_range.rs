@@ -0,0 +1,110 @@
+use crate::codecs;
+use crate::hdr::VideoDescriptors;
+
+use super::attributes;
+use super::namespaces::canonical_attr_name;
+use quick_xml::NsReader;
+
+/// Collects the information needed to classify the dynamic range and to
+/// pair the codec layers of each `<Representation>`, on it and on its parent
+/// elements.
+#[derive(Default)]
+pub struct DynamicRangeTracker {
+    /// Descriptors found on the `<MPD>` element.
+    mpd: VideoDescriptors,
+
+    /// Descriptors found on the current `<AdaptationSet>` and its children
+    /// other than `<Representation>` elements.
+    adaptation_set: Option<VideoDescriptors>,
+
+    /// Descriptors found on the current `<Representation>` and its children.
+    representation: Option<VideoDescriptors>,
+}
+
+impl DynamicRangeTracker {
+    /// Signal that the `<MPD>` element has been opened.
+    pub fn open_mpd<R>(&mut self, tag: &quick_xml::events::BytesStart, reader: &NsReader<R>) {
+        *self = DynamicRangeTracker::default();
+        self.mpd = descriptors_from_element(tag, reader);
+    }
+
+    /// Signal that an `<AdaptationSet>` element has been opened.
+    pub fn open_adaptation_set<R>(
+        &mut self,
+        tag: &quick_xml::events::BytesStart,
+        reader: &NsReader<R>,
+    ) {
+        self.adaptation_set = Some(descriptors_from_element(tag, reader));
+        self.representation = None;
+    }
+
+    /// Signal that the current `<AdaptationSet>` element has been closed.
+    pub fn close_adaptation_set(&mut self) {
+        self.adaptation_set = None;
+        self.representation = None;
+    }
+
+    /// Signal that a `<Representation>` element has been opened.
+    pub fn open_representation<R>(
+        &mut self,
+        tag: &quick_xml::events::BytesStart,
+        reader: &NsReader<R>,
+    ) {
+        self.representation = Some(descriptors_from_element(tag, reader));
+    }
+
+    /// Signal that the current `<Representation>` element has been closed,
+    /// returning its descriptors combined with those of its parents.
+    pub fn close_representation(&mut self) -> Option<VideoDescriptors> {
+        let representation = self.representation.take()?;
+        let representation = match self.adaptation_set.as_ref() {
+            Some(adaptation_set) => representation.inherit(adaptation_set),
+            None => representation,
+        };
+        Some(representation.inherit(&self.mpd))
+    }
+
+    /// Takes into account an `<EssentialProperty>` or `<SupplementalProperty>`
+    /// element, for the innermost `<AdaptationSet>` or `<Representation>`
+    /// currently open. Those found elsewhere are ignored.
+    pub fn add_property(&mut self, tag: &quick_xml::events::BytesStart) {
+        let descriptors = match (self.representation.as_mut(), self.adaptation_set.as_mut()) {
+            (Some(descriptors), _) | (None, Some(descriptors)) => descriptors,
+            (None, None) => return,
+        };
+        let (scheme_id_uri, value) = attributes::scheme_attrs(tag);
+        if let Some(scheme_id_uri) = scheme_id_uri {
+            if let Err(err) = descriptors.add_property(&scheme_id_uri, value.as_deref()) {
+                err.report_err();
+            }
+        }
+    }
+}
+
+/// Reads the attributes relevant to the dynamic range of an `<MPD>`,
+/// `<AdaptationSet>` or `<Representation>` element.
+///
+/// Invalid attributes are ignored here, as they are already signaled when
+/// reported.
+fn descriptors_from_element<R>(
+    tag: &quick_xml::events::BytesStart,
+    reader: &NsReader<R>,
+) -> VideoDescriptors {
+    let mut descriptors = VideoDescriptors::default();
+    for attr in tag.attributes().flatten() {
+        let value = match attr.unescape_value() {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        match canonical_attr_name(reader, attr.key).as_ref() {
+            b"codecs" => descriptors.codecs = codecs::parse_codecs(&value).0,
+            b"scte214:supplementalCodecs" => {
+                descriptors.supplemental_codecs = codecs::parse_codecs(&value).0
+            }
+            b"mimeType" => descriptors.set_mime_type(&value),
+            b"profiles" => descriptors.set_profiles(&value),
+            _ => {}
+        }
+    }
+    descriptors
+}
@@ -0,0 +1,341 @@
+use crate::errors::ParsingError;
+use crate::thumbnails::THUMBNAIL_TILE_SCHEMES;
+use quick_xml::events::Event;
+
+use super::attributes;
+use super::trick_mode::TRICK_MODE_SCHEME;
+
+/// Element which has to be ignored because of an `<EssentialProperty>` whose
+/// scheme is not supported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnsupportedElement {
+    AdaptationSet,
+    Representation,
+}
+
+/// An `<EssentialProperty>` whose scheme is not supported.
+#[derive(Debug, Clone)]
+pub struct UnsupportedProperty {
+    /// Element which has to be ignored because of it.
+    pub element: UnsupportedElement,
+
+    /// `true` if that element was not already known to have to be ignored.
+    pub newly_unsupported: bool,
+
+    /// Description of why that element has to be ignored.
+    pub error: ParsingError,
+}
+
+/// Evaluates the `<EssentialProperty>` elements of the current
+/// `<AdaptationSet>` and `<Representation>` against the schemes supported by
+/// the host.
+///
+/// As defined by the DASH specification, an element with an
+/// `<EssentialProperty>` whose scheme is not understood should be ignored.
+#[derive(Default)]
+pub struct EssentialPropertyEvaluator {
+    /// State of the current `<AdaptationSet>`, if one is open.
+    adaptation_set: Option<ElementState>,
+
+    /// State of the current `<Representation>`, if one is open.
+    representation: Option<ElementState>,
+}
+
+/// An `<AdaptationSet>` or `<Representation>` element currently open.
+#[derive(Default)]
+struct ElementState {
+    /// Raw name of the element, as written in the MPD.
+    raw_name: Vec<u8>,
+
+    /// Its `id` attribute, empty if not set.
+    id: String,
+
+    /// Unsupported schemes of its `<EssentialProperty>` children, in
+    /// document order.
+    unsupported_schemes: Vec<String>,
+}
+
+impl ElementState {
+    fn from_element(tag: &quick_xml::events::BytesStart) -> ElementState {
+        let id = tag
+            .try_get_attribute("id")
+            .ok()
+            .flatten()
+            .and_then(|attr| attr.unescape_value().ok())
+            .map(|id| id.into_owned())
+            .unwrap_or_default();
+        ElementState {
+            raw_name: tag.name().as_ref().to_vec(),
+            id,
+            unsupported_schemes: Vec::new(),
+        }
+    }
+}
+
+impl EssentialPropertyEvaluator {
+    /// Signal that an `<AdaptationSet>` element has been opened.
+    pub fn open_adaptation_set(&mut self, tag: &quick_xml::events::BytesStart) {
+        self.adaptation_set = Some(ElementState::from_element(tag));
+        self.representation = None;
+    }
+
+    /// Signal that the current `<AdaptationSet>` element has been closed,
+    /// returning the unsupported schemes of its `<EssentialProperty>`
+    /// elements.
+    pub fn close_adaptation_set(&mut self) -> Vec<String> {
+        self.representation = None;
+        self.adaptation_set
+            .take()
+            .map(|state| state.unsupported_schemes)
+            .unwrap_or_default()
+    }
+
+    /// Signal that a `<Representation>` element has been opened.
+    pub fn open_representation(&mut self, tag: &quick_xml::events::BytesStart) {
+        self.representation = Some(ElementState::from_element(tag));
+    }
+
+    /// Signal that the current `<Representation>` element has been closed,
+    /// returning the unsupported schemes of its `<EssentialProperty>`
+    /// elements.
+    pub fn close_representation(&mut self) -> Vec<String> {
+        self.representation
+            .take()
+            .map(|state| state.unsupported_schemes)
+            .unwrap_or_default()
+    }
+
+    /// Returns the raw name, as written in the MPD, of the given element if
+    /// it is currently open.
+    pub fn raw_name(&self, element: UnsupportedElement) -> Option<&[u8]> {
+        let state = match element {
+            UnsupportedElement::AdaptationSet => self.adaptation_set.as_ref(),
+            UnsupportedElement::Representation => self.representation.as_ref(),
+        };
+        state.map(|state| state.raw_name.as_slice())
+    }
+
+    /// Takes into account an `<EssentialProperty>` element of the innermost
+    /// `<AdaptationSet>` or `<Representation>` currently open, given the
+    /// schemes supported by the host.
+    ///
+    /// Returns what has to be ignored because of it, and why, if its scheme
+    /// is not supported.
+    pub fn add_property(
+        &mut self,
+        tag: &quick_xml::events::BytesStart,
+        supported_schemes: &[String],
+    ) -> Option<UnsupportedProperty> {
+        let (state, element) = match (self.representation.as_mut(), self.adaptation_set.as_mut()) {
+            (Some(state), _) => (state, UnsupportedElement::Representation),
+            (None, Some(state)) => (state, UnsupportedElement::AdaptationSet),
+            (None, None) => return None,
+        };
+        let scheme_id_uri = attributes::scheme_attrs(tag)
+            .0
+            .map(|scheme| scheme.trim().to_owned())
+            .unwrap_or_default();
+        if is_supported(&scheme_id_uri, supported_schemes) {
+            return None;
+        }
+        let newly_unsupported = state.unsupported_schemes.is_empty();
+        let element_name = match element {
+            UnsupportedElement::AdaptationSet => "AdaptationSet",
+            UnsupportedElement::Representation => "Representation",
+        };
+        let designation = if state.id.is_empty() {
+            element_name.to_owned()
+        } else {
+            format!("{} \"{}\"", element_name, state.id)
+        };
+        let error = ParsingError(format!(
+            "{} should be ignored: its EssentialProperty scheme \"{}\" is not supported",
+            designation, scheme_id_uri
+        ));
+        state.unsupported_schemes.push(scheme_id_uri);
+        Some(UnsupportedProperty {
+            element,
+            newly_unsupported,
+            error,
+        })
+    }
+}
+
+/// Progress made in skipping the content of an element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkipProgress {
+    /// The end of the element has not been reached yet.
+    Pending,
+
+    /// The end of the element has just been reached.
+    Ended,
+
+    /// The document ended before the element.
+    UnexpectedEof,
+}
+
+/// Follows the content of an element being skipped, to know when it ends.
+pub struct SkippedElement {
+    /// Raw name of the element, as written in the MPD.
+    raw_name: Vec<u8>,
+
+    /// Number of inner elements with the same name currently open, so their
+    /// end is not mistaken for the one of the skipped element.
+    inner_depth: u32,
+}
+
+impl SkippedElement {
+    /// Starts skipping the content of an element, whose start tag has already
+    /// been read, with the given raw name.
+    pub fn new(raw_name: &[u8]) -> SkippedElement {
+        SkippedElement {
+            raw_name: raw_name.to_vec(),
+            inner_depth: 0,
+        }
+    }
+
+    /// Takes into account the next event read in the element.
+    pub fn process(&mut self, event: &Event) -> SkipProgress {
+        match event {
+            Event::Start(tag) if tag.name().as_ref() == self.raw_name.as_slice() => {
+                self.inner_depth += 1;
+                SkipProgress::Pending
+            }
+            Event::End(tag) if tag.name().as_ref() == self.raw_name.as_slice() => {
+                if self.inner_depth > 0 {
+                    self.inner_depth -= 1;
+                    SkipProgress::Pending
+                } else {
+                    SkipProgress::Ended
+                }
+            }
+            Event::Eof => SkipProgress::UnexpectedEof,
+            _ => SkipProgress::Pending,
+        }
+    }
+}
+
+/// Returns `true` if the given `<EssentialProperty>` scheme is one of the
+/// schemes supported by the host.
+///
+/// The trick mode and thumbnail tile schemes, which are understood by the
+/// parser itself, are always considered as supported.
+fn is_supported(scheme_id_uri: &str, supported_schemes: &[String]) -> bool {
+    scheme_id_uri == TRICK_MODE_SCHEME
+        || THUMBNAIL_TILE_SCHEMES.contains(&scheme_id_uri)
+        || supported_schemes
+            .iter()
+            .any(|supported| supported.trim() == scheme_id_uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::BytesStart;
+    use quick_xml::Reader;
+
+    fn property(scheme_id_uri: &str) -> BytesStart<'static> {
+        BytesStart::from_content(
+            format!("EssentialProperty schemeIdUri=\"{}\"", scheme_id_uri),
+            17,
+        )
+    }
+
+    /// Skips the content of the element whose start tag precedes `content`,
+    /// returning the outcome and the name of the next element's start tag.
+    fn skip(raw_name: &[u8], content: &str) -> (SkipProgress, Option<String>) {
+        let mut reader = Reader::from_str(content);
+        reader.expand_empty_elements(true);
+        reader.check_end_names(false);
+        let mut skipped = SkippedElement::new(raw_name);
+        let progress = loop {
+            match skipped.process(&reader.read_event().unwrap()) {
+                SkipProgress::Pending => {}
+                progress => break progress,
+            }
+        };
+        let next = loop {
+            match reader.read_event().unwrap() {
+                Event::Start(tag) => {
+                    break Some(String::from_utf8(tag.name().as_ref().to_vec()).unwrap())
+                }
+                Event::Eof => break None,
+                _ => {}
+            }
+        };
+        (progress, next)
+    }
+
+    #[test]
+    fn test_skip_element() {
+        assert_eq!(
+            skip(
+                b"AdaptationSet",
+                "<Representation/><AdaptationSet><AdaptationSet></AdaptationSet>\
+                 </AdaptationSet></AdaptationSet><AdaptationSet>",
+            ),
+            (SkipProgress::Ended, Some("AdaptationSet".to_owned()))
+        );
+        assert_eq!(
+            skip(
+                b"dash:Representation",
+                "<Representation></Representation></dash:Representation><Next>",
+            ),
+            (SkipProgress::Ended, Some("Next".to_owned()))
+        );
+        assert_eq!(
+            skip(b"AdaptationSet", "<AdaptationSet></AdaptationSet><Next>"),
+            (SkipProgress::UnexpectedEof, None)
+        );
+    }
+
+    #[test]
+    fn test_attribution() {
+        let supported = vec!["urn:supported".to_owned()];
+        let mut evaluator = EssentialPropertyEvaluator::default();
+        assert!(evaluator
+            .add_property(&property("urn:foo"), &supported)
+            .is_none());
+
+        evaluator.open_adaptation_set(&BytesStart::from_content("AdaptationSet id=\"a\"", 13));
+        assert!(evaluator
+            .add_property(&property("urn:supported"), &supported)
+            .is_none());
+        assert!(evaluator
+            .add_property(&property(TRICK_MODE_SCHEME), &supported)
+            .is_none());
+        assert!(evaluator
+            .add_property(&property(THUMBNAIL_TILE_SCHEMES[0]), &supported)
+            .is_none());
+
+        evaluator.open_representation(&BytesStart::new("dash:Representation"));
+        let unsupported = evaluator.add_property(&property("urn:foo"), &supported);
+        let unsupported = unsupported.unwrap();
+        assert_eq!(unsupported.element, UnsupportedElement::Representation);
+        assert!(unsupported.newly_unsupported);
+        assert_eq!(
+            unsupported.error.0,
+            "Representation should be ignored: its EssentialProperty scheme \"urn:foo\" \
+             is not supported"
+        );
+        let unsupported = evaluator.add_property(&property(" urn:bar "), &supported);
+        assert!(!unsupported.unwrap().newly_unsupported);
+        assert_eq!(
+            evaluator.raw_name(UnsupportedElement::Representation),
+            Some(&b"dash:Representation"[..])
+        );
+        assert_eq!(evaluator.close_representation(), vec!["urn:foo", "urn:bar"]);
+        assert_eq!(evaluator.raw_name(UnsupportedElement::Representation), None);
+
+        let unsupported = evaluator.add_property(&property("urn:baz"), &supported);
+        let unsupported = unsupported.unwrap();
+        assert_eq!(unsupported.element, UnsupportedElement::AdaptationSet);
+        assert!(unsupported.newly_unsupported);
+        assert_eq!(
+            unsupported.error.0,
+            "AdaptationSet \"a\" should be ignored: its EssentialProperty scheme \
+             \"urn:baz\" is not supported"
+        );
+        assert_eq!(evaluator.close_adaptation_set(), vec!["urn:baz"]);
+    }
+}
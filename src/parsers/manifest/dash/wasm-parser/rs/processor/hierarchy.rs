@@ -0,0 +1,589 @@
+use crate::errors::ParsingError;
+use crate::processor::SegmentObject;
+use crate::segment_index::{RepresentationContext, SegmentTemplate};
+use crate::thumbnails::TileGrid;
+use crate::url;
+use crate::utils;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::BytesStart;
+use quick_xml::name::QName;
+use quick_xml::NsReader;
+use std::borrow::Cow;
+
+use super::content_protection::ContentProtectionDescriptor;
+use super::namespaces::canonical_attr_name;
+
+/// Depth of a `<Period>` element in a `Hierarchy`.
+pub const PERIOD: usize = 0;
+
+/// Depth of an `<AdaptationSet>` element in a `Hierarchy`.
+pub const ADAPTATION_SET: usize = 1;
+
+/// Depth of a `<Representation>` element in a `Hierarchy`.
+pub const REPRESENTATION: usize = 2;
+
+/// Attributes of an element, as their canonical name (@see
+/// `namespaces::canonical_name`) and their raw (still escaped) value, in
+/// document order.
+type RawAttributes = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Attributes which an `<AdaptationSet>` shares with its `<Representation>`
+/// elements when they do not define them, by canonical name.
+const INHERITED_ATTRIBUTES: [&[u8]; 15] = [
+    b"profiles",
+    b"width",
+    b"height",
+    b"sar",
+    b"frameRate",
+    b"audioSamplingRate",
+    b"mimeType",
+    b"segmentProfiles",
+    b"codecs",
+    b"scte214:supplementalCodecs",
+    b"maximumSAPPeriod",
+    b"maxPlayoutRate",
+    b"codingDependency",
+    b"availabilityTimeOffset",
+    b"availabilityTimeComplete",
+];
+
+/// Information on the `<Period>`, `<AdaptationSet>` and `<Representation>`
+/// elements currently open.
+///
+/// It is only tracked when needed by an optional processing step, as it
+/// implies some copies.
+#[derive(Default)]
+pub struct Hierarchy {
+    /// `id` attributes of the currently open elements, indexed by their depth.
+    /// Empty if not set or if no element is open at that depth.
+    ids: [String; 3],
+
+    /// Index of each currently open element among the elements of the same
+    /// depth in its parent, indexed by their depth.
+    positions: [usize; 3],
+
+    /// Number of elements opened until now at each depth, in the current
+    /// parent element.
+    opened_counts: [usize; 3],
+
+    /// `<SegmentTemplate>` found directly in the currently open elements,
+    /// indexed by their depth.
+    segment_templates: [Option<SegmentTemplate>; 3],
+
+    /// Number of elements currently open.
+    level: usize,
+
+    /// `true` when a `<SegmentTemplate>` element is currently open.
+    in_segment_template: bool,
+
+    /// The `duration` attribute of the current `<Period>`, in seconds.
+    period_duration: Option<f64>,
+
+    /// The `bandwidth` attribute of the current `<Representation>`.
+    bandwidth: Option<u64>,
+
+    /// Resolved URLs of the `<BaseURL>` elements found directly in the
+    /// `<MPD>` element.
+    mpd_base_urls: Vec<String>,
+
+    /// Resolved URLs of the `<BaseURL>` elements found directly in the
+    /// currently open elements, indexed by their depth.
+    base_urls: [Vec<String>; 3],
+
+    /// Attributes of the currently open elements, indexed by their depth.
+    attributes: [RawAttributes; 3],
+
+    /// Attributes of the `<SegmentTemplate>` found directly in the currently
+    /// open elements, indexed by their depth.
+    segment_template_attributes: [Option<RawAttributes>; 3],
+
+    /// Attributes of the `<SegmentBase>` found directly in the currently open
+    /// elements, indexed by their depth.
+    segment_base_attributes: [Option<RawAttributes>; 3],
+
+    /// Attributes of the `<Initialization>` child of those `<SegmentBase>`.
+    initialization_attributes: [Option<RawAttributes>; 3],
+
+    /// `true` when a `<SegmentBase>` element is currently open.
+    in_segment_base: bool,
+
+    /// `<ContentProtection>` elements found directly in the currently open
+    /// elements, with what they inherit from the ones they reference,
+    /// indexed by their depth.
+    content_protections: [Vec<ContentProtectionDescriptor>; 3],
+
+    /// Thumbnail tile grids announced directly in the currently open
+    /// elements, indexed by their depth.
+    tile_grids: [Option<TileGrid>; 3],
+}
+
+impl Hierarchy {
+    /// Signal that an element at the given depth has been opened.
+    pub fn open<R>(&mut self, depth: usize, tag: &BytesStart, reader: &NsReader<R>) {
+        self.close(depth);
+        self.ids[depth] = get_attribute(tag, b"id").unwrap_or_default();
+        self.track_position(depth);
+        self.attributes[depth] = raw_attributes(tag, reader);
+        match depth {
+            PERIOD => {
+                self.period_duration = get_attribute(tag, b"duration").and_then(|val| {
+                    match utils::parse_iso_8601_duration(val.as_bytes()) {
+                        Ok(duration) => Some(duration),
+                        Err(err) => {
+                            err.report_err();
+                            None
+                        }
+                    }
+                })
+            }
+            REPRESENTATION => {
+                self.bandwidth = get_attribute(tag, b"bandwidth").and_then(|val| {
+                    match utils::parse_u64(val.as_bytes()) {
+                        Ok(bandwidth) => Some(bandwidth),
+                        Err(err) => {
+                            err.report_err();
+                            None
+                        }
+                    }
+                })
+            }
+            _ => {}
+        }
+    }
+
+    /// Signal that the element at the given depth has been closed, which also
+    /// closes all of its children.
+    pub fn close(&mut self, depth: usize) {
+        for id in self.ids[depth..].iter_mut() {
+            id.clear();
+        }
+        for template in self.segment_templates[depth..].iter_mut() {
+            *template = None;
+        }
+        for urls in self.base_urls[depth..].iter_mut() {
+            urls.clear();
+        }
+        for attributes in self.attributes[depth..].iter_mut() {
+            attributes.clear();
+        }
+        for attributes in self.segment_template_attributes[depth..].iter_mut() {
+            *attributes = None;
+        }
+        for attributes in self.segment_base_attributes[depth..].iter_mut() {
+            *attributes = None;
+        }
+        for attributes in self.initialization_attributes[depth..].iter_mut() {
+            *attributes = None;
+        }
+        for descriptors in self.content_protections[depth..].iter_mut() {
+            descriptors.clear();
+        }
+        for grid in self.tile_grids[depth..].iter_mut() {
+            *grid = None;
+        }
+        if depth == PERIOD {
+            self.period_duration = None;
+        }
+        self.bandwidth = None;
+        self.level = depth;
+        self.in_segment_template = false;
+        self.in_segment_base = false;
+    }
+
+    /// Update the level and the position, among the elements of the same
+    /// depth in its parent, of the element just opened at the given depth.
+    fn track_position(&mut self, depth: usize) {
+        self.positions[depth] = self.opened_counts[depth];
+        self.opened_counts[depth] += 1;
+        for count in self.opened_counts[depth + 1..].iter_mut() {
+            *count = 0;
+        }
+        self.level = depth + 1;
+    }
+
+    /// Returns identifiers of the currently open `<Period>`,
+    /// `<AdaptationSet>` and `<Representation>` elements: their `id`
+    /// attribute or, when not set, their index in their parent prefixed by a
+    /// `#` character. Empty for depths at which no element is open.
+    pub fn identifiers(&self) -> [String; 3] {
+        let mut identifiers: [String; 3] = Default::default();
+        for (depth, identifier) in identifiers.iter_mut().enumerate().take(self.level) {
+            *identifier = if self.ids[depth].is_empty() {
+                format!("#{}", self.positions[depth])
+            } else {
+                self.ids[depth].clone()
+            };
+        }
+        identifiers
+    }
+
+    /// Returns the index of the currently open `<Period>` in the MPD, of the
+    /// currently open `<AdaptationSet>` in that `<Period>` and of the
+    /// currently open `<Representation>` in that `<AdaptationSet>`.
+    pub fn positions(&self) -> [usize; 3] {
+        self.positions
+    }
+
+    /// Signal that a `<SegmentTemplate>` element has been opened in the
+    /// deepest element currently open.
+    pub fn open_segment_template<R>(
+        &mut self,
+        template: SegmentTemplate,
+        tag: &BytesStart,
+        reader: &NsReader<R>,
+    ) {
+        if self.level > 0 {
+            self.segment_templates[self.level - 1] = Some(template);
+            self.segment_template_attributes[self.level - 1] = Some(raw_attributes(tag, reader));
+            self.in_segment_template = true;
+        }
+    }
+
+    /// Signal that the current `<SegmentTemplate>` element has been closed.
+    pub fn close_segment_template(&mut self) {
+        self.in_segment_template = false;
+    }
+
+    /// Signal that a `<SegmentBase>` element has been opened in the deepest
+    /// element currently open.
+    pub fn open_segment_base<R>(&mut self, tag: &BytesStart, reader: &NsReader<R>) {
+        if self.level > 0 {
+            self.segment_base_attributes[self.level - 1] = Some(raw_attributes(tag, reader));
+            self.in_segment_base = true;
+        }
+    }
+
+    /// Signal that the current `<SegmentBase>` element has been closed.
+    pub fn close_segment_base(&mut self) {
+        self.in_segment_base = false;
+    }
+
+    /// Signal that an `<Initialization>` element has been found. It is only
+    /// considered in a `<SegmentBase>`.
+    pub fn set_initialization<R>(&mut self, tag: &BytesStart, reader: &NsReader<R>) {
+        if self.in_segment_base && self.level > 0 {
+            self.initialization_attributes[self.level - 1] = Some(raw_attributes(tag, reader));
+        }
+    }
+
+    /// Signal that a `<ContentProtection>` element has been entirely parsed
+    /// in the deepest element currently open, with what it inherits from the
+    /// one it references.
+    pub fn add_content_protection(&mut self, descriptor: ContentProtectionDescriptor) {
+        if self.level > 0 {
+            self.content_protections[self.level - 1].push(descriptor);
+        }
+    }
+
+    /// Signal that a thumbnail tile grid has been announced for the deepest
+    /// element currently open.
+    pub fn set_tile_grid(&mut self, grid: TileGrid) {
+        if self.level > 0 {
+            self.tile_grids[self.level - 1] = Some(grid);
+        }
+    }
+
+    /// Returns the thumbnail tile grid applying to the deepest element
+    /// currently open, if one.
+    pub fn tile_grid(&self) -> Option<TileGrid> {
+        self.tile_grids[..self.level]
+            .iter()
+            .rev()
+            .find_map(|grid| *grid)
+    }
+
+    /// Returns the unescaped value of the given attribute on the deepest
+    /// element currently open defining it, if one.
+    pub fn inherited_attribute(&self, key: &[u8]) -> Option<String> {
+        self.attributes[..self.level]
+            .iter()
+            .rev()
+            .flat_map(|attributes| attributes.iter())
+            .find(|(name, _)| name == key)
+            .and_then(|(_, value)| {
+                match quick_xml::escape::unescape(&String::from_utf8_lossy(value)) {
+                    Ok(val) => Some(val.into_owned()),
+                    Err(err) => {
+                        ParsingError::from(err).report_err();
+                        None
+                    }
+                }
+            })
+    }
+
+    /// Associate the given timeline to the `<SegmentTemplate>` currently open.
+    /// Does nothing if no `<SegmentTemplate>` is currently open.
+    pub fn set_segment_timeline(&mut self, timeline: &[SegmentObject]) {
+        if self.in_segment_template && self.level > 0 {
+            if let Some(template) = &mut self.segment_templates[self.level - 1] {
+                template.timeline = Some(timeline.to_vec());
+            }
+        }
+    }
+
+    /// Returns the `<SegmentTemplate>` applying to the deepest element
+    /// currently open, each of its attributes being taken from the closest
+    /// element defining it.
+    pub fn segment_template(&self) -> Option<SegmentTemplate> {
+        self.segment_templates[..self.level]
+            .iter()
+            .flatten()
+            .fold(None, |parent, template| match parent {
+                Some(parent) => Some(template.inherit(&parent)),
+                None => Some(template.clone()),
+            })
+    }
+
+    /// Returns the effective attributes of the current `<Representation>`:
+    /// its own, completed by those its `<AdaptationSet>` shares with it, as
+    /// a `<Representation>` element.
+    pub fn effective_attributes(&self) -> BytesStart<'static> {
+        let inherited: RawAttributes = self.attributes[ADAPTATION_SET]
+            .iter()
+            .filter(|(name, _)| INHERITED_ATTRIBUTES.contains(&name.as_slice()))
+            .cloned()
+            .collect();
+        to_element(
+            "Representation",
+            &merge_attributes(&self.attributes[REPRESENTATION], &inherited),
+        )
+    }
+
+    /// Returns the effective `<SegmentTemplate>` of the current
+    /// `<Representation>`, each of its attributes being taken from the
+    /// closest element defining it, or `None` if there's none.
+    pub fn effective_segment_template(&self) -> Option<BytesStart<'static>> {
+        merge_levels(&self.segment_template_attributes[..self.level])
+            .map(|attributes| to_element("SegmentTemplate", &attributes))
+    }
+
+    /// Returns the effective `<SegmentBase>` of the current `<Representation>`,
+    /// in the same way than `effective_segment_template`, with its
+    /// `<Initialization>` from the closest element having one.
+    pub fn effective_segment_base(
+        &self,
+    ) -> Option<(BytesStart<'static>, Option<BytesStart<'static>>)> {
+        let attributes = merge_levels(&self.segment_base_attributes[..self.level])?;
+        let initialization = self.initialization_attributes[..self.level]
+            .iter()
+            .rev()
+            .find_map(|attributes| attributes.as_ref())
+            .map(|attributes| to_element("Initialization", attributes));
+        Some((to_element("SegmentBase", &attributes), initialization))
+    }
+
+    /// Returns the resolved URLs of the `<BaseURL>` elements applying to the
+    /// current `<Representation>`: those of the closest element having some.
+    pub fn effective_base_urls(&self) -> &[String] {
+        self.base_urls[..self.level]
+            .iter()
+            .rev()
+            .find(|urls| !urls.is_empty())
+            .unwrap_or(&self.mpd_base_urls)
+    }
+
+    /// Returns the `<ContentProtection>` elements applying to the current
+    /// `<Representation>`: those of its `<AdaptationSet>` then its own.
+    pub fn effective_content_protections(
+        &self,
+    ) -> impl Iterator<Item = &ContentProtectionDescriptor> {
+        self.content_protections[..self.level].iter().flatten()
+    }
+
+    /// Signal that a `<BaseURL>` element with the given text has been found in
+    /// the deepest element currently open.
+    ///
+    /// Returns the URLs obtained by resolving that text against each resolved
+    /// `<BaseURL>` of the closest parent element having some, or against
+    /// `mpd_url` if there's none.
+    /// Those are then used to resolve the `<BaseURL>` elements of children
+    /// elements.
+    pub fn add_base_url(&mut self, text: &str, mpd_url: Option<&str>) -> Vec<String> {
+        let parent_urls: &[String] = if self.level == 0 {
+            &[]
+        } else {
+            self.base_urls[..self.level - 1]
+                .iter()
+                .rev()
+                .find(|urls| !urls.is_empty())
+                .unwrap_or(&self.mpd_base_urls)
+        };
+        let resolved: Vec<String> = if parent_urls.is_empty() {
+            match mpd_url {
+                Some(mpd_url) => vec![url::resolve(mpd_url, text)],
+                None => vec![text.to_owned()],
+            }
+        } else {
+            parent_urls
+                .iter()
+                .map(|parent| url::resolve(parent, text))
+                .collect()
+        };
+        let current_urls = if self.level == 0 {
+            &mut self.mpd_base_urls
+        } else {
+            &mut self.base_urls[self.level - 1]
+        };
+        current_urls.extend_from_slice(&resolved);
+        resolved
+    }
+
+    /// Returns the information on the current `<Representation>` needed to
+    /// compute the list of its segments.
+    pub fn representation_context(&self) -> RepresentationContext<'_> {
+        RepresentationContext {
+            id: &self.ids[REPRESENTATION],
+            bandwidth: self.bandwidth,
+            period_duration: self.period_duration,
+        }
+    }
+}
+
+/// Returns the unescaped value of the given attribute of an element, or `None`
+/// if it has no such attribute.
+fn get_attribute(tag: &quick_xml::events::BytesStart, key: &[u8]) -> Option<String> {
+    match tag.try_get_attribute(key) {
+        Ok(Some(attr)) => match attr.unescape_value() {
+            Ok(val) => Some(val.into_owned()),
+            Err(err) => {
+                ParsingError::from(err).report_err();
+                None
+            }
+        },
+        Ok(None) => None,
+        Err(err) => {
+            ParsingError::from(err).report_err();
+            None
+        }
+    }
+}
+
+/// Returns the attributes of an element, for them to be combined with those
+/// of other elements.
+///
+/// Their names are made canonical, so attributes are matched whatever the
+/// prefix used for their namespace in the MPD.
+fn raw_attributes<R>(tag: &BytesStart, reader: &NsReader<R>) -> RawAttributes {
+    tag.attributes()
+        .flatten()
+        .map(|attr| {
+            let name = canonical_attr_name(reader, attr.key).into_owned();
+            (name, attr.value.into_owned())
+        })
+        .collect()
+}
+
+/// Combines the attributes of an element with those of the same element in
+/// a parent, the ones defined on the element itself taking precedence.
+fn merge_attributes(own: &RawAttributes, parent: &RawAttributes) -> RawAttributes {
+    let mut merged = own.clone();
+    for (name, value) in parent {
+        if !own.iter().any(|(own_name, _)| own_name == name) {
+            merged.push((name.clone(), value.clone()));
+        }
+    }
+    merged
+}
+
+/// Combines the attributes of the same element found at each depth, the
+/// deepest ones taking precedence. Returns `None` if that element was found
+/// at no depth.
+fn merge_levels(levels: &[Option<RawAttributes>]) -> Option<RawAttributes> {
+    levels
+        .iter()
+        .flatten()
+        .fold(None, |parent, attributes| match parent {
+            Some(parent) => Some(merge_attributes(attributes, &parent)),
+            None => Some(attributes.clone()),
+        })
+}
+
+/// Constructs an element with the given name and attributes, so it can be
+/// reported like the ones found in the MPD.
+fn to_element(name: &'static str, attributes: &RawAttributes) -> BytesStart<'static> {
+    let mut element = BytesStart::new(name);
+    for (key, value) in attributes {
+        element.push_attribute(Attribute {
+            key: QName(key),
+            value: Cow::Borrowed(value),
+        });
+    }
+    element
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+
+    #[test]
+    fn test_raw_attributes() {
+        let xml = r#"<AdaptationSet xmlns:s="urn:scte:dash:scte214-extensions"
+                        s:supplementalCodecs="dvh1.08.07" codecs="hvc1.2.4.L153"/>"#;
+        let mut reader = NsReader::from_str(xml);
+        let tag = match reader.read_resolved_event().unwrap() {
+            (_, Event::Empty(tag)) => tag.into_owned(),
+            _ => unreachable!(),
+        };
+        let names: Vec<Vec<u8>> = raw_attributes(&tag, &reader)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                b"xmlns:s".to_vec(),
+                b"scte214:supplementalCodecs".to_vec(),
+                b"codecs".to_vec()
+            ]
+        );
+        assert!(names
+            .iter()
+            .filter(|name| INHERITED_ATTRIBUTES.contains(&name.as_slice()))
+            .eq([&names[1], &names[2]]));
+    }
+
+    #[test]
+    fn test_merge_levels() {
+        let attrs = |list: &[(&str, &str)]| -> RawAttributes {
+            list.iter()
+                .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
+                .collect()
+        };
+        let period = attrs(&[("timescale", "1000"), ("media", "a.mp4")]);
+        let representation = attrs(&[("media", "b.mp4"), ("startNumber", "3")]);
+        let merged = merge_levels(&[Some(period), None, Some(representation)]).unwrap();
+        assert_eq!(
+            merged,
+            attrs(&[
+                ("media", "b.mp4"),
+                ("startNumber", "3"),
+                ("timescale", "1000")
+            ])
+        );
+        assert_eq!(merge_levels(&[None, None]), None);
+
+        let element = to_element("SegmentTemplate", &merged);
+        assert_eq!(element.name().as_ref(), b"SegmentTemplate");
+        assert_eq!(element.attributes().count(), 3);
+    }
+
+    #[test]
+    fn test_identifiers() {
+        // Not relying on `open`, which may report errors
+        let mut hierarchy = Hierarchy::default();
+        hierarchy.track_position(PERIOD);
+        hierarchy.ids[PERIOD] = "p1".to_owned();
+        hierarchy.track_position(ADAPTATION_SET);
+        hierarchy.track_position(ADAPTATION_SET);
+        assert_eq!(hierarchy.identifiers(), ["p1", "#1", ""]);
+        hierarchy.track_position(REPRESENTATION);
+        assert_eq!(hierarchy.identifiers(), ["p1", "#1", "#0"]);
+
+        // Positions start again from 0 in a new parent
+        hierarchy.track_position(PERIOD);
+        hierarchy.ids[PERIOD].clear();
+        hierarchy.track_position(ADAPTATION_SET);
+        assert_eq!(hierarchy.identifiers(), ["#1", "#0", ""]);
+    }
+}
@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+/// The different elements of a `<ContentProtection>` which can announce the
+/// URL of a license server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LicenseUrlElement {
+    /// `dashif:Laurl`, whose text is the URL and whose optional `licenseType`
+    /// attribute is the type of license served.
+    DashIf,
+
+    /// `clearkey:Laurl`, whose text is the URL and whose optional `Lic_type`
+    /// attribute is the type of license served.
+    ClearKey,
+
+    /// `ms:laurl`, whose `licenseUrl` attribute is the URL.
+    LegacyPlayReady,
+}
+
+impl LicenseUrlElement {
+    /// Returns the name of the attribute indicating the type of license
+    /// served, if that element has one.
+    fn license_type_attribute(self) -> Option<&'static [u8]> {
+        match self {
+            LicenseUrlElement::DashIf => Some(b"licenseType"),
+            LicenseUrlElement::ClearKey => Some(b"Lic_type"),
+            LicenseUrlElement::LegacyPlayReady => None,
+        }
+    }
+
+    /// Returns the URL announced in the attributes of that element, if it is
+    /// one which announces it there.
+    pub fn url_attribute(self, tag: &quick_xml::events::BytesStart) -> Option<String> {
+        match self {
+            LicenseUrlElement::LegacyPlayReady => attribute_value(tag, b"licenseUrl"),
+            LicenseUrlElement::DashIf | LicenseUrlElement::ClearKey => None,
+        }
+    }
+
+    /// Returns the type of license served as announced in the attributes of
+    /// that element, if any.
+    pub fn license_type(self, tag: &quick_xml::events::BytesStart) -> Option<String> {
+        attribute_value(tag, self.license_type_attribute()?)
+    }
+}
+
+/// Returns the unescaped value of the attribute named `name`, ignoring invalid
+/// ones as they are already signaled when reported.
+fn attribute_value(tag: &quick_xml::events::BytesStart, name: &[u8]) -> Option<String> {
+    let attr = tag.try_get_attribute(name).ok()??;
+    attr.unescape_value().ok().map(Cow::into_owned)
+}
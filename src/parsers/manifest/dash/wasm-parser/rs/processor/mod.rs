@@ -1,20 +1,93 @@
 use quick_xml::events::Event;
-use quick_xml::Reader;
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+use std::borrow::Cow;
 use std::io::BufReader;
 
+mod adaptation_type;
 mod attributes;
+mod content_protection;
+mod deferred_segments;
+mod dynamic_range;
+mod essential_property;
+mod hierarchy;
+mod license_url;
+mod namespaces;
+mod period_times;
 mod s_element;
+mod timeline;
+mod trick_mode;
 
+use crate::codecs::{self, Codec};
 use crate::errors::ParsingError;
 use crate::events::*;
+use crate::hdr::VideoDescriptors;
+use crate::options::{self, ParserOptions};
+use crate::periods::ResolvedPeriod;
+use crate::playready;
+use crate::pssh::PsshBox;
 use crate::reader::MPDReader;
+use crate::segment_index::{Segment, SegmentTemplate};
+use crate::thumbnails::{Thumbnail, TileGrid};
+use crate::track_type::{Role, TrackType};
+use crate::url;
+use crate::utils;
+use adaptation_type::AdaptationTypeTracker;
+use content_protection::{ContentProtectionDescriptor, ContentProtectionRefs};
+use deferred_segments::DeferredSegments;
+use dynamic_range::DynamicRangeTracker;
+use essential_property::{
+    EssentialPropertyEvaluator, SkipProgress, SkippedElement, UnsupportedElement,
+};
+use hierarchy::Hierarchy;
+use license_url::LicenseUrlElement;
+use period_times::PeriodTimesTracker;
+use trick_mode::TrickModeLinker;
 
 pub use s_element::SegmentObject;
 
 pub struct MPDProcessor {
-    reader: quick_xml::Reader<BufReader<MPDReader>>,
+    reader: NsReader<BufReader<MPDReader>>,
     reader_buf: Vec<u8>,
     segment_objs_buf: Vec<SegmentObject>,
+    options: ParserOptions,
+
+    /// Information on the `<Period>`, `<AdaptationSet>` and `<Representation>`
+    /// elements currently open.
+    ///
+    /// Only tracked when previous timelines have been communicated or when
+    /// segments have to be reported, as it is only needed in those cases.
+    hierarchy: Hierarchy,
+
+    /// The `<ContentProtection>` element currently open, if one.
+    content_protection: Option<ContentProtectionDescriptor>,
+
+    /// `<ContentProtection>` elements of the current `<Period>` which may be
+    /// referenced by others.
+    content_protection_refs: ContentProtectionRefs,
+
+    /// Information needed to classify the dynamic range and to pair the
+    /// codec layers of the current `<Representation>`.
+    dynamic_range: DynamicRangeTracker,
+
+    /// Information needed to infer the type and to normalize the roles of
+    /// the current `<AdaptationSet>`.
+    adaptation_type: AdaptationTypeTracker,
+
+    /// Trick mode `<AdaptationSet>` elements of the current `<Period>` and
+    /// the ones they are associated to.
+    trick_mode: TrickModeLinker,
+
+    /// `<EssentialProperty>` elements of the current `<AdaptationSet>` and
+    /// `<Representation>` which are not supported by the host.
+    essential_properties: EssentialPropertyEvaluator,
+
+    /// Time attributes of the `<MPD>` and of its `<Period>` elements.
+    period_times: PeriodTimesTracker,
+
+    /// `<Representation>` elements whose segments can only be reported once
+    /// the end of their `<Period>` is known.
+    deferred_segments: Vec<DeferredSegments>,
 }
 
 impl MPDProcessor {
@@ -23,8 +96,10 @@ impl MPDProcessor {
     /// # Arguments
     ///
     /// * `reader` - A BufReader allowing to read the MPD document
-    pub fn new(reader: BufReader<MPDReader>) -> Self {
-        let mut reader = Reader::from_reader(reader);
+    ///
+    /// * `options` - Optional processing steps to apply while parsing
+    pub fn new(reader: BufReader<MPDReader>, options: ParserOptions) -> Self {
+        let mut reader = NsReader::from_reader(reader);
         reader.expand_empty_elements(true);
         reader.trim_text(true);
         reader.check_end_names(false);
@@ -32,122 +107,288 @@ impl MPDProcessor {
             reader,
             reader_buf: Vec::new(),
             segment_objs_buf: Vec::new(),
+            options,
+            hierarchy: Hierarchy::default(),
+            content_protection: None,
+            content_protection_refs: ContentProtectionRefs::default(),
+            dynamic_range: DynamicRangeTracker::default(),
+            adaptation_type: AdaptationTypeTracker::default(),
+            trick_mode: TrickModeLinker::default(),
+            essential_properties: EssentialPropertyEvaluator::default(),
+            period_times: PeriodTimesTracker::default(),
+            deferred_segments: Vec::new(),
         }
     }
 
     pub fn process_tags(&mut self) {
+        let track_hierarchy = !self.options.previous_timelines.is_empty()
+            || self.options.segment_range.is_some()
+            || self.options.resolve_base_urls
+            || self.options.resolve_inheritance;
+
+        // Element whose remaining content should be skipped once the current
+        // `<EssentialProperty>` is closed, as it is not supported.
+        let mut pending_skip: Option<UnsupportedElement> = None;
         loop {
-            match self.read_next_event() {
-                Ok(Event::Start(tag)) => match tag.name().as_ref() {
-                    b"MPD" => {
-                        TagName::MPD.report_tag_open();
-                        attributes::report_mpd_attrs(&tag);
-                    }
-                    b"Period" => {
-                        TagName::Period.report_tag_open();
-                        attributes::report_period_attrs(&tag);
-                    }
-                    b"AdaptationSet" => {
-                        TagName::AdaptationSet.report_tag_open();
-                        attributes::report_adaptation_set_attrs(&tag);
-                    }
-                    b"Representation" => {
-                        TagName::Representation.report_tag_open();
-                        attributes::report_representation_attrs(&tag);
-                    }
-                    b"Accessibility" => {
-                        TagName::Accessibility.report_tag_open();
-                        attributes::report_scheme_attrs(&tag);
-                    }
-                    b"ContentComponent" => {
-                        TagName::ContentComponent.report_tag_open();
-                        attributes::report_content_component_attrs(&tag);
-                    }
-                    b"ContentProtection" => {
-                        TagName::ContentProtection.report_tag_open();
-                        attributes::report_content_protection_attrs(&tag);
-                    }
-                    b"EssentialProperty" => {
-                        TagName::EssentialProperty.report_tag_open();
-                        attributes::report_scheme_attrs(&tag);
-                    }
-                    b"InbandEventStream" => {
-                        TagName::InbandEventStream.report_tag_open();
-                        attributes::report_scheme_attrs(&tag);
-                    }
-                    b"Role" => {
-                        TagName::Role.report_tag_open();
-                        attributes::report_scheme_attrs(&tag);
-                    }
-                    b"SupplementalProperty" => {
-                        TagName::SupplementalProperty.report_tag_open();
-                        attributes::report_scheme_attrs(&tag);
-                    }
-                    b"SegmentBase" => {
-                        TagName::SegmentBase.report_tag_open();
-                        attributes::report_segment_base_attrs(&tag);
-                    }
-                    b"Initialization" => attributes::report_initialization_attrs(&tag),
-                    b"SegmentTemplate" => {
-                        TagName::SegmentTemplate.report_tag_open();
-                        attributes::report_segment_template_attrs(&tag);
-                    }
-                    b"SegmentList" => {
-                        TagName::SegmentList.report_tag_open();
+            // Not relying on `read_next_event` here, so the read element only
+            // borrows `reader_buf`, letting us update the `hierarchy` with it.
+            //
+            // Elements are identified by their namespace and local name, so
+            // the prefixes used in the MPD do not matter.
+            self.reader_buf.clear();
+            match self.reader.read_resolved_event_into(&mut self.reader_buf) {
+                Ok((ns, Event::Start(tag))) => {
+                    match namespaces::canonical_name(ns, tag.name(), tag.local_name()).as_ref() {
+                        b"MPD" => {
+                            TagName::MPD.report_tag_open();
+                            let position = self.reader.buffer_position();
+                            attributes::report_mpd_attrs(&tag, position);
+                            self.dynamic_range.open_mpd(&tag, &self.reader);
+                            self.period_times.open_mpd(&tag);
+                        }
+                        b"Period" => {
+                            // References of the descriptors defined directly in
+                            // the MPD can no longer be resolved
+                            for err in self.content_protection_refs.close_period() {
+                                err.report_err();
+                            }
+                            TagName::Period.report_tag_open();
+                            attributes::report_period_attrs(&tag, &self.reader);
+                            self.adaptation_type.open_period();
+                            self.trick_mode.open_period();
+                            self.period_times.open_period(&tag);
+                            if track_hierarchy {
+                                self.hierarchy.open(hierarchy::PERIOD, &tag, &self.reader);
+                            }
+                        }
+                        b"AdaptationSet" => {
+                            TagName::AdaptationSet.report_tag_open();
+                            attributes::report_adaptation_set_attrs(&tag, &self.reader);
+                            self.dynamic_range.open_adaptation_set(&tag, &self.reader);
+                            self.adaptation_type.open_adaptation_set(&tag, &self.reader);
+                            self.trick_mode.open_adaptation_set(&tag);
+                            self.essential_properties.open_adaptation_set(&tag);
+                            if track_hierarchy {
+                                self.hierarchy
+                                    .open(hierarchy::ADAPTATION_SET, &tag, &self.reader);
+                            }
+                        }
+                        b"Representation" => {
+                            TagName::Representation.report_tag_open();
+                            attributes::report_representation_attrs(&tag, &self.reader);
+                            self.dynamic_range.open_representation(&tag, &self.reader);
+                            self.adaptation_type.open_representation(&tag, &self.reader);
+                            self.trick_mode.set_in_representation(true);
+                            self.essential_properties.open_representation(&tag);
+                            if track_hierarchy {
+                                self.hierarchy
+                                    .open(hierarchy::REPRESENTATION, &tag, &self.reader);
+                            }
+                        }
+                        b"Accessibility" => {
+                            TagName::Accessibility.report_tag_open();
+                            attributes::report_accessibility_attrs(&tag);
+                        }
+                        b"ContentComponent" => {
+                            TagName::ContentComponent.report_tag_open();
+                            attributes::report_content_component_attrs(&tag);
+                        }
+                        b"ContentProtection" => {
+                            TagName::ContentProtection.report_tag_open();
+                            let position = self.reader.buffer_position();
+                            attributes::report_content_protection_attrs(
+                                &tag,
+                                &self.reader,
+                                position,
+                            );
+                            self.content_protection =
+                                Some(ContentProtectionDescriptor::from_element(
+                                    &tag,
+                                    &self.reader,
+                                    position,
+                                ));
+                        }
+                        b"EssentialProperty" => {
+                            TagName::EssentialProperty.report_tag_open();
+                            let grid = attributes::report_essential_property_attrs(&tag);
+                            self.dynamic_range.add_property(&tag);
+                            self.trick_mode.add_property(&tag);
+                            if let Some(supported) = &self.options.supported_essential_properties {
+                                if let Some(unsupported) =
+                                    self.essential_properties.add_property(&tag, supported)
+                                {
+                                    unsupported.error.report_err();
+                                    if self.options.skip_unsupported_sets
+                                        && unsupported.newly_unsupported
+                                    {
+                                        pending_skip = pending_skip.or(Some(unsupported.element));
+                                    }
+                                }
+                            }
+                            if let (Some(grid), true) = (grid, track_hierarchy) {
+                                self.hierarchy.set_tile_grid(grid);
+                            }
+                        }
+                        b"InbandEventStream" => {
+                            TagName::InbandEventStream.report_tag_open();
+                            attributes::report_scheme_attrs(&tag);
+                        }
+                        b"Role" => {
+                            TagName::Role.report_tag_open();
+                            attributes::report_scheme_attrs(&tag);
+                            self.adaptation_type.add_role(&tag);
+                        }
+                        b"SupplementalProperty" => {
+                            TagName::SupplementalProperty.report_tag_open();
+                            attributes::report_scheme_attrs(&tag);
+                            self.dynamic_range.add_property(&tag);
+                        }
+                        b"SegmentBase" => {
+                            TagName::SegmentBase.report_tag_open();
+                            attributes::report_segment_base_attrs(&tag);
+                            if track_hierarchy {
+                                self.hierarchy.open_segment_base(&tag, &self.reader);
+                            }
+                        }
+                        b"Initialization" => {
+                            attributes::report_initialization_attrs(&tag);
+                            if track_hierarchy {
+                                self.hierarchy.set_initialization(&tag, &self.reader);
+                            }
+                        }
+                        b"SegmentTemplate" => {
+                            TagName::SegmentTemplate.report_tag_open();
+                            let urls = attributes::report_segment_template_attrs(&tag);
+                            if track_hierarchy {
+                                match SegmentTemplate::from_element(&tag, urls) {
+                                    Ok(template) => self.hierarchy.open_segment_template(
+                                        template,
+                                        &tag,
+                                        &self.reader,
+                                    ),
+                                    Err(err) => err.report_err(),
+                                }
+                            }
+                        }
+                        b"SegmentList" => {
+                            TagName::SegmentList.report_tag_open();
 
-                        // Re-use SegmentBase-one as it should not be different
-                        attributes::report_segment_base_attrs(&tag);
-                    }
-                    b"SegmentURL" => {
-                        TagName::SegmentUrl.report_tag_open();
-                        attributes::report_segment_url_attrs(&tag);
-                    }
-                    b"UTCTiming" => {
-                        TagName::UtcTiming.report_tag_open();
-                        attributes::report_scheme_attrs(&tag);
-                    }
+                            // Re-use SegmentBase-one as it should not be different
+                            attributes::report_segment_base_attrs(&tag);
+                        }
+                        b"SegmentURL" => {
+                            TagName::SegmentUrl.report_tag_open();
+                            attributes::report_segment_url_attrs(&tag);
+                        }
+                        b"UTCTiming" => {
+                            TagName::UtcTiming.report_tag_open();
+                            attributes::report_scheme_attrs(&tag);
+                        }
 
-                    b"BaseURL" => {
-                        TagName::BaseURL.report_tag_open();
-                        attributes::report_base_url_attrs(&tag);
-                        self.process_base_url_element();
-                    }
-                    b"cenc:pssh" => self.process_cenc_element(),
-                    b"Location" => self.process_location_element(),
-                    b"Label" => {
-                        TagName::Label.report_tag_open();
-                        self.process_label_element();
-                    }
-                    b"SegmentTimeline" => self.process_segment_timeline_element(),
+                        b"BaseURL" => {
+                            TagName::BaseURL.report_tag_open();
+                            attributes::report_base_url_attrs(&tag);
+                            self.process_base_url_element();
+                        }
+                        b"cenc:pssh" => {
+                            let name = tag.name().as_ref().to_vec();
+                            self.process_cenc_element(&name);
+                        }
+                        b"mspr:pro" => {
+                            let name = tag.name().as_ref().to_vec();
+                            self.process_mspr_pro_element(&name);
+                        }
+                        b"dashif:Laurl" => {
+                            let elt = LicenseUrlElement::DashIf;
+                            attributes::report_license_url_attrs(&tag, elt);
+                            let license_type = elt.license_type(&tag);
+                            let name = tag.name().as_ref().to_vec();
+                            self.process_license_url_element(&name, license_type);
+                        }
+                        b"clearkey:Laurl" => {
+                            let elt = LicenseUrlElement::ClearKey;
+                            attributes::report_license_url_attrs(&tag, elt);
+                            let license_type = elt.license_type(&tag);
+                            let name = tag.name().as_ref().to_vec();
+                            self.process_license_url_element(&name, license_type);
+                        }
+                        // Found either in the PlayReady namespace or in the
+                        // `urn:microsoft` one, conventionally prefixed by `ms`
+                        b"mspr:laurl" | b"ms:laurl" => {
+                            let elt = LicenseUrlElement::LegacyPlayReady;
+                            attributes::report_license_url_attrs(&tag, elt);
+                            if let (Some(url), Some(cp)) =
+                                (elt.url_attribute(&tag), self.content_protection.as_mut())
+                            {
+                                cp.license_urls.push((url, None));
+                            }
+                        }
+                        b"Location" => self.process_location_element(),
+                        b"Label" => {
+                            TagName::Label.report_tag_open();
+                            self.process_label_element();
+                        }
+                        b"SegmentTimeline" => self.process_segment_timeline_element(),
 
-                    b"EventStream" => {
-                        TagName::EventStream.report_tag_open();
-                        attributes::report_event_stream_attrs(&tag);
-                        self.process_event_stream_element();
-                    }
+                        b"EventStream" => {
+                            TagName::EventStream.report_tag_open();
+                            attributes::report_event_stream_attrs(&tag);
+                            self.process_event_stream_element();
+                        }
 
-                    _ => {}
-                },
-                Ok(Event::End(tag)) => match tag.name().as_ref() {
-                    b"MPD" => TagName::MPD.report_tag_close(),
-                    b"Period" => TagName::Period.report_tag_close(),
-                    b"AdaptationSet" => TagName::AdaptationSet.report_tag_close(),
-                    b"Representation" => TagName::Representation.report_tag_close(),
-                    b"Accessibility" => TagName::Accessibility.report_tag_close(),
-                    b"ContentComponent" => TagName::ContentComponent.report_tag_close(),
-                    b"ContentProtection" => TagName::ContentProtection.report_tag_close(),
-                    b"EssentialProperty" => TagName::EssentialProperty.report_tag_close(),
-                    b"InbandEventStream" => TagName::InbandEventStream.report_tag_close(),
-                    b"Role" => TagName::Role.report_tag_close(),
-                    b"SupplementalProperty" => TagName::SupplementalProperty.report_tag_close(),
-                    b"SegmentBase" => TagName::SegmentBase.report_tag_close(),
-                    b"SegmentList" => TagName::SegmentList.report_tag_close(),
-                    b"SegmentURL" => TagName::SegmentUrl.report_tag_close(),
-                    b"SegmentTemplate" => TagName::SegmentTemplate.report_tag_close(),
-                    b"UTCTiming" => TagName::UtcTiming.report_tag_close(),
-                    _ => {}
-                },
-                Ok(Event::Eof) => {
+                        _ => {}
+                    }
+                }
+                Ok((ns, Event::End(tag))) => {
+                    match namespaces::canonical_name(ns, tag.name(), tag.local_name()).as_ref() {
+                        b"MPD" => {
+                            for err in self.content_protection_refs.close_period() {
+                                err.report_err();
+                            }
+                            let resolved = self.report_period_times();
+                            self.report_deferred_segments(resolved.as_deref());
+                            TagName::MPD.report_tag_close();
+                        }
+                        b"Period" => {
+                            self.report_trick_mode_links();
+                            for err in self.content_protection_refs.close_period() {
+                                err.report_err();
+                            }
+                            TagName::Period.report_tag_close();
+                            self.hierarchy.close(hierarchy::PERIOD);
+                        }
+                        b"AdaptationSet" => self.close_adaptation_set(),
+                        b"Representation" => self.close_representation(track_hierarchy),
+                        b"Accessibility" => TagName::Accessibility.report_tag_close(),
+                        b"ContentComponent" => TagName::ContentComponent.report_tag_close(),
+                        b"ContentProtection" => {
+                            self.resolve_content_protection();
+                            TagName::ContentProtection.report_tag_close();
+                        }
+                        b"EssentialProperty" => {
+                            TagName::EssentialProperty.report_tag_close();
+                            if let Some(element) = pending_skip.take() {
+                                self.skip_unsupported_element(element, track_hierarchy);
+                            }
+                        }
+                        b"InbandEventStream" => TagName::InbandEventStream.report_tag_close(),
+                        b"Role" => TagName::Role.report_tag_close(),
+                        b"SupplementalProperty" => TagName::SupplementalProperty.report_tag_close(),
+                        b"SegmentBase" => {
+                            TagName::SegmentBase.report_tag_close();
+                            self.hierarchy.close_segment_base();
+                        }
+                        b"SegmentList" => TagName::SegmentList.report_tag_close(),
+                        b"SegmentURL" => TagName::SegmentUrl.report_tag_close(),
+                        b"SegmentTemplate" => {
+                            TagName::SegmentTemplate.report_tag_close();
+                            self.hierarchy.close_segment_template();
+                        }
+                        b"UTCTiming" => TagName::UtcTiming.report_tag_close(),
+                        _ => {}
+                    }
+                }
+                Ok((_, Event::Eof)) => {
                     break;
                 }
                 Err(e) => ParsingError::from(e).report_err(),
@@ -156,24 +397,111 @@ impl MPDProcessor {
         }
     }
 
+    /// Report the end of the current `<AdaptationSet>`, preceded by what was
+    /// deduced from its content.
+    fn close_adaptation_set(&mut self) {
+        let unsupported = self.essential_properties.close_adaptation_set();
+        if !unsupported.is_empty() {
+            AttributeName::UnsupportedEssentialProperties.report(unsupported.as_slice());
+        }
+        if let Some((track_type, roles)) = self.adaptation_type.close_adaptation_set() {
+            report_track_type(track_type, &roles);
+        }
+        TagName::AdaptationSet.report_tag_close();
+        self.dynamic_range.close_adaptation_set();
+        self.trick_mode.close_adaptation_set();
+        self.hierarchy.close(hierarchy::ADAPTATION_SET);
+    }
+
+    /// Report the end of the current `<Representation>`, preceded by what was
+    /// deduced from its content.
+    fn close_representation(&mut self, track_hierarchy: bool) {
+        let unsupported = self.essential_properties.close_representation();
+        if !unsupported.is_empty() {
+            AttributeName::UnsupportedEssentialProperties.report(unsupported.as_slice());
+        }
+        if track_hierarchy {
+            self.report_segments();
+        }
+        if let Some(descriptors) = self.dynamic_range.close_representation() {
+            report_video_descriptors(&descriptors);
+        }
+        if self.options.resolve_inheritance {
+            self.report_effective_attributes();
+        }
+        TagName::Representation.report_tag_close();
+        self.trick_mode.set_in_representation(false);
+        self.hierarchy.close(hierarchy::REPRESENTATION);
+    }
+
+    /// Skip the remaining content of an `<AdaptationSet>` or
+    /// `<Representation>` having an unsupported `<EssentialProperty>`, then
+    /// report its end.
+    fn skip_unsupported_element(&mut self, element: UnsupportedElement, track_hierarchy: bool) {
+        let raw_name = match self.essential_properties.raw_name(element) {
+            Some(raw_name) => raw_name.to_vec(),
+            None => return,
+        };
+
+        let mut skipped = SkippedElement::new(&raw_name);
+        loop {
+            match self.read_next_event() {
+                Ok(event) => match skipped.process(&event) {
+                    SkipProgress::Pending => {}
+                    SkipProgress::Ended => break,
+                    SkipProgress::UnexpectedEof => {
+                        ParsingError("Unexpected end of file in a skipped element.".to_owned())
+                            .report_err();
+                        return;
+                    }
+                },
+                Err(e) => {
+                    ParsingError::from(e).report_err();
+                    return;
+                }
+            }
+        }
+        match element {
+            UnsupportedElement::AdaptationSet => self.close_adaptation_set(),
+            UnsupportedElement::Representation => self.close_representation(track_hierarchy),
+        }
+    }
+
     /// Read the MPD document until an "Event" (@see quick-xml documentation)
     /// is encountered.
     ///
     /// This method is always inlined for optimization reasons as it is both
     /// short and generally used in loops.
     #[inline(always)]
-    fn read_next_event(&mut self) -> quick_xml::Result<quick_xml::events::Event> {
+    fn read_next_event(&mut self) -> quick_xml::Result<quick_xml::events::Event<'_>> {
         if !self.reader_buf.is_empty() {
             self.reader_buf.clear();
         }
         self.reader.read_event_into(&mut self.reader_buf)
     }
 
+    /// Same than `read_next_event`, but also returns the namespace of the
+    /// element concerned by that "Event", if any, so it can be recognized
+    /// whatever the prefix used for it (@see `namespaces::is_named`).
+    #[inline(always)]
+    fn read_next_resolved_event(
+        &mut self,
+    ) -> quick_xml::Result<(ResolveResult<'_>, quick_xml::events::Event<'_>)> {
+        if !self.reader_buf.is_empty() {
+            self.reader_buf.clear();
+        }
+        self.reader.read_resolved_event_into(&mut self.reader_buf)
+    }
+
     /// Loop over a SegmentTimeline's children (to call when a <SegmentTimeline>
     /// node just has been found).
     ///
     /// Report its children tag and attributes until either its corresponding
     /// closing SegmentTemplate tag has been found or until EOF is encountered.
+    ///
+    /// If the `normalize_segment_timeline` option is enabled, contiguous `<S>`
+    /// elements with the same duration are folded together and gaps or
+    /// overlaps between them are reported.
     fn process_segment_timeline_element(&mut self) {
         // Count inner SegmentTimeline tags if it exists.
         // Allowing to not close the current node when it is an inner that is closed
@@ -187,8 +515,13 @@ impl MPDProcessor {
         let mut curr_time_base: f64 = 0.;
 
         loop {
-            match self.read_next_event() {
-                Ok(Event::Start(tag)) | Ok(Event::Empty(tag)) if tag.name().as_ref() == b"S" => {
+            // Position from which the next element is read, to designate the
+            // `<S>` element concerned by a diagnostic.
+            let element_position = self.reader.buffer_position();
+            match self.read_next_resolved_event() {
+                Ok((ns, Event::Start(tag))) | Ok((ns, Event::Empty(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"S") =>
+                {
                     match SegmentObject::from_s_element(&tag, curr_time_base) {
                         Ok(segment_obj) => {
                             if segment_obj.repeat_count == 0. {
@@ -198,23 +531,49 @@ impl MPDProcessor {
                                     segment_obj.duration * (segment_obj.repeat_count + 1.);
                                 curr_time_base = segment_obj.start + duration;
                             }
-                            self.segment_objs_buf.push(segment_obj);
+                            if self.options.normalize_segment_timeline {
+                                let discontinuity = timeline::push_normalized(
+                                    &mut self.segment_objs_buf,
+                                    segment_obj,
+                                );
+                                if let Some(discontinuity) = discontinuity {
+                                    discontinuity
+                                        .to_parsing_error(element_position)
+                                        .report_err();
+                                }
+                            } else {
+                                self.segment_objs_buf.push(segment_obj);
+                            }
                         }
                         Err(err) => err.report_err(),
                     }
                 }
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"SegmentTimeline" => {
+                Ok((ns, Event::Start(tag)))
+                    if namespaces::is_named(
+                        &ns,
+                        tag.name(),
+                        tag.local_name(),
+                        b"SegmentTimeline",
+                    ) =>
+                {
                     inner_tag += 1
                 }
-                Ok(Event::End(tag)) if tag.name().as_ref() == b"SegmentTimeline" => {
+                Ok((ns, Event::End(tag)))
+                    if namespaces::is_named(
+                        &ns,
+                        tag.name(),
+                        tag.local_name(),
+                        b"SegmentTimeline",
+                    ) =>
+                {
                     if inner_tag > 0 {
                         inner_tag -= 1;
                     } else {
-                        AttributeName::SegmentTimeline.report(self.segment_objs_buf.as_slice());
+                        self.report_segment_timeline();
                         break;
                     }
                 }
-                Ok(Event::Eof) => {
+                Ok((_, Event::Eof)) => {
                     ParsingError("Unexpected end of file in a SegmentTimeline.".to_owned())
                         .report_err();
                     break;
@@ -229,30 +588,257 @@ impl MPDProcessor {
         self.segment_objs_buf.clear();
     }
 
+    /// Report the `<SegmentTimeline>` which has just been parsed into
+    /// `segment_objs_buf`.
+    ///
+    /// If a previous timeline has been communicated for it, only the entries
+    /// which changed since are reported, through the `SegmentTimelineUpdate`
+    /// attribute.
+    fn report_segment_timeline(&mut self) {
+        if self.options.segment_range.is_some() || self.options.resolve_inheritance {
+            self.hierarchy.set_segment_timeline(&self.segment_objs_buf);
+        }
+        if !self.options.previous_timelines.is_empty() {
+            let [period, adaptation_set, representation] = self.hierarchy.identifiers();
+            let key = options::timeline_key(&period, &adaptation_set, &representation);
+            if let Some(prev_timeline) = self.options.previous_timelines.get(&key) {
+                match timeline::merge_with_previous(prev_timeline, &self.segment_objs_buf) {
+                    Some(merge) => {
+                        AttributeName::SegmentTimelineFirstCommonStart
+                            .report(merge.first_common_start);
+                        AttributeName::SegmentTimelineUpdate.report(merge.updated.as_slice());
+                        return;
+                    }
+                    // It has entries the previous timeline did not have at
+                    // its start, which are reported with the whole timeline
+                    None if timeline::starts_before(&self.segment_objs_buf, prev_timeline) => {}
+                    None => ParsingError(format!(
+                        "Cannot perform incremental update of the SegmentTimeline \"{}\": \
+                         no compatible common segment with the previous timeline",
+                        key
+                    ))
+                    .report_err(),
+                }
+            }
+        }
+        AttributeName::SegmentTimeline.report(self.segment_objs_buf.as_slice());
+    }
+
+    /// Report the effective attributes of the current `<Representation>`,
+    /// once the ones it inherits from its parents are taken into account, as
+    /// children of an `EffectiveAttributes` tag.
+    ///
+    /// Those are reported in the same way than the ones found in the MPD, as
+    /// if the `<Representation>` and the children it inherits were all
+    /// defined in that tag.
+    fn report_effective_attributes(&self) {
+        TagName::EffectiveAttributes.report_tag_open();
+        attributes::report_representation_attrs(
+            &self.hierarchy.effective_attributes(),
+            &self.reader,
+        );
+        for url in self.hierarchy.effective_base_urls() {
+            TagName::BaseURL.report_tag_open();
+            AttributeName::Text.report(Cow::Borrowed(url.as_str()));
+            TagName::BaseURL.report_tag_close();
+        }
+        if let Some(segment_template) = self.hierarchy.effective_segment_template() {
+            TagName::SegmentTemplate.report_tag_open();
+            attributes::report_segment_template_attrs(&segment_template);
+            let timeline = self
+                .hierarchy
+                .segment_template()
+                .and_then(|template| template.timeline);
+            if let Some(timeline) = timeline {
+                AttributeName::SegmentTimeline.report(timeline.as_slice());
+            }
+            TagName::SegmentTemplate.report_tag_close();
+        }
+        if let Some((segment_base, initialization)) = self.hierarchy.effective_segment_base() {
+            TagName::SegmentBase.report_tag_open();
+            attributes::report_segment_base_attrs(&segment_base);
+            if let Some(initialization) = initialization {
+                attributes::report_initialization_attrs(&initialization);
+            }
+            TagName::SegmentBase.report_tag_close();
+        }
+        for descriptor in self.hierarchy.effective_content_protections() {
+            TagName::ContentProtection.report_tag_open();
+            report_content_protection_fields(descriptor);
+            TagName::ContentProtection.report_tag_close();
+        }
+        TagName::EffectiveAttributes.report_tag_close();
+    }
+
+    /// Report the segments of the current `<Representation>` in the time
+    /// range set in the `segment_range` option, if one.
+    ///
+    /// Segments depending on the end of a `<Period>` which has no `duration`
+    /// attribute are only reported once the whole MPD has been read (@see
+    /// `report_deferred_segments`).
+    fn report_segments(&mut self) {
+        let (from, to) = match self.options.segment_range {
+            Some(range) => range,
+            None => return,
+        };
+        let template = match self.hierarchy.segment_template() {
+            Some(template) => template,
+            None => {
+                if self.hierarchy.tile_grid().is_some() {
+                    ParsingError(
+                        "Cannot compute thumbnails: no SegmentTemplate applies to the \
+                         Representation"
+                            .to_owned(),
+                    )
+                    .report_err();
+                }
+                return;
+            }
+        };
+        let ctx = self.hierarchy.representation_context();
+        if let Some(init_url) = template.get_init_segment_url(&ctx) {
+            AttributeName::SegmentsInitializationUrl.report(Cow::Borrowed(init_url.as_str()));
+        }
+        let thumbnails = self
+            .hierarchy
+            .tile_grid()
+            .map(|grid| (grid, self.thumbnail_size()));
+        if ctx.period_duration.is_none() && template.depends_on_period_end() {
+            self.deferred_segments.push(DeferredSegments {
+                positions: self.hierarchy.positions(),
+                id: ctx.id.to_owned(),
+                bandwidth: ctx.bandwidth,
+                template,
+                thumbnails,
+            });
+            return;
+        }
+        match template.get_segments(&ctx, from, to) {
+            Ok(segments) => {
+                AttributeName::Segments.report(segments.as_slice());
+                if let Some((grid, size)) = thumbnails {
+                    report_thumbnails(grid, size, &segments);
+                }
+            }
+            Err(err) => err.report_err(),
+        }
+    }
+
+    /// Report the segments which could not be computed when their
+    /// `<Representation>` was closed, now that the time bounds of the
+    /// `<Period>` elements are known, if they could be resolved.
+    fn report_deferred_segments(&mut self, periods: Option<&[ResolvedPeriod]>) {
+        let (from, to) = match self.options.segment_range {
+            Some(range) => range,
+            None => return,
+        };
+        for deferred in std::mem::take(&mut self.deferred_segments) {
+            TagName::DeferredSegments.report_tag_open();
+            let mut target = Vec::with_capacity(12);
+            for position in deferred.positions {
+                target.extend(utils::u32_to_u8_slice_be(position as u32));
+            }
+            AttributeName::SegmentsTarget.report(Cow::<[u8]>::Owned(target));
+            match deferred.get_segments(periods, from, to) {
+                Ok(segments) => {
+                    AttributeName::Segments.report(segments.as_slice());
+                    if let Some((grid, size)) = deferred.thumbnails {
+                        report_thumbnails(grid, size, &segments);
+                    }
+                }
+                Err(err) => err.report_err(),
+            }
+            TagName::DeferredSegments.report_tag_close();
+        }
+    }
+
+    /// Returns the width and height of the images of the current
+    /// `<Representation>`, if both are known.
+    fn thumbnail_size(&self) -> Option<(u32, u32)> {
+        let size = |key: &[u8]| {
+            self.hierarchy
+                .inherited_attribute(key)
+                .and_then(|val| val.trim().parse::<u32>().ok())
+        };
+        size(b"width").zip(size(b"height"))
+    }
+
+    /// Report the links between the trick mode `<AdaptationSet>` elements of
+    /// the current `<Period>` and the ones they are associated to, as well as
+    /// the references which could not be resolved.
+    fn report_trick_mode_links(&self) {
+        let (links, errors) = self.trick_mode.resolve();
+        for err in errors {
+            err.report_err();
+        }
+        if !links.is_empty() {
+            let mut msg = Vec::with_capacity(links.len() * 8);
+            for (trick_mode_idx, idx) in links {
+                msg.extend(utils::u32_to_u8_slice_be(trick_mode_idx as u32));
+                msg.extend(utils::u32_to_u8_slice_be(idx as u32));
+            }
+            AttributeName::TrickModeLinks.report(Cow::<[u8]>::Owned(msg));
+        }
+    }
+
+    /// Report the time bounds of the `<Period>` elements of the MPD, as well
+    /// as why they could not be resolved or how they have been flattened.
+    ///
+    /// Returns those time bounds, if they could be resolved.
+    fn report_period_times(&self) -> Option<Vec<ResolvedPeriod>> {
+        let flatten = self.options.flatten_overlapping_periods;
+        let (resolved, errors) = self.period_times.resolve(flatten);
+        for err in errors {
+            err.report_err();
+        }
+        if let Some(resolved) = &resolved {
+            AttributeName::ResolvedPeriodTimes.report(resolved.as_slice());
+        }
+        resolved
+    }
+
     fn process_location_element(&mut self) {
         // Count inner Location tags if it exists.
         // Allowing to not close the current node when it is an inner that is closed
         let mut inner_tag: u32 = 0;
 
+        // URL against which the Location is resolved, if it has to be
+        let mpd_url = if self.options.resolve_base_urls {
+            self.options.mpd_url.clone()
+        } else {
+            None
+        };
+
         loop {
-            match self.read_next_event() {
-                Ok(Event::Text(t)) => {
-                    if t.len() > 0 {
-                        match t.unescape() {
-                            Ok(unescaped) => AttributeName::Location.report(unescaped),
-                            Err(err) => ParsingError::from(err).report_err(),
+            match self.read_next_resolved_event() {
+                Ok((_, Event::Text(t))) if !t.is_empty() => match t.unescape() {
+                    Ok(unescaped) => {
+                        let resolved = mpd_url
+                            .as_ref()
+                            .map(|mpd_url| url::resolve(mpd_url, &unescaped))
+                            .filter(|resolved| url::is_absolute(resolved));
+                        AttributeName::Location.report(unescaped);
+                        if let Some(resolved) = resolved {
+                            AttributeName::ResolvedLocation.report(Cow::<str>::Owned(resolved));
                         }
                     }
+                    Err(err) => ParsingError::from(err).report_err(),
+                },
+                Ok((ns, Event::Start(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Location") =>
+                {
+                    inner_tag += 1
                 }
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"Location" => inner_tag += 1,
-                Ok(Event::End(tag)) if tag.name().as_ref() == b"Location" => {
+                Ok((ns, Event::End(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Location") =>
+                {
                     if inner_tag > 0 {
                         inner_tag -= 1;
                     } else {
                         break;
                     }
                 }
-                Ok(Event::Eof) => {
+                Ok((_, Event::Eof)) => {
                     ParsingError("Unexpected end of file in a Location tag.".to_owned())
                         .report_err();
                     break;
@@ -273,17 +859,19 @@ impl MPDProcessor {
         let mut inner_tag: u32 = 0;
 
         loop {
-            match self.read_next_event() {
-                Ok(Event::Text(t)) => {
-                    if t.len() > 0 {
-                        match t.unescape() {
-                            Ok(unescaped) => AttributeName::Text.report(unescaped),
-                            Err(err) => ParsingError::from(err).report_err(),
-                        }
-                    }
+            match self.read_next_resolved_event() {
+                Ok((_, Event::Text(t))) if !t.is_empty() => match t.unescape() {
+                    Ok(unescaped) => AttributeName::Text.report(unescaped),
+                    Err(err) => ParsingError::from(err).report_err(),
+                },
+                Ok((ns, Event::Start(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Label") =>
+                {
+                    inner_tag += 1
                 }
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"Label" => inner_tag += 1,
-                Ok(Event::End(tag)) if tag.name().as_ref() == b"Label" => {
+                Ok((ns, Event::End(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Label") =>
+                {
                     if inner_tag > 0 {
                         inner_tag -= 1;
                     } else {
@@ -291,7 +879,7 @@ impl MPDProcessor {
                         break;
                     }
                 }
-                Ok(Event::Eof) => {
+                Ok((_, Event::Eof)) => {
                     ParsingError("Unexpected end of file in a Label tag.".to_owned()).report_err();
                     break;
                 }
@@ -310,26 +898,52 @@ impl MPDProcessor {
         // Allowing to not close the current node when it is an inner that is closed
         let mut inner_tag: u32 = 0;
 
+        // Text of the BaseURL, only kept when it has to be resolved
+        let mut text: Option<String> = None;
+        let resolve_base_urls = self.options.resolve_base_urls;
+        let keep_text = resolve_base_urls || self.options.resolve_inheritance;
+
         loop {
-            match self.read_next_event() {
-                Ok(Event::Text(t)) => {
-                    if t.len() > 0 {
-                        match t.unescape() {
-                            Ok(unescaped) => AttributeName::Text.report(unescaped),
-                            Err(err) => ParsingError::from(err).report_err(),
+            match self.read_next_resolved_event() {
+                Ok((_, Event::Text(t))) if !t.is_empty() => match t.unescape() {
+                    Ok(unescaped) => {
+                        if keep_text {
+                            text = Some(unescaped.to_string());
                         }
+                        AttributeName::Text.report(unescaped)
                     }
+                    Err(err) => ParsingError::from(err).report_err(),
+                },
+                Ok((ns, Event::Start(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"BaseURL") =>
+                {
+                    inner_tag += 1
                 }
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"BaseURL" => inner_tag += 1,
-                Ok(Event::End(tag)) if tag.name().as_ref() == b"BaseURL" => {
+                Ok((ns, Event::End(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"BaseURL") =>
+                {
                     if inner_tag > 0 {
                         inner_tag -= 1;
                     } else {
+                        if let Some(text) = text.take() {
+                            let resolved = self
+                                .hierarchy
+                                .add_base_url(&text, self.options.mpd_url.as_deref());
+                            if resolve_base_urls {
+                                let absolute: Vec<String> = resolved
+                                    .into_iter()
+                                    .filter(|url| url::is_absolute(url))
+                                    .collect();
+                                if !absolute.is_empty() {
+                                    AttributeName::ResolvedBaseUrls.report(absolute.as_slice());
+                                }
+                            }
+                        }
                         TagName::BaseURL.report_tag_close();
                         break;
                     }
                 }
-                Ok(Event::Eof) => {
+                Ok((_, Event::Eof)) => {
                     ParsingError("Unexpected end of file in a BaseURL.".to_owned()).report_err();
                     break;
                 }
@@ -343,27 +957,27 @@ impl MPDProcessor {
         }
     }
 
-    fn process_cenc_element(&mut self) {
+    /// Loop over a `<cenc:pssh>` element's content, to call when its opening
+    /// tag named `name` has just been found.
+    fn process_cenc_element(&mut self, name: &[u8]) {
         // Count inner cenc:pssh tags if it exists.
         // Allowing to not close the current node when it is an inner that is closed
         let mut inner_tag: u32 = 0;
+        let mut decoded_boxes = Vec::new();
 
         loop {
             match self.read_next_event() {
-                Ok(Event::Text(t)) => {
-                    if t.len() > 0 {
-                        match t.unescape() {
-                            Ok(unescaped) =>
-                            // TODO parse from base64 here?
-                            {
-                                AttributeName::ContentProtectionCencPSSH.report(unescaped)
-                            }
-                            Err(err) => ParsingError::from(err).report_err(),
+                Ok(Event::Text(t)) if !t.is_empty() => match t.unescape() {
+                    Ok(unescaped) => {
+                        if let Some(decoded) = decode_base64_content(&unescaped, "cenc:pssh") {
+                            report_pssh_box(&decoded);
+                            decoded_boxes.push(decoded);
                         }
                     }
-                }
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"cenc:pssh" => inner_tag += 1,
-                Ok(Event::End(tag)) if tag.name().as_ref() == b"cenc:pssh" => {
+                    Err(err) => ParsingError::from(err).report_err(),
+                },
+                Ok(Event::Start(tag)) if tag.name().as_ref() == name => inner_tag += 1,
+                Ok(Event::End(tag)) if tag.name().as_ref() == name => {
                     if inner_tag > 0 {
                         inner_tag -= 1;
                     } else {
@@ -383,6 +997,132 @@ impl MPDProcessor {
             }
             self.reader_buf.clear();
         }
+        if let Some(cp) = self.content_protection.as_mut() {
+            cp.pssh.extend(decoded_boxes);
+        }
+    }
+
+    /// Loop over a `<mspr:pro>` element's content, to call when its opening
+    /// tag named `name` has just been found, and report the information of
+    /// the PlayReady Object it contains.
+    fn process_mspr_pro_element(&mut self, name: &[u8]) {
+        // Count inner mspr:pro tags if it exists.
+        // Allowing to not close the current node when it is an inner that is closed
+        let mut inner_tag: u32 = 0;
+        let mut decoded_objects = Vec::new();
+
+        loop {
+            match self.read_next_event() {
+                Ok(Event::Text(t)) if !t.is_empty() => match t.unescape() {
+                    Ok(unescaped) => {
+                        if let Some(decoded) = decode_base64_content(&unescaped, "mspr:pro") {
+                            report_playready_object(&decoded);
+                            decoded_objects.push(decoded);
+                        }
+                    }
+                    Err(err) => ParsingError::from(err).report_err(),
+                },
+                Ok(Event::Start(tag)) if tag.name().as_ref() == name => inner_tag += 1,
+                Ok(Event::End(tag)) if tag.name().as_ref() == name => {
+                    if inner_tag > 0 {
+                        inner_tag -= 1;
+                    } else {
+                        break;
+                    }
+                }
+                Ok(Event::Eof) => {
+                    ParsingError("Unexpected end of file in a mspr:pro tag.".to_owned())
+                        .report_err();
+                    break;
+                }
+                Err(e) => {
+                    ParsingError::from(e).report_err();
+                    break;
+                }
+                _ => (),
+            }
+            self.reader_buf.clear();
+        }
+        if let Some(cp) = self.content_protection.as_mut() {
+            cp.playready_objects.extend(decoded_objects);
+        }
+    }
+
+    /// Loop over the content of an element announcing a license server URL
+    /// in its text, such as `<dashif:Laurl>`, to call when its opening tag
+    /// named `name` has just been found.
+    fn process_license_url_element(&mut self, name: &[u8], license_type: Option<String>) {
+        // Count inner tags with the same name if it exists.
+        // Allowing to not close the current node when it is an inner that is closed
+        let mut inner_tag: u32 = 0;
+        let mut urls = Vec::new();
+
+        loop {
+            match self.read_next_event() {
+                Ok(Event::Text(t)) if !t.is_empty() => match t.unescape() {
+                    Ok(unescaped) => {
+                        // Reported with each URL, so it never applies to
+                        // the URLs of another element
+                        if let Some(license_type) = &license_type {
+                            AttributeName::ContentProtectionLicenseType
+                                .report(Cow::Borrowed(license_type.as_str()));
+                        }
+                        AttributeName::ContentProtectionLicenseUrl
+                            .report(Cow::Borrowed(&*unescaped));
+                        urls.push((unescaped.into_owned(), license_type.clone()));
+                    }
+                    Err(err) => ParsingError::from(err).report_err(),
+                },
+                Ok(Event::Start(tag)) if tag.name().as_ref() == name => inner_tag += 1,
+                Ok(Event::End(tag)) if tag.name().as_ref() == name => {
+                    if inner_tag > 0 {
+                        inner_tag -= 1;
+                    } else {
+                        break;
+                    }
+                }
+                Ok(Event::Eof) => {
+                    ParsingError("Unexpected end of file in a license URL tag.".to_owned())
+                        .report_err();
+                    break;
+                }
+                Err(e) => {
+                    ParsingError::from(e).report_err();
+                    break;
+                }
+                _ => (),
+            }
+            self.reader_buf.clear();
+        }
+        if let Some(cp) = self.content_protection.as_mut() {
+            cp.license_urls.extend(urls);
+        }
+    }
+
+    /// To call when the `<ContentProtection>` element currently open is
+    /// closed: report what it inherits from the descriptor it references, if
+    /// one was already found, and keep it in case it is referenced by other
+    /// ones.
+    fn resolve_content_protection(&mut self) {
+        let descriptor = match self.content_protection.take() {
+            Some(descriptor) => descriptor,
+            None => return,
+        };
+        let inherited = self.content_protection_refs.resolve(&descriptor);
+        if let Some(inherited) = &inherited {
+            report_content_protection_fields(inherited);
+            AttributeName::ContentProtectionRefResolved.report(true);
+        }
+        if self.options.resolve_inheritance {
+            let effective = match &inherited {
+                Some(inherited) => descriptor.completed_with(inherited),
+                None => descriptor.clone(),
+            };
+            self.hierarchy.add_content_protection(effective);
+        }
+        if let Err(err) = self.content_protection_refs.add(descriptor) {
+            err.report_err();
+        }
     }
 
     fn process_event_stream_element(&mut self) {
@@ -404,9 +1144,11 @@ impl MPDProcessor {
             // corresponding XML.
             let initial_buffer_pos = self.reader.buffer_position();
 
-            let evt = self.read_next_event();
+            let evt = self.read_next_resolved_event();
             match evt {
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"Event" => {
+                Ok((ns, Event::Start(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Event") =>
+                {
                     TagName::EventStreamElt.report_tag_open();
                     attributes::report_event_stream_event_attrs(&tag);
                     match self.get_event_stream_event_ending_position() {
@@ -418,7 +1160,9 @@ impl MPDProcessor {
                     }
                     TagName::EventStreamElt.report_tag_close();
                 }
-                Ok(Event::Empty(tag)) if tag.name().as_ref() == b"Event" => {
+                Ok((ns, Event::Empty(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Event") =>
+                {
                     TagName::EventStreamElt.report_tag_open();
                     attributes::report_event_stream_event_attrs(&tag);
                     let curr_pos = self.reader.buffer_position();
@@ -426,8 +1170,14 @@ impl MPDProcessor {
                         .report((initial_buffer_pos as f64, curr_pos as f64));
                     TagName::EventStreamElt.report_tag_close();
                 }
-                Ok(Event::Start(tag)) if tag.name().as_ref() == b"EventStream" => inner_tag += 1,
-                Ok(Event::End(tag)) if tag.name().as_ref() == b"EventStream" => {
+                Ok((ns, Event::Start(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"EventStream") =>
+                {
+                    inner_tag += 1
+                }
+                Ok((ns, Event::End(tag)))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"EventStream") =>
+                {
                     if inner_tag > 0 {
                         inner_tag -= 1;
                     } else {
@@ -435,7 +1185,7 @@ impl MPDProcessor {
                         break;
                     }
                 }
-                Ok(Event::Eof) => {
+                Ok((_, Event::Eof)) => {
                     ParsingError("Unexpected end of file in a EventStream.".to_owned())
                         .report_err();
                     break;
@@ -455,16 +1205,22 @@ impl MPDProcessor {
     fn get_event_stream_event_ending_position(&mut self) -> Result<usize, ParsingError> {
         let mut inner_event_tag = 0u32;
         loop {
-            match self.read_next_event()? {
-                Event::Start(tag) if tag.name().as_ref() == b"Event" => inner_event_tag += 1,
-                Event::End(tag) if tag.name().as_ref() == b"Event" => {
+            match self.read_next_resolved_event()? {
+                (ns, Event::Start(tag))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Event") =>
+                {
+                    inner_event_tag += 1
+                }
+                (ns, Event::End(tag))
+                    if namespaces::is_named(&ns, tag.name(), tag.local_name(), b"Event") =>
+                {
                     if inner_event_tag > 0 {
                         inner_event_tag -= 1;
                     } else {
                         return Ok(self.reader.buffer_position());
                     }
                 }
-                Event::Eof => {
+                (_, Event::Eof) => {
                     return Err(ParsingError(
                         "Unexpected end of file in an Event element.".to_owned(),
                     ));
@@ -474,3 +1230,130 @@ impl MPDProcessor {
         }
     }
 }
+
+/// Decode the base64 content of the element named `elt_name`, reporting an
+/// error if it is invalid.
+fn decode_base64_content(base64: &str, elt_name: &str) -> Option<Vec<u8>> {
+    match utils::decode_base64(base64.as_bytes()) {
+        Ok(decoded) => Some(decoded),
+        Err(err) => {
+            ParsingError(format!("Invalid {} content: {}", elt_name, err.0)).report_err();
+            None
+        }
+    }
+}
+
+/// Report a `pssh` box, as decoded from a `<cenc:pssh>` element, and its
+/// parsed fields.
+fn report_pssh_box(decoded: &[u8]) {
+    AttributeName::ContentProtectionCencPSSH.report(Cow::Borrowed(decoded));
+    match PsshBox::parse(decoded) {
+        Ok((pssh, warning)) => {
+            if let Some(warning) = warning {
+                warning.to_parsing_error().report_err();
+            }
+            AttributeName::ContentProtectionPssh.report(&pssh);
+        }
+        Err(err) => err.report_err(),
+    }
+}
+
+/// Report the key IDs and license server URL found in the PlayReady Headers
+/// of a PlayReady Object, as decoded from a `<mspr:pro>` element.
+fn report_playready_object(decoded: &[u8]) {
+    let headers = match playready::parse_pro(decoded) {
+        Ok(headers) => headers,
+        Err(err) => {
+            ParsingError(format!("Invalid mspr:pro content: {}", err.0)).report_err();
+            return;
+        }
+    };
+    let key_ids: Vec<u8> = headers
+        .iter()
+        .flat_map(|header| header.key_ids.iter().flatten().copied())
+        .collect();
+    if !key_ids.is_empty() {
+        AttributeName::ContentProtectionPlayReadyKeyIds.report(Cow::Borrowed(key_ids.as_slice()));
+    }
+    if let Some(la_url) = headers.iter().find_map(|header| header.la_url.as_deref()) {
+        AttributeName::ContentProtectionPlayReadyLaUrl.report(Cow::Borrowed(la_url));
+    }
+}
+
+/// Report the information of a `<ContentProtection>` descriptor, such as what
+/// it inherits from the one it references, as if it was defined on the
+/// element currently open.
+fn report_content_protection_fields(descriptor: &ContentProtectionDescriptor) {
+    if let Some(scheme_id_uri) = &descriptor.scheme_id_uri {
+        AttributeName::SchemeIdUri.report(Cow::Borrowed(scheme_id_uri.as_str()));
+    }
+    if let Some(value) = &descriptor.value {
+        AttributeName::ContentProtectionValue.report(Cow::Borrowed(value.as_str()));
+    }
+    if let Some(key_id) = &descriptor.key_id {
+        AttributeName::ContentProtectionKeyId.report(Cow::Borrowed(&key_id[..]));
+    }
+    for pssh in &descriptor.pssh {
+        report_pssh_box(pssh);
+    }
+    for pro in &descriptor.playready_objects {
+        report_playready_object(pro);
+    }
+    for (url, license_type) in &descriptor.license_urls {
+        if let Some(license_type) = license_type {
+            AttributeName::ContentProtectionLicenseType
+                .report(Cow::Borrowed(license_type.as_str()));
+        }
+        AttributeName::ContentProtectionLicenseUrl.report(Cow::Borrowed(url.as_str()));
+    }
+}
+
+/// Report the dynamic range of a `<Representation>` and the enhancement
+/// layers of its codecs, from its descriptors combined with those of its
+/// parents.
+fn report_video_descriptors(descriptors: &VideoDescriptors) {
+    if let Some(info) = descriptors.classify() {
+        AttributeName::HdrInformation.report(info);
+    }
+    let layers =
+        codecs::pair_enhancement_layers(&descriptors.codecs, &descriptors.supplemental_codecs);
+    if !layers.is_empty() {
+        let layers: Vec<Codec> = layers
+            .into_iter()
+            .flat_map(|(base, enhancement)| [base.clone(), enhancement.clone()])
+            .collect();
+        AttributeName::CodecLayers.report(layers.as_slice());
+    }
+}
+
+/// Report the type inferred for an `<AdaptationSet>` and its normalized roles.
+fn report_track_type(track_type: Option<TrackType>, roles: &[Role]) {
+    if let Some(track_type) = track_type {
+        AttributeName::InferredTrackType.report(Cow::Borrowed(&[track_type as u8][..]));
+    }
+    let roles: Vec<u8> = roles.iter().map(|role| *role as u8).collect();
+    AttributeName::NormalizedRoles.report(Cow::<[u8]>::Owned(roles));
+}
+
+/// Report the thumbnails contained in the given image segments of a
+/// `<Representation>`, laid out following `grid` in images of the given
+/// width and height.
+fn report_thumbnails(grid: TileGrid, size: Option<(u32, u32)>, segments: &[Segment]) {
+    let (width, height) = match size {
+        Some(size) => size,
+        None => {
+            ParsingError(
+                "Cannot compute thumbnails: no valid width and height for the \
+                 Representation"
+                    .to_owned(),
+            )
+            .report_err();
+            return;
+        }
+    };
+    let thumbnails: Vec<Thumbnail> = segments
+        .iter()
+        .flat_map(|segment| grid.thumbnails(segment, width, height))
+        .collect();
+    AttributeName::Thumbnails.report(thumbnails.as_slice());
+}
@@ -0,0 +1,150 @@
+use quick_xml::name::{LocalName, Namespace, QName, ResolveResult};
+use quick_xml::NsReader;
+use std::borrow::Cow;
+
+/// Namespaces of the elements and attributes the processor recognizes, with
+/// the prefix they are conventionally written with.
+///
+/// An empty prefix means that they are matched by their local name only.
+const KNOWN_NAMESPACES: [(&[u8], &[u8]); 9] = [
+    (b"urn:mpeg:dash:schema:mpd:2011", b""),
+    (b"urn:mpeg:DASH:schema:MPD:2011", b""),
+    (b"urn:mpeg:cenc:2013", b"cenc"),
+    (b"urn:microsoft:playready", b"mspr"),
+    (b"urn:microsoft", b"ms"),
+    (b"http://www.w3.org/1999/xlink", b"xlink"),
+    (b"urn:scte:dash:scte214-extensions", b"scte214"),
+    (b"https://dashif.org/CPS", b"dashif"),
+    (b"http://dashif.org/guidelines/clearKey", b"clearkey"),
+];
+
+/// Returns the name under which the processor recognizes an element or
+/// attribute, whatever the prefix used for its namespace in the MPD.
+///
+/// Names in a known namespace are rewritten with the conventional prefix of
+/// that namespace (e.g. `<c:pssh>` with `xmlns:c="urn:mpeg:cenc:2013"` becomes
+/// `cenc:pssh`), and without prefix for the DASH namespace.
+///
+/// Unprefixed names, as well as names whose prefix was not declared, are
+/// returned as is so MPDs relying on conventional prefixes without declaring
+/// them are still understood.
+/// Names in an unknown namespace are returned in the `{namespace}local-name`
+/// form, which never matches a recognized name.
+pub fn canonical_name<'a>(
+    ns: ResolveResult,
+    qname: QName<'a>,
+    local: LocalName<'a>,
+) -> Cow<'a, [u8]> {
+    if qname.prefix().is_none() {
+        return Cow::Borrowed(qname.into_inner());
+    }
+    match ns {
+        ResolveResult::Bound(Namespace(uri)) => {
+            match KNOWN_NAMESPACES.iter().find(|(known, _)| *known == uri) {
+                Some((_, b"")) => Cow::Borrowed(local.into_inner()),
+                Some((_, prefix)) => {
+                    let mut name = Vec::with_capacity(prefix.len() + 1 + local.as_ref().len());
+                    name.extend_from_slice(prefix);
+                    name.push(b':');
+                    name.extend_from_slice(local.as_ref());
+                    Cow::Owned(name)
+                }
+                None => {
+                    let mut name = Vec::with_capacity(uri.len() + 2 + local.as_ref().len());
+                    name.push(b'{');
+                    name.extend_from_slice(uri);
+                    name.push(b'}');
+                    name.extend_from_slice(local.as_ref());
+                    Cow::Owned(name)
+                }
+            }
+        }
+        ResolveResult::Unbound | ResolveResult::Unknown(_) => Cow::Borrowed(qname.into_inner()),
+    }
+}
+
+/// Returns `true` if the element with the given qualified and local names,
+/// in the namespace `ns`, is recognized as `name` (@see `canonical_name`).
+pub fn is_named(ns: &ResolveResult, qname: QName, local: LocalName, name: &[u8]) -> bool {
+    canonical_name(ns.clone(), qname, local).as_ref() == name
+}
+
+/// Returns the name under which the processor recognizes the attribute with
+/// the given qualified name (@see `canonical_name`).
+pub fn canonical_attr_name<'a, R>(reader: &NsReader<R>, qname: QName<'a>) -> Cow<'a, [u8]> {
+    let (ns, local) = reader.resolve_attribute(qname);
+    canonical_name(ns, qname, local)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+
+    #[test]
+    fn test_canonical_name() {
+        let xml = r#"<MPD xmlns="urn:mpeg:dash:schema:mpd:2011" xmlns:c="urn:mpeg:cenc:2013"
+                        xmlns:d="urn:mpeg:dash:schema:mpd:2011" xmlns:o="urn:other">
+            <c:pssh/><d:Period/><cenc:pssh/><o:pssh/><Period/>
+            <ContentProtection c:default_KID="a" value="b"/>
+        </MPD>"#;
+        let mut reader = NsReader::from_str(xml);
+        reader.trim_text(true);
+        let mut names = Vec::new();
+        let mut attr_names = Vec::new();
+        loop {
+            match reader.read_resolved_event().unwrap() {
+                (_, Event::Eof) => break,
+                (ns, Event::Empty(tag)) => {
+                    let name = canonical_name(ns, tag.name(), tag.local_name());
+                    names.push(String::from_utf8(name.into_owned()).unwrap());
+                    for attr in tag.attributes() {
+                        let attr = attr.unwrap();
+                        let name = canonical_attr_name(&reader, attr.key);
+                        attr_names.push(String::from_utf8(name.into_owned()).unwrap());
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(
+            names,
+            vec![
+                "cenc:pssh",
+                "Period",
+                "cenc:pssh",
+                "{urn:other}pssh",
+                "Period",
+                "ContentProtection"
+            ]
+        );
+        assert_eq!(attr_names, vec!["cenc:default_KID", "value"]);
+    }
+
+    #[test]
+    fn test_is_named() {
+        let xml = r#"<mpd:BaseURL xmlns:mpd="urn:mpeg:dash:schema:mpd:2011"
+                        xmlns:p="urn:microsoft"><mpd:S/><p:laurl/><S/><o:S/></mpd:BaseURL>"#;
+        let mut reader = NsReader::from_str(xml);
+        let mut results = Vec::new();
+        loop {
+            match reader.read_resolved_event().unwrap() {
+                (_, Event::Eof) => break,
+                (ns, Event::Empty(tag)) => {
+                    results.push((
+                        is_named(&ns, tag.name(), tag.local_name(), b"S"),
+                        is_named(&ns, tag.name(), tag.local_name(), b"ms:laurl"),
+                    ));
+                }
+                (ns, Event::End(tag)) => {
+                    assert!(is_named(&ns, tag.name(), tag.local_name(), b"BaseURL"));
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(
+            results,
+            vec![(true, false), (false, true), (true, false), (false, false)]
+        );
+    }
+}
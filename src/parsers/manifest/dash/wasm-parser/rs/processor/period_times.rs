@@ -0,0 +1,140 @@
+use crate::errors::ParsingError;
+use crate::periods::{self, MpdTimeInfo, PeriodTimeInfo, ResolvedPeriod};
+use crate::utils;
+
+/// Collects the time attributes of the `<MPD>` and of its `<Period>`
+/// elements, so their time bounds can be resolved once the whole MPD is
+/// known.
+#[derive(Default)]
+pub struct PeriodTimesTracker {
+    mpd: MpdTimeInfo,
+    periods: Vec<PeriodTimeInfo>,
+
+    /// `true` if a `<Period>` has to be fetched through an xlink, in which
+    /// case the `<Period>` elements of the MPD are not the final ones.
+    has_xlink: bool,
+}
+
+impl PeriodTimesTracker {
+    /// Signal that the `<MPD>` element has been opened.
+    pub fn open_mpd(&mut self, tag: &quick_xml::events::BytesStart) {
+        *self = PeriodTimesTracker::default();
+        for attr in tag.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"type" => self.mpd.is_dynamic = attr.value.as_ref() == b"dynamic",
+                b"availabilityStartTime" => {
+                    self.mpd.availability_start_time = utils::parse_date_time(&attr.value).ok()
+                }
+                b"mediaPresentationDuration" => {
+                    self.mpd.media_presentation_duration =
+                        utils::parse_iso_8601_duration(&attr.value).ok()
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Signal that a `<Period>` element has been opened.
+    pub fn open_period(&mut self, tag: &quick_xml::events::BytesStart) {
+        let mut period = PeriodTimeInfo::default();
+        for attr in tag.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"id" => {
+                    if let Ok(id) = attr.unescape_value() {
+                        period.id = id.into_owned();
+                    }
+                }
+                b"start" => period.start = utils::parse_iso_8601_duration(&attr.value).ok(),
+                b"duration" => period.duration = utils::parse_iso_8601_duration(&attr.value).ok(),
+                _ if attr.key.local_name().as_ref() == b"href" => self.has_xlink = true,
+                _ => {}
+            }
+        }
+        self.periods.push(period);
+    }
+
+    /// Resolves the time bounds of the `<Period>` elements of the MPD,
+    /// flattening them if `flatten` is set.
+    ///
+    /// Returns `None` if they cannot be resolved, either because of an error,
+    /// returned alongside, or because some `<Period>` elements still have to
+    /// be fetched through an xlink. Diagnostics for each flattened `<Period>`
+    /// are also returned.
+    pub fn resolve(&self, flatten: bool) -> (Option<Vec<ResolvedPeriod>>, Vec<ParsingError>) {
+        if self.has_xlink {
+            return (None, Vec::new());
+        }
+        match periods::resolve(&self.mpd, &self.periods) {
+            Ok(mut resolved) => {
+                let diagnostics = if flatten {
+                    periods::flatten(&self.periods, &mut resolved)
+                } else {
+                    Vec::new()
+                };
+                (Some(resolved), diagnostics)
+            }
+            Err(err) => (None, vec![err]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::BytesStart;
+
+    fn bounds(resolved: &[ResolvedPeriod]) -> Vec<(f64, Option<f64>, bool)> {
+        resolved
+            .iter()
+            .map(|period| (period.start, period.end, period.removed))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_periods() {
+        let mut tracker = PeriodTimesTracker::default();
+        tracker.open_mpd(&BytesStart::from_content(
+            "MPD type=\"dynamic\" availabilityStartTime=\"1970-01-01T00:16:40Z\"",
+            3,
+        ));
+        tracker.open_period(&BytesStart::from_content(
+            "Period id=\"1\" duration=\"PT30S\"",
+            6,
+        ));
+        tracker.open_period(&BytesStart::from_content(
+            "Period id=\"2\" start=\"PT1010S\"",
+            6,
+        ));
+        let (resolved, errors) = tracker.resolve(false);
+        assert!(errors.is_empty());
+        assert_eq!(
+            bounds(&resolved.unwrap()),
+            vec![(1000., Some(1030.), false), (1010., None, false)]
+        );
+        let (resolved, errors) = tracker.resolve(true);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            bounds(&resolved.unwrap()),
+            vec![(1000., Some(1010.), false), (1010., None, false)]
+        );
+
+        // A dynamic MPD without availabilityStartTime starts at 0, and the
+        // Periods of a previous MPD are forgotten
+        tracker.open_mpd(&BytesStart::from_content("MPD type=\"dynamic\"", 3));
+        tracker.open_period(&BytesStart::new("Period"));
+        let (resolved, errors) = tracker.resolve(false);
+        assert!(errors.is_empty());
+        assert_eq!(bounds(&resolved.unwrap()), vec![(0., None, false)]);
+
+        // Nothing is resolved while some Periods have to be fetched
+        tracker.open_mpd(&BytesStart::from_content(
+            "MPD mediaPresentationDuration=\"PT1M\"",
+            3,
+        ));
+        tracker.open_period(&BytesStart::from_content(
+            "Period xlink:href=\"http://example.com/period.xml\"",
+            6,
+        ));
+        assert!(tracker.resolve(false).0.is_none());
+    }
+}
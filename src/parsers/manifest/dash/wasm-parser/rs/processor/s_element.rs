@@ -1,5 +1,6 @@
 use crate::errors::{ParsingError, Result};
 use crate::utils;
+use core::mem;
 
 /// Represents a parsed <S> node, itself in a <SegmentTimeline> node from an
 /// MPD.
@@ -7,7 +8,7 @@ use crate::utils;
 /// Attributes are defined as f64 despite being u64 to simplify Rust-to-JS
 /// communication.
 #[repr(C)] // Used in FFI
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct SegmentObject {
     /// Starting timestamp for the segment, in the corresponding Timescale
     ///
@@ -70,3 +71,33 @@ impl SegmentObject {
         Ok(segment_obj)
     }
 }
+
+impl SegmentObject {
+    /// Re-construct the list of `SegmentObject` from the data previously
+    /// reported through the `SegmentTimeline` attribute: each `SegmentObject`
+    /// as three contiguous little-endian f64 values (`start`, `duration` and
+    /// `repeat_count`).
+    pub fn from_bytes(data: &[u8]) -> Result<Vec<SegmentObject>> {
+        const SIZE: usize = mem::size_of::<SegmentObject>();
+        if data.len() % SIZE != 0 {
+            return Err(ParsingError(format!(
+                "Invalid timeline data: length {} is not a multiple of {}",
+                data.len(),
+                SIZE
+            )));
+        }
+        let read_f64 = |bytes: &[u8]| {
+            let mut arr = [0u8; 8];
+            arr.copy_from_slice(bytes);
+            f64::from_le_bytes(arr)
+        };
+        Ok(data
+            .chunks_exact(SIZE)
+            .map(|chunk| SegmentObject {
+                start: read_f64(&chunk[0..8]),
+                duration: read_f64(&chunk[8..16]),
+                repeat_count: read_f64(&chunk[16..24]),
+            })
+            .collect())
+    }
+}
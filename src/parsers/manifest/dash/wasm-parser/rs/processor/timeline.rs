@@ -0,0 +1,312 @@
+use crate::errors::ParsingError;
+use crate::processor::SegmentObject;
+
+/// Discontinuity detected between two consecutive `<S>` elements of a
+/// `<SegmentTimeline>`.
+///
+/// All values are expressed in the corresponding timescale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimelineDiscontinuity {
+    /// The `<S>` element starts after the end of the previous one.
+    Gap { previous_end: f64, start: f64 },
+
+    /// The `<S>` element starts before the end of the previous one.
+    Overlap { previous_end: f64, start: f64 },
+}
+
+impl TimelineDiscontinuity {
+    /// Convert this discontinuity into a `ParsingError` which can be reported
+    /// to the JS-side.
+    ///
+    /// `position` is the position in bytes in the MPD from which the
+    /// concerned `<S>` element has been read, which is the end of the markup
+    /// preceding it.
+    pub fn to_parsing_error(self, position: usize) -> ParsingError {
+        match self {
+            TimelineDiscontinuity::Gap {
+                previous_end,
+                start,
+            } => ParsingError(format!(
+                "SegmentTimeline gap of {} ticks at position {}: \
+                 S@t is {} but the previous S element ended at {}",
+                start - previous_end,
+                position,
+                start,
+                previous_end
+            )),
+            TimelineDiscontinuity::Overlap {
+                previous_end,
+                start,
+            } => ParsingError(format!(
+                "SegmentTimeline overlap of {} ticks at position {}: \
+                 S@t is {} but the previous S element ended at {}",
+                previous_end - start,
+                position,
+                start,
+                previous_end
+            )),
+        }
+    }
+}
+
+/// Push `segment` at the end of `timeline`, folding it into the last entry's
+/// repeat count when both are contiguous and share the same duration.
+///
+/// Returns the discontinuity between that last entry and `segment`, if one.
+/// Entries whose end is unknown (because they have a negative repeat count)
+/// are never folded nor checked.
+pub fn push_normalized(
+    timeline: &mut Vec<SegmentObject>,
+    segment: SegmentObject,
+) -> Option<TimelineDiscontinuity> {
+    let last = match timeline.last_mut() {
+        Some(last) if last.repeat_count >= 0. => last,
+        _ => {
+            timeline.push(segment);
+            return None;
+        }
+    };
+    let previous_end = last.start + last.duration * (last.repeat_count + 1.);
+    let discontinuity = if segment.start > previous_end {
+        Some(TimelineDiscontinuity::Gap {
+            previous_end,
+            start: segment.start,
+        })
+    } else if segment.start < previous_end {
+        Some(TimelineDiscontinuity::Overlap {
+            previous_end,
+            start: segment.start,
+        })
+    } else {
+        None
+    };
+
+    if discontinuity.is_none() && last.duration == segment.duration && segment.repeat_count >= 0. {
+        last.repeat_count += segment.repeat_count + 1.;
+    } else {
+        timeline.push(segment);
+    }
+    discontinuity
+}
+
+/// Result of merging a `<SegmentTimeline>` with its previous version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineMerge {
+    /// Start, in the corresponding timescale, of the first segment found in
+    /// both timelines.
+    /// Entries of the merged timeline begin at that time.
+    pub first_common_start: f64,
+
+    /// Entries of the new timeline which are either not in the previous one or
+    /// which changed since (e.g. its last entry, with a higher repeat count).
+    ///
+    /// They replace every entry of the previous timeline starting at or after
+    /// the start of the first of them.
+    pub updated: Vec<SegmentObject>,
+}
+
+/// Find the index of the entry of `timeline` in which a segment starts at
+/// `time`, and that entry with its `start` and `repeat_count` updated so it
+/// begins at that segment.
+///
+/// Returns `None` if no segment starts at `time` in `timeline`.
+fn find_segment_start(timeline: &[SegmentObject], time: f64) -> Option<(usize, SegmentObject)> {
+    for (i, entry) in timeline.iter().enumerate() {
+        if entry.start == time {
+            return Some((i, *entry));
+        } else if entry.start > time {
+            return None;
+        } else if entry.repeat_count > 0. && entry.duration > 0. {
+            let diff = time - entry.start;
+            let repeat_nb = diff / entry.duration;
+            if repeat_nb.fract() == 0. && repeat_nb <= entry.repeat_count {
+                let trimmed = SegmentObject {
+                    start: time,
+                    duration: entry.duration,
+                    repeat_count: entry.repeat_count - repeat_nb,
+                };
+                return Some((i, trimmed));
+            }
+        }
+    }
+    None
+}
+
+/// Returns `true` if `new_timeline` has segments starting before the first
+/// one of `prev_timeline`.
+pub fn starts_before(new_timeline: &[SegmentObject], prev_timeline: &[SegmentObject]) -> bool {
+    match (new_timeline.first(), prev_timeline.first()) {
+        (Some(new_first), Some(prev_first)) => new_first.start < prev_first.start,
+        _ => false,
+    }
+}
+
+/// Merge `new_timeline`, which has just been parsed, with `prev_timeline`,
+/// which was obtained for the same `<SegmentTimeline>` in the previous version
+/// of the MPD.
+///
+/// Returns `None` if both cannot be merged, either because no segment is
+/// common to both or because the entries they have in common differ.
+/// In that case, `new_timeline` should be considered as a whole.
+///
+/// `new_timeline` should also be considered as a whole when it starts before
+/// `prev_timeline` (@see `starts_before`), as its first entries would be
+/// lost otherwise. `None` is then returned.
+pub fn merge_with_previous(
+    prev_timeline: &[SegmentObject],
+    new_timeline: &[SegmentObject],
+) -> Option<TimelineMerge> {
+    let prev_first = prev_timeline.first()?;
+    let new_first = new_timeline.first()?;
+    if new_first.start < prev_first.start || prev_timeline.iter().any(|s| s.repeat_count < 0.) {
+        return None;
+    }
+    let first_common_start = new_first.start;
+    let (prev_idx, prev_trimmed) = find_segment_start(prev_timeline, first_common_start)?;
+    let (new_idx, new_trimmed) = find_segment_start(new_timeline, first_common_start)?;
+
+    let prev_common = &prev_timeline[prev_idx..];
+    let new_common = &new_timeline[new_idx..];
+    if new_common.len() < prev_common.len() {
+        return None;
+    }
+    let last_prev_idx = prev_common.len() - 1;
+    for i in 0..prev_common.len() {
+        let prev = if i == 0 { prev_trimmed } else { prev_common[i] };
+        let new = if i == 0 { new_trimmed } else { new_common[i] };
+        if prev.start != new.start || prev.duration != new.duration {
+            return None;
+        }
+        if prev.repeat_count == new.repeat_count {
+            continue;
+        }
+        if i != last_prev_idx || (new.repeat_count >= 0. && new.repeat_count < prev.repeat_count) {
+            return None;
+        }
+
+        // Last entry of the previous timeline was extended
+        let mut updated = Vec::with_capacity(new_common.len() - i);
+        updated.push(new);
+        updated.extend_from_slice(&new_common[i + 1..]);
+        return Some(TimelineMerge {
+            first_common_start,
+            updated,
+        });
+    }
+    Some(TimelineMerge {
+        first_common_start,
+        updated: new_common[prev_common.len()..].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f64, duration: f64, repeat_count: f64) -> SegmentObject {
+        SegmentObject {
+            start,
+            duration,
+            repeat_count,
+        }
+    }
+
+    #[test]
+    fn test_push_normalized_folds_identical_entries() {
+        let mut timeline = Vec::new();
+        assert!(push_normalized(&mut timeline, seg(0., 10., 0.)).is_none());
+        assert!(push_normalized(&mut timeline, seg(10., 10., 0.)).is_none());
+        assert!(push_normalized(&mut timeline, seg(20., 10., 2.)).is_none());
+        assert!(push_normalized(&mut timeline, seg(50., 10., 0.)).is_none());
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].start, 0.);
+        assert_eq!(timeline[0].repeat_count, 5.);
+
+        assert!(push_normalized(&mut timeline, seg(60., 12., 0.)).is_none());
+        assert!(push_normalized(&mut timeline, seg(72., 12., 0.)).is_none());
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[1].start, 60.);
+        assert_eq!(timeline[1].repeat_count, 1.);
+    }
+
+    #[test]
+    fn test_push_normalized_discontinuities() {
+        let mut timeline = Vec::new();
+        push_normalized(&mut timeline, seg(0., 10., 1.));
+        assert_eq!(
+            push_normalized(&mut timeline, seg(25., 10., 0.)),
+            Some(TimelineDiscontinuity::Gap {
+                previous_end: 20.,
+                start: 25.
+            })
+        );
+        assert_eq!(
+            push_normalized(&mut timeline, seg(30., 10., 0.)),
+            Some(TimelineDiscontinuity::Overlap {
+                previous_end: 35.,
+                start: 30.
+            })
+        );
+        assert_eq!(timeline.len(), 3);
+    }
+
+    #[test]
+    fn test_push_normalized_negative_repeat_count() {
+        let mut timeline = Vec::new();
+        push_normalized(&mut timeline, seg(0., 10., -1.));
+        assert!(push_normalized(&mut timeline, seg(100., 10., 0.)).is_none());
+        assert!(push_normalized(&mut timeline, seg(110., 10., -1.)).is_none());
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[1].repeat_count, 0.);
+    }
+
+    #[test]
+    fn test_merge_with_previous_extended_last_entry() {
+        let prev = [seg(0., 10., 4.), seg(50., 12., 2.)];
+        let new = [seg(20., 10., 2.), seg(50., 12., 4.), seg(110., 10., 0.)];
+        let merge = merge_with_previous(&prev, &new).unwrap();
+        assert_eq!(merge.first_common_start, 20.);
+        assert_eq!(merge.updated, vec![seg(50., 12., 4.), seg(110., 10., 0.)]);
+    }
+
+    #[test]
+    fn test_merge_with_previous_new_entries_only() {
+        let prev = [seg(0., 10., 4.), seg(50., 12., 2.)];
+        let new = [seg(50., 12., 2.), seg(86., 10., 1.)];
+        let merge = merge_with_previous(&prev, &new).unwrap();
+        assert_eq!(merge.first_common_start, 50.);
+        assert_eq!(merge.updated, vec![seg(86., 10., 1.)]);
+
+        let merge = merge_with_previous(&prev, &prev).unwrap();
+        assert_eq!(merge.first_common_start, 0.);
+        assert!(merge.updated.is_empty());
+    }
+
+    #[test]
+    fn test_merge_with_previous_new_timeline_starting_earlier() {
+        // The new timeline has to be considered as a whole, to not lose its
+        // first entries
+        let prev = [seg(30., 10., 1.)];
+        let new = [seg(0., 10., 4.), seg(50., 10., 0.)];
+        assert!(starts_before(&new, &prev));
+        assert!(merge_with_previous(&prev, &new).is_none());
+
+        assert!(!starts_before(&prev, &new));
+        assert!(!starts_before(&[], &prev));
+    }
+
+    #[test]
+    fn test_merge_with_previous_incompatible() {
+        let prev = [seg(0., 10., 4.), seg(50., 12., 2.)];
+        // No common segment start
+        assert!(merge_with_previous(&prev, &[seg(25., 10., 3.)]).is_none());
+        // Common entry with a different duration
+        assert!(merge_with_previous(&prev, &[seg(20., 10., 2.), seg(50., 11., 2.)]).is_none());
+        // Common entry in the middle which changed
+        assert!(merge_with_previous(&prev, &[seg(20., 10., 1.), seg(50., 12., 2.)]).is_none());
+        // Shorter timeline
+        assert!(merge_with_previous(&prev, &[seg(20., 10., 2.)]).is_none());
+        assert!(merge_with_previous(&[], &prev).is_none());
+        assert!(merge_with_previous(&prev, &[]).is_none());
+    }
+}
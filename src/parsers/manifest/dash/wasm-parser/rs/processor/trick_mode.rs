@@ -0,0 +1,188 @@
+use crate::errors::ParsingError;
+
+use super::attributes;
+
+/// `schemeIdUri` of the `<EssentialProperty>` signaling a trick mode
+/// `<AdaptationSet>`, whose value lists the `id` of the `<AdaptationSet>`
+/// elements it is associated to.
+pub const TRICK_MODE_SCHEME: &str = "http://dashif.org/guidelines/trickmode";
+
+/// Links the trick mode `<AdaptationSet>` elements of a `<Period>` to the
+/// `<AdaptationSet>` elements they are associated to.
+///
+/// As a trick mode `<AdaptationSet>` may be defined before the ones it
+/// references, links are only resolved once the whole `<Period>` is known.
+#[derive(Default)]
+pub struct TrickModeLinker {
+    /// `id` attributes of the `<AdaptationSet>` elements of the current
+    /// `<Period>`, in document order. Empty if not set.
+    ids: Vec<String>,
+
+    /// `id` lists referenced by trick mode `<AdaptationSet>` elements, with
+    /// the index of those in `ids`.
+    references: Vec<(usize, Vec<String>)>,
+
+    /// `true` when a `<Representation>` element is currently open.
+    in_representation: bool,
+
+    /// `true` when an `<AdaptationSet>` element is currently open.
+    in_adaptation_set: bool,
+}
+
+impl TrickModeLinker {
+    /// Signal that a `<Period>` element has been opened.
+    pub fn open_period(&mut self) {
+        *self = TrickModeLinker::default();
+    }
+
+    /// Signal that an `<AdaptationSet>` element has been opened.
+    pub fn open_adaptation_set(&mut self, tag: &quick_xml::events::BytesStart) {
+        let id = tag
+            .try_get_attribute("id")
+            .ok()
+            .flatten()
+            .and_then(|attr| attr.unescape_value().ok())
+            .map(|id| id.trim().to_owned())
+            .unwrap_or_default();
+        self.ids.push(id);
+        self.in_adaptation_set = true;
+    }
+
+    /// Signal that the current `<AdaptationSet>` element has been closed.
+    pub fn close_adaptation_set(&mut self) {
+        self.in_adaptation_set = false;
+        self.in_representation = false;
+    }
+
+    /// Signal that a `<Representation>` element has been opened or closed.
+    pub fn set_in_representation(&mut self, in_representation: bool) {
+        self.in_representation = in_representation;
+    }
+
+    /// Takes into account an `<EssentialProperty>` element. Only those found
+    /// directly in an `<AdaptationSet>` may signal a trick mode one.
+    pub fn add_property(&mut self, tag: &quick_xml::events::BytesStart) {
+        if !self.in_adaptation_set || self.in_representation {
+            return;
+        }
+        let (scheme_id_uri, value) = attributes::scheme_attrs(tag);
+        if scheme_id_uri.as_deref().map(str::trim) != Some(TRICK_MODE_SCHEME) {
+            return;
+        }
+        let ids = value
+            .as_deref()
+            .unwrap_or_default()
+            .split(|c: char| c == ',' || c.is_ascii_whitespace())
+            .filter(|id| !id.is_empty())
+            .map(str::to_owned)
+            .collect();
+        self.references.push((self.ids.len() - 1, ids));
+    }
+
+    /// Resolves the links of the trick mode `<AdaptationSet>` elements of the
+    /// current `<Period>`, as couples of the index of a trick mode
+    /// `<AdaptationSet>` and of the index of an `<AdaptationSet>` it is
+    /// associated to, both in document order in the `<Period>`.
+    ///
+    /// Also returns an error for each reference which could not be resolved.
+    pub fn resolve(&self) -> (Vec<(usize, usize)>, Vec<ParsingError>) {
+        let mut links = Vec::new();
+        let mut errors = Vec::new();
+        for (trick_mode_idx, ids) in &self.references {
+            if ids.is_empty() {
+                errors.push(ParsingError(format!(
+                    "Trick mode AdaptationSet {} references no AdaptationSet",
+                    self.describe(*trick_mode_idx)
+                )));
+            }
+            for id in ids {
+                match self.ids.iter().position(|other| other == id) {
+                    Some(idx) if idx == *trick_mode_idx => errors.push(ParsingError(format!(
+                        "Trick mode AdaptationSet {} references itself",
+                        self.describe(*trick_mode_idx)
+                    ))),
+                    Some(idx) => links.push((*trick_mode_idx, idx)),
+                    None => errors.push(ParsingError(format!(
+                        "Trick mode AdaptationSet {} references an unknown AdaptationSet \
+                         id: \"{}\"",
+                        self.describe(*trick_mode_idx),
+                        id
+                    ))),
+                }
+            }
+        }
+        (links, errors)
+    }
+
+    /// Designates an `<AdaptationSet>` in diagnostics, by its `id` if it has
+    /// one or else by its position in the `<Period>`.
+    fn describe(&self, idx: usize) -> String {
+        match self.ids.get(idx) {
+            Some(id) if !id.is_empty() => format!("\"{}\"", id),
+            _ => format!("#{}", idx + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::BytesStart;
+
+    fn adaptation_set(linker: &mut TrickModeLinker, id: &str, trick_mode: Option<&str>) {
+        let mut tag = BytesStart::new("AdaptationSet");
+        if !id.is_empty() {
+            tag.push_attribute(("id", id));
+        }
+        linker.open_adaptation_set(&tag);
+        if let Some(value) = trick_mode {
+            let mut property = BytesStart::new("EssentialProperty");
+            property.push_attribute(("schemeIdUri", TRICK_MODE_SCHEME));
+            property.push_attribute(("value", value));
+            linker.add_property(&property);
+        }
+        linker.close_adaptation_set();
+    }
+
+    #[test]
+    fn test_resolve_links() {
+        let mut linker = TrickModeLinker::default();
+        adaptation_set(&mut linker, "", Some("1 3"));
+        adaptation_set(&mut linker, "1", None);
+        adaptation_set(&mut linker, "2", None);
+        adaptation_set(&mut linker, "3", None);
+        let (links, errors) = linker.resolve();
+        assert_eq!(links, vec![(0, 1), (0, 3)]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_references() {
+        let mut linker = TrickModeLinker::default();
+        adaptation_set(&mut linker, "video", None);
+        adaptation_set(&mut linker, "trick", Some("vide0,trick"));
+        adaptation_set(&mut linker, "", Some(""));
+        let (links, errors) = linker.resolve();
+        assert!(links.is_empty());
+        let messages: Vec<String> = errors.into_iter().map(|err| err.0).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Trick mode AdaptationSet \"trick\" references an unknown AdaptationSet id: \
+                 \"vide0\"",
+                "Trick mode AdaptationSet \"trick\" references itself",
+                "Trick mode AdaptationSet #3 references no AdaptationSet",
+            ]
+        );
+
+        // Properties of Representation elements are not considered
+        let mut linker = TrickModeLinker::default();
+        linker.open_adaptation_set(&BytesStart::new("AdaptationSet"));
+        linker.set_in_representation(true);
+        let mut property = BytesStart::new("EssentialProperty");
+        property.push_attribute(("schemeIdUri", TRICK_MODE_SCHEME));
+        property.push_attribute(("value", "1"));
+        linker.add_property(&property);
+        assert!(linker.references.is_empty());
+    }
+}
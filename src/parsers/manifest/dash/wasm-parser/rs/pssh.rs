@@ -0,0 +1,247 @@
+use crate::errors::{ParsingError, Result};
+
+/// Content of a Protection System Specific Header (`pssh`) box, as defined by
+/// ISO/IEC 23001-7.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PsshBox {
+    /// Version of the box. Only versions `0` and `1` are supported.
+    pub version: u8,
+
+    /// Identifier of the DRM system this box is intended for.
+    pub system_id: [u8; 16],
+
+    /// Key IDs listed in the box, only present in version `1` boxes.
+    pub key_ids: Vec<[u8; 16]>,
+
+    /// DRM-specific data carried by the box.
+    pub data: Vec<u8>,
+}
+
+/// Anomaly found when parsing a `pssh` box which does not prevent from
+/// obtaining its content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PsshWarning {
+    /// The box's size field does not correspond to the length of the data it
+    /// was found in.
+    SizeMismatch { box_size: u64, data_len: usize },
+
+    /// The box, whose size is consistent with the data it was found in,
+    /// contains that many bytes after the end of its content.
+    TrailingData { len: usize },
+}
+
+impl PsshWarning {
+    /// Convert this warning into a `ParsingError` which can be reported to the
+    /// JS-side.
+    pub fn to_parsing_error(self) -> ParsingError {
+        match self {
+            PsshWarning::SizeMismatch { box_size, data_len } => ParsingError(format!(
+                "Invalid pssh box: its size field is {} but {} bytes were found",
+                box_size, data_len
+            )),
+            PsshWarning::TrailingData { len } => ParsingError(format!(
+                "Invalid pssh box: {} bytes of trailing data after its content",
+                len
+            )),
+        }
+    }
+}
+
+/// Cursor reading big endian values in a `pssh` box, returning an error as
+/// soon as the box is truncated.
+struct BoxReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BoxReader<'a> {
+    fn read_bytes(&mut self, len: usize, field: &str) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len());
+        match end {
+            Some(end) => {
+                let bytes = &self.data[self.offset..end];
+                self.offset = end;
+                Ok(bytes)
+            }
+            None => Err(ParsingError(format!(
+                "Invalid pssh box: truncated at offset {} while reading its {} \
+                 ({} bytes needed, {} remaining)",
+                self.offset,
+                field,
+                len,
+                self.data.len() - self.offset
+            ))),
+        }
+    }
+
+    fn read_u32(&mut self, field: &str) -> Result<u32> {
+        let bytes = self.read_bytes(4, field)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_uuid(&mut self, field: &str) -> Result<[u8; 16]> {
+        let mut uuid = [0u8; 16];
+        uuid.copy_from_slice(self.read_bytes(16, field)?);
+        Ok(uuid)
+    }
+}
+
+impl PsshBox {
+    /// Parse the `pssh` box contained in `data`.
+    ///
+    /// Returns an error if `data` is not a `pssh` box or if it is truncated.
+    /// If its size field does not correspond to the length of `data`, or if
+    /// the box contains data after its content, its content is still
+    /// returned, with the corresponding warning.
+    pub fn parse(data: &[u8]) -> Result<(PsshBox, Option<PsshWarning>)> {
+        let mut reader = BoxReader { data, offset: 0 };
+        let mut box_size = reader.read_u32("size")? as u64;
+        let box_type = reader.read_bytes(4, "type")?;
+        if box_type != b"pssh" {
+            return Err(ParsingError(format!(
+                "Invalid pssh box: unexpected box type \"{}\"",
+                String::from_utf8_lossy(box_type)
+            )));
+        }
+        if box_size == 1 {
+            let high = reader.read_u32("largesize")? as u64;
+            let low = reader.read_u32("largesize")? as u64;
+            box_size = (high << 32) | low;
+        } else if box_size == 0 {
+            // The box extends to the end of the data
+            box_size = data.len() as u64;
+        }
+
+        // The box may be shorter than the data, in which case the remaining
+        // data is not considered
+        if box_size < data.len() as u64 {
+            if box_size < reader.offset as u64 {
+                return Err(ParsingError(format!(
+                    "Invalid pssh box: its size field ({}) is smaller than its header",
+                    box_size
+                )));
+            }
+            reader.data = &data[..box_size as usize];
+        }
+
+        let version_and_flags = reader.read_u32("version")?;
+        let version = (version_and_flags >> 24) as u8;
+        if version > 1 {
+            return Err(ParsingError(format!(
+                "Invalid pssh box: unsupported version {}",
+                version
+            )));
+        }
+        let system_id = reader.read_uuid("SystemID")?;
+        let mut key_ids = Vec::new();
+        if version == 1 {
+            let kid_count = reader.read_u32("KID count")?;
+            for _ in 0..kid_count {
+                key_ids.push(reader.read_uuid("KID")?);
+            }
+        }
+        let data_size = reader.read_u32("DataSize")? as usize;
+        let pssh_data = reader.read_bytes(data_size, "Data")?.to_vec();
+
+        let warning = if box_size != data.len() as u64 {
+            Some(PsshWarning::SizeMismatch {
+                box_size,
+                data_len: data.len(),
+            })
+        } else if reader.offset != reader.data.len() {
+            Some(PsshWarning::TrailingData {
+                len: reader.data.len() - reader.offset,
+            })
+        } else {
+            None
+        };
+        Ok((
+            PsshBox {
+                version,
+                system_id,
+                key_ids,
+                data: pssh_data,
+            },
+            warning,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_box(version: u8, kids: &[[u8; 16]], data: &[u8]) -> Vec<u8> {
+        let mut content = vec![version, 0, 0, 0];
+        content.extend([0xed; 16]);
+        if version == 1 {
+            content.extend((kids.len() as u32).to_be_bytes());
+            for kid in kids {
+                content.extend(kid);
+            }
+        }
+        content.extend((data.len() as u32).to_be_bytes());
+        content.extend(data);
+        let mut pssh = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+        pssh.extend(b"pssh");
+        pssh.extend(content);
+        pssh
+    }
+
+    #[test]
+    fn test_parse_v0_and_v1() {
+        let (pssh, warning) = PsshBox::parse(&build_box(0, &[], b"abc")).unwrap();
+        assert!(warning.is_none());
+        assert_eq!(pssh.version, 0);
+        assert_eq!(pssh.system_id, [0xed; 16]);
+        assert!(pssh.key_ids.is_empty());
+        assert_eq!(pssh.data, b"abc");
+
+        let (pssh, warning) = PsshBox::parse(&build_box(1, &[[1; 16], [2; 16]], b"")).unwrap();
+        assert!(warning.is_none());
+        assert_eq!(pssh.version, 1);
+        assert_eq!(pssh.key_ids, vec![[1; 16], [2; 16]]);
+        assert!(pssh.data.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let valid = build_box(1, &[[1; 16]], b"abcdef");
+
+        // Truncated in the KIDs, then in the data
+        assert!(PsshBox::parse(&valid[..40]).is_err());
+        assert!(PsshBox::parse(&valid[..valid.len() - 1]).is_err());
+
+        // Size field bigger than the data
+        let mut invalid = valid.clone();
+        invalid[3] += 1;
+        let (_, warning) = PsshBox::parse(&invalid).unwrap();
+        assert_eq!(
+            warning,
+            Some(PsshWarning::SizeMismatch {
+                box_size: valid.len() as u64 + 1,
+                data_len: valid.len()
+            })
+        );
+
+        // Data after the box
+        let mut invalid = valid.clone();
+        invalid.push(0);
+        assert!(PsshBox::parse(&invalid).unwrap().1.is_some());
+
+        // Data after the content of the box, included in its size
+        let mut invalid = valid.clone();
+        invalid.extend([0, 0]);
+        invalid[3] += 2;
+        let (pssh, warning) = PsshBox::parse(&invalid).unwrap();
+        assert_eq!(pssh.data, b"abcdef");
+        assert_eq!(warning, Some(PsshWarning::TrailingData { len: 2 }));
+
+        let mut invalid = valid;
+        invalid[4] = b'm';
+        assert!(PsshBox::parse(&invalid).is_err());
+    }
+}
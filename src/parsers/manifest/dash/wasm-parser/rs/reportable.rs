@@ -1,6 +1,14 @@
+use crate::accessibility::CaptionServices;
+use crate::codecs::Codec;
 use crate::events::AttributeName;
+use crate::hdr::HdrInformation;
 use crate::onAttribute;
+use crate::periods::ResolvedPeriod;
 use crate::processor::SegmentObject;
+use crate::pssh::PsshBox;
+use crate::segment_index::Segment;
+use crate::thumbnails::Thumbnail;
+use crate::utils::ByteRange;
 use core::mem;
 use std::borrow::Cow;
 
@@ -84,6 +92,29 @@ impl ReportableAttribute for (f64, f64) {
     }
 }
 
+// For byte ranges: their start and end as little endian f64, followed by an u8
+// set to `1` if the range is open-ended, in which case the end is meaningless.
+impl ReportableAttribute for ByteRange {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = [0u8; 17];
+        msg[0..8].copy_from_slice(&(self.start as f64).to_le_bytes());
+        if let Some(end) = self.end {
+            msg[8..16].copy_from_slice(&(end as f64).to_le_bytes());
+        } else {
+            msg[16] = 1;
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
 impl ReportableAttribute for &[SegmentObject] {
     #[inline(always)]
     fn report_as_attr(&self, attr_name: AttributeName) {
@@ -92,12 +123,198 @@ impl ReportableAttribute for &[SegmentObject] {
         // UNSAFE: We're using FFI, so we don't know how the pointer is used.
         // Hopefully, the JavaScript-side should clone that value synchronously.
         unsafe {
-            let len = self.len() * mem::size_of::<SegmentObject>();
+            let len = mem::size_of_val(*self);
             onAttribute(attr_name, self.as_ptr() as *const u8, len);
         }
     }
 }
 
+impl ReportableAttribute for &[Segment] {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        use crate::utils;
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = Vec::with_capacity(self.len() * 64);
+        for segment in self.iter() {
+            let url = segment.url.as_deref().unwrap_or("");
+            msg.extend((segment.number as f64).to_le_bytes());
+            msg.extend(segment.start.to_le_bytes());
+            msg.extend(segment.end.to_le_bytes());
+            msg.extend(utils::u32_to_u8_slice_be(url.len() as u32));
+            msg.extend(url.as_bytes());
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
+// For each thumbnail: the number of its segment, its start, its end and its
+// crop rectangle (x, y, width and height), each as a little endian f64.
+impl ReportableAttribute for &[Thumbnail] {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = Vec::with_capacity(self.len() * 56);
+        for thumbnail in self.iter() {
+            msg.extend((thumbnail.segment_number as f64).to_le_bytes());
+            msg.extend(thumbnail.start.to_le_bytes());
+            msg.extend(thumbnail.end.to_le_bytes());
+            for val in [thumbnail.x, thumbnail.y, thumbnail.width, thumbnail.height] {
+                msg.extend((val as f64).to_le_bytes());
+            }
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
+impl ReportableAttribute for &PsshBox {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        use crate::utils;
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = Vec::with_capacity(25 + self.key_ids.len() * 16 + self.data.len());
+        msg.push(self.version);
+        msg.extend(self.system_id);
+        msg.extend(utils::u32_to_u8_slice_be(self.key_ids.len() as u32));
+        for kid in self.key_ids.iter() {
+            msg.extend(kid);
+        }
+        msg.extend(utils::u32_to_u8_slice_be(self.data.len() as u32));
+        msg.extend(&self.data);
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
+// For lists of strings, each prefixed by its length
+impl ReportableAttribute for &[String] {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        use crate::utils;
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let total_len: usize = self.iter().map(|s| s.len() + 4).sum();
+        let mut msg = Vec::with_capacity(total_len);
+        for val in self.iter() {
+            msg.extend(utils::u32_to_u8_slice_be(val.len() as u32));
+            msg.extend(val.as_bytes());
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
+// For parsed codecs, for each of them: its codec string prefixed by its
+// length, its family as an u8, then each of its optional fields as a big
+// endian u32, set to `u32::MAX` when absent.
+impl ReportableAttribute for &[Codec] {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        use crate::utils;
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = Vec::with_capacity(self.iter().map(|c| c.codec.len() + 49).sum());
+        for codec in self.iter() {
+            msg.extend(utils::u32_to_u8_slice_be(codec.codec.len() as u32));
+            msg.extend(codec.codec.as_bytes());
+            msg.push(codec.family as u8);
+            let fields = [
+                codec.profile,
+                codec.level,
+                codec.tier.map(|t| t as u32),
+                codec.bit_depth,
+                codec.chroma_subsampling.map(|c| c as u32),
+                codec.color_primaries,
+                codec.transfer_characteristics,
+                codec.matrix_coefficients,
+                codec.full_range.map(u32::from),
+                codec.object_type_indication,
+                codec.audio_object_type,
+            ];
+            for field in fields {
+                msg.extend(utils::u32_to_u8_slice_be(field.unwrap_or(u32::MAX)));
+            }
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
+// For the caption channels of an `<Accessibility>` element: the caption
+// standard as an u8, then for each channel its number as an u8 (`0` if
+// unknown), an u8 of flags (`1` for easy reader, `2` for wide aspect ratio)
+// and its language prefixed by its length as a big endian u32.
+impl ReportableAttribute for &CaptionServices {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        use crate::utils;
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = vec![self.standard as u8];
+        for service in &self.services {
+            let language = service.language.as_deref().unwrap_or_default();
+            let flags = service.easy_reader as u8 | (service.wide_aspect_ratio as u8) << 1;
+            msg.push(service.channel.unwrap_or(0));
+            msg.push(flags);
+            msg.extend(utils::u32_to_u8_slice_be(language.len() as u32));
+            msg.extend(language.as_bytes());
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
+// For the dynamic range of a Representation: its format, color space, bit
+// depth and transfer characteristics, each as an u8 set to `255` if unknown.
+impl ReportableAttribute for HdrInformation {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let to_u8 = |val: Option<u32>| val.map_or(u8::MAX, |v| v.min(u8::MAX as u32) as u8);
+        let msg = [
+            self.dynamic_range as u8,
+            self.color_space.map_or(u8::MAX, |c| c as u8),
+            to_u8(self.bit_depth),
+            to_u8(self.transfer_characteristics),
+        ];
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
+
 // For key-value couples (such as XML namespaces)
 impl<'a> ReportableAttribute for (&'a [u8], Cow<'a, str>) {
     #[inline(always)]
@@ -145,3 +362,25 @@ impl<'a> ReportableAttribute for Cow<'a, str> {
         };
     }
 }
+
+// For each Period: its start, its end (NaN if unknown) and `1` if it has been
+// removed or else `0`, each as a little endian f64.
+impl ReportableAttribute for &[ResolvedPeriod] {
+    #[inline(always)]
+    fn report_as_attr(&self, attr_name: AttributeName) {
+        debug_assert!(attr_name as u64 <= u8::MAX as u64);
+
+        let mut msg = Vec::with_capacity(self.len() * 24);
+        for period in self.iter() {
+            msg.extend(period.start.to_le_bytes());
+            msg.extend(period.end.unwrap_or(f64::NAN).to_le_bytes());
+            msg.extend((period.removed as u8 as f64).to_le_bytes());
+        }
+
+        // UNSAFE: We're using FFI, so we don't know how the pointer is used.
+        // Hopefully, the JavaScript-side should clone that value synchronously.
+        unsafe {
+            onAttribute(attr_name, msg.as_ptr(), msg.len());
+        };
+    }
+}
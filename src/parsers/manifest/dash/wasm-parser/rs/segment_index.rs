@@ -0,0 +1,432 @@
+use crate::errors::{ParsingError, Result};
+use crate::processor::SegmentObject;
+use crate::url_template::{TemplateValues, UrlTemplate};
+use crate::utils;
+
+/// Attributes of a `<SegmentTemplate>` element, as well as its optional
+/// `<SegmentTimeline>`, needed to compute the list of its segments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentTemplate {
+    /// The `media` attribute, template for the URL of media segments.
+    pub media: Option<UrlTemplate>,
+
+    /// The `initialization` attribute, template for the URL of the
+    /// initialization segment.
+    pub initialization: Option<UrlTemplate>,
+
+    /// The `startNumber` attribute, number of the first segment.
+    /// Defaults to `1`.
+    pub start_number: Option<u64>,
+
+    /// The `endNumber` attribute, number of the last segment.
+    pub end_number: Option<u64>,
+
+    /// The `timescale` attribute, in units per second. Defaults to `1`.
+    pub timescale: Option<u64>,
+
+    /// The `presentationTimeOffset` attribute, in the timescale.
+    /// Defaults to `0`.
+    pub presentation_time_offset: Option<f64>,
+
+    /// The `duration` attribute, duration of each segment in the timescale
+    /// when no `<SegmentTimeline>` is present.
+    pub duration: Option<u64>,
+
+    /// Entries of the `<SegmentTimeline>` element, if one.
+    pub timeline: Option<Vec<SegmentObject>>,
+}
+
+/// The `media` and `initialization` URL templates of a `<SegmentTemplate>`
+/// element, compiled when its attributes are reported.
+#[derive(Debug, Clone, Default)]
+pub struct SegmentTemplateUrls {
+    pub media: Option<UrlTemplate>,
+    pub initialization: Option<UrlTemplate>,
+}
+
+/// Information on the Representation a `SegmentTemplate` applies to.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RepresentationContext<'a> {
+    /// The `id` attribute of the Representation, used for the
+    /// `$RepresentationID$` identifier.
+    pub id: &'a str,
+
+    /// The `bandwidth` attribute of the Representation, used for the
+    /// `$Bandwidth$` identifier.
+    pub bandwidth: Option<u64>,
+
+    /// Duration of the parent Period in seconds, if known.
+    pub period_duration: Option<f64>,
+}
+
+/// A media segment, as computed from a `SegmentTemplate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// Number of that segment, which is the value of the `$Number$` identifier.
+    pub number: u64,
+
+    /// Media time at which that segment starts, in the timescale, which is the
+    /// value of the `$Time$` identifier.
+    pub time: u64,
+
+    /// Start of the segment in seconds, relative to the start of its Period.
+    pub start: f64,
+
+    /// End of the segment in seconds, relative to the start of its Period.
+    pub end: f64,
+
+    /// URL of the segment, with all identifiers replaced.
+    /// `None` if the `SegmentTemplate` has no `media` attribute.
+    pub url: Option<String>,
+}
+
+impl SegmentTemplate {
+    /// Creates a new `SegmentTemplate` from the attributes encountered in a
+    /// `<SegmentTemplate>` element in the MPD.
+    ///
+    /// Its `timeline` has to be set separately, once the `<SegmentTimeline>`
+    /// element has been parsed.
+    ///
+    /// Its URL templates are the ones compiled when its attributes were
+    /// reported, invalid ones being already signaled at that time.
+    pub fn from_element(
+        e: &quick_xml::events::BytesStart,
+        urls: SegmentTemplateUrls,
+    ) -> Result<SegmentTemplate> {
+        let mut template = SegmentTemplate {
+            media: urls.media,
+            initialization: urls.initialization,
+            ..SegmentTemplate::default()
+        };
+        for res_attr in e.attributes() {
+            let attr = res_attr?;
+            match attr.key.as_ref() {
+                b"startNumber" => template.start_number = Some(utils::parse_u64(&attr.value)?),
+                b"endNumber" => template.end_number = Some(utils::parse_u64(&attr.value)?),
+                b"timescale" => template.timescale = Some(utils::parse_u64(&attr.value)?),
+                b"presentationTimeOffset" => {
+                    template.presentation_time_offset = Some(utils::parse_f64(&attr.value)?)
+                }
+                b"duration" => template.duration = Some(utils::parse_u64(&attr.value)?),
+                _ => {}
+            }
+        }
+        Ok(template)
+    }
+
+    /// Combines this `SegmentTemplate` with the one of a parent element,
+    /// attribute by attribute: those not defined here are taken from the
+    /// `parent`, as is its `<SegmentTimeline>` if this one has none.
+    pub fn inherit(&self, parent: &SegmentTemplate) -> SegmentTemplate {
+        SegmentTemplate {
+            media: self.media.clone().or_else(|| parent.media.clone()),
+            initialization: self
+                .initialization
+                .clone()
+                .or_else(|| parent.initialization.clone()),
+            start_number: self.start_number.or(parent.start_number),
+            end_number: self.end_number.or(parent.end_number),
+            timescale: self.timescale.or(parent.timescale),
+            presentation_time_offset: self
+                .presentation_time_offset
+                .or(parent.presentation_time_offset),
+            duration: self.duration.or(parent.duration),
+            timeline: self.timeline.clone().or_else(|| parent.timeline.clone()),
+        }
+    }
+
+    /// Returns `true` if its segments cannot be computed without knowing the
+    /// duration of the Period: when they are described by a `duration`
+    /// attribute, or when the last entry of its `<SegmentTimeline>` is
+    /// repeated until the end of the Period.
+    pub fn depends_on_period_end(&self) -> bool {
+        match &self.timeline {
+            Some(timeline) => timeline.last().is_some_and(|last| last.repeat_count < 0.),
+            None => self.duration.is_some(),
+        }
+    }
+
+    /// Returns the URL of the initialization segment, with all identifiers
+    /// replaced, or `None` if the `SegmentTemplate` has no `initialization`
+    /// attribute.
+    pub fn get_init_segment_url(&self, ctx: &RepresentationContext) -> Option<String> {
+        self.initialization
+            .as_ref()
+            .map(|init| init.expand(&ctx.template_values(None, None)))
+    }
+
+    /// Returns the segments which overlap with the time range going from
+    /// `from` (included) to `to` (not included).
+    ///
+    /// Both are in seconds, relative to the start of the Period.
+    ///
+    /// Returns an error when the list cannot be computed, for example because
+    /// neither a `duration` attribute nor a `<SegmentTimeline>` are present.
+    pub fn get_segments(
+        &self,
+        ctx: &RepresentationContext,
+        from: f64,
+        to: f64,
+    ) -> Result<Vec<Segment>> {
+        let timescale = self.timescale.unwrap_or(1);
+        if timescale == 0 {
+            return Err(ParsingError(
+                "Invalid SegmentTemplate: timescale set to `0`".to_owned(),
+            ));
+        }
+        match (&self.timeline, self.duration) {
+            (Some(timeline), _) => {
+                self.get_segments_from_timeline(timeline, timescale as f64, ctx, from, to)
+            }
+            (None, Some(duration)) => {
+                self.get_segments_from_duration(duration, timescale as f64, ctx, from, to)
+            }
+            (None, None) => Err(ParsingError(
+                "Invalid SegmentTemplate: neither a duration nor a SegmentTimeline".to_owned(),
+            )),
+        }
+    }
+
+    fn get_segments_from_timeline(
+        &self,
+        timeline: &[SegmentObject],
+        timescale: f64,
+        ctx: &RepresentationContext,
+        from: f64,
+        to: f64,
+    ) -> Result<Vec<Segment>> {
+        let pto = self.presentation_time_offset.unwrap_or(0.);
+        let scaled_from = from * timescale + pto;
+        let scaled_to = to * timescale + pto;
+        let scaled_period_end = ctx.period_duration.map(|d| d * timescale + pto);
+        let mut number = self.start_number.unwrap_or(1);
+        let mut segments = Vec::new();
+
+        for (i, entry) in timeline.iter().enumerate() {
+            if entry.duration <= 0. {
+                continue;
+            }
+            let repeat_count = if entry.repeat_count >= 0. {
+                entry.repeat_count
+            } else {
+                // Repeated until the next entry or the end of the Period
+                let end = match (timeline.get(i + 1), scaled_period_end) {
+                    (Some(next), _) => next.start,
+                    (None, Some(period_end)) => period_end,
+                    (None, None) => scaled_to,
+                };
+                if end.is_infinite() {
+                    return Err(ParsingError(
+                        "Cannot compute SegmentTemplate segments: unbounded time range".to_owned(),
+                    ));
+                }
+                ((end - entry.start) / entry.duration).ceil() - 1.
+            };
+
+            // Skip directly to the first wanted segment in that entry
+            let first_idx = if scaled_from > entry.start {
+                ((scaled_from - entry.start) / entry.duration).floor()
+            } else {
+                0.
+            };
+            let mut idx = first_idx;
+            while idx <= repeat_count {
+                let seg_number = number + idx as u64;
+                if self.end_number.is_some_and(|end_nb| seg_number > end_nb) {
+                    return Ok(segments);
+                }
+                let seg_time = entry.start + idx * entry.duration;
+                if seg_time >= scaled_to {
+                    return Ok(segments);
+                }
+                let seg_end = seg_time + entry.duration;
+                if seg_end > scaled_from {
+                    let time = seg_time as u64;
+                    segments.push(Segment {
+                        number: seg_number,
+                        time,
+                        start: (seg_time - pto) / timescale,
+                        end: (seg_end - pto) / timescale,
+                        url: self.media.as_ref().map(|media| {
+                            media.expand(&ctx.template_values(Some(seg_number), Some(time)))
+                        }),
+                    });
+                }
+                idx += 1.;
+            }
+            number += repeat_count.max(0.) as u64 + 1;
+        }
+        Ok(segments)
+    }
+
+    fn get_segments_from_duration(
+        &self,
+        duration: u64,
+        timescale: f64,
+        ctx: &RepresentationContext,
+        from: f64,
+        to: f64,
+    ) -> Result<Vec<Segment>> {
+        if duration == 0 {
+            return Err(ParsingError(
+                "Invalid SegmentTemplate: duration set to `0`".to_owned(),
+            ));
+        }
+        let pto = self.presentation_time_offset.unwrap_or(0.);
+        let seg_duration = duration as f64 / timescale;
+        let start_number = self.start_number.unwrap_or(1);
+        let max_end = match ctx.period_duration {
+            Some(period_duration) => to.min(period_duration),
+            None => to,
+        };
+        if max_end.is_infinite() && self.end_number.is_none() {
+            return Err(ParsingError(
+                "Cannot compute SegmentTemplate segments: unbounded time range".to_owned(),
+            ));
+        }
+
+        let mut idx = if from > 0. {
+            (from / seg_duration).floor() as u64
+        } else {
+            0
+        };
+        let mut segments = Vec::new();
+        loop {
+            let number = start_number + idx;
+            if self.end_number.is_some_and(|end_nb| number > end_nb) {
+                break;
+            }
+            let start = idx as f64 * seg_duration;
+            if start >= max_end {
+                break;
+            }
+            let end = match ctx.period_duration {
+                Some(period_duration) => (start + seg_duration).min(period_duration),
+                None => start + seg_duration,
+            };
+            let time = (idx * duration) + pto as u64;
+            segments.push(Segment {
+                number,
+                time,
+                start,
+                end,
+                url: self
+                    .media
+                    .as_ref()
+                    .map(|media| media.expand(&ctx.template_values(Some(number), Some(time)))),
+            });
+            idx += 1;
+        }
+        Ok(segments)
+    }
+}
+
+impl RepresentationContext<'_> {
+    /// Returns the values replacing the identifiers of this Representation's
+    /// URL templates, for the segment with the given number and time.
+    fn template_values(&self, number: Option<u64>, time: Option<u64>) -> TemplateValues<'_> {
+        TemplateValues {
+            representation_id: Some(self.id),
+            number,
+            time,
+            bandwidth: self.bandwidth,
+            sub_number: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f64, duration: f64, repeat_count: f64) -> SegmentObject {
+        SegmentObject {
+            start,
+            duration,
+            repeat_count,
+        }
+    }
+
+    #[test]
+    fn test_get_segments_from_duration() {
+        let template = SegmentTemplate {
+            media: UrlTemplate::compile("$RepresentationID$/$Number$.m4s").ok(),
+            start_number: Some(5),
+            timescale: Some(1000),
+            duration: Some(4000),
+            ..Default::default()
+        };
+        let ctx = RepresentationContext {
+            id: "a",
+            bandwidth: None,
+            period_duration: Some(10.),
+        };
+        let segments = template.get_segments(&ctx, 3., 100.).unwrap();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].number, 5);
+        assert_eq!(segments[0].start, 0.);
+        assert_eq!(segments[0].end, 4.);
+        assert_eq!(segments[0].url.as_deref(), Some("a/5.m4s"));
+        assert_eq!(segments[2].number, 7);
+        assert_eq!(segments[2].time, 8000);
+        assert_eq!(segments[2].end, 10.);
+
+        let ctx = RepresentationContext {
+            period_duration: None,
+            ..ctx
+        };
+        assert!(template.get_segments(&ctx, 0., f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_get_segments_from_timeline() {
+        let template = SegmentTemplate {
+            media: UrlTemplate::compile("$Time$.m4s").ok(),
+            timescale: Some(10),
+            presentation_time_offset: Some(100.),
+            end_number: Some(6),
+            timeline: Some(vec![
+                seg(100., 20., 2.),
+                seg(160., 30., -1.),
+                seg(250., 20., 0.),
+            ]),
+            ..Default::default()
+        };
+        let ctx = RepresentationContext::default();
+        let segments = template.get_segments(&ctx, 3., 14.).unwrap();
+        let numbers: Vec<u64> = segments.iter().map(|s| s.number).collect();
+        assert_eq!(numbers, vec![2, 3, 4, 5, 6]);
+        assert_eq!(segments[0].start, 2.);
+        assert_eq!(segments[2].time, 160);
+        assert_eq!(segments[2].end, 9.);
+        assert_eq!(segments[4].url.as_deref(), Some("220.m4s"));
+
+        // `endNumber` excludes the last entry
+        let segments = template.get_segments(&ctx, 0., f64::INFINITY).unwrap();
+        assert_eq!(segments.len(), 6);
+        assert_eq!(segments[5].time, 220);
+    }
+
+    #[test]
+    fn test_inherit_segment_template() {
+        let parent = SegmentTemplate {
+            media: UrlTemplate::compile("$Number$.m4s").ok(),
+            timescale: Some(1000),
+            duration: Some(4000),
+            timeline: Some(vec![seg(0., 20., 0.)]),
+            ..Default::default()
+        };
+        let child = SegmentTemplate {
+            initialization: UrlTemplate::compile("init.mp4").ok(),
+            timescale: Some(90000),
+            ..Default::default()
+        };
+        let merged = child.inherit(&parent);
+        assert_eq!(merged.media, parent.media);
+        assert_eq!(merged.initialization, child.initialization);
+        assert_eq!(merged.timescale, Some(90000));
+        assert_eq!(merged.duration, Some(4000));
+        assert_eq!(merged.timeline, parent.timeline);
+        assert_eq!(SegmentTemplate::default().inherit(&merged), merged);
+    }
+}
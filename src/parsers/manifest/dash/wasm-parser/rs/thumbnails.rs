@@ -0,0 +1,173 @@
+use crate::errors::{ParsingError, Result};
+use crate::segment_index::Segment;
+
+/// `schemeIdUri` of the `<EssentialProperty>` describing the tiles of image
+/// segments, as defined by the DASH-IF IOP. The second one is an older form
+/// still found in the wild.
+pub const THUMBNAIL_TILE_SCHEMES: [&str; 2] = [
+    "http://dashif.org/guidelines/thumbnail_tile",
+    "http://dashif.org/thumbnail_tile",
+];
+
+/// Maximum number of thumbnails a tile grid can contain. Bigger grids are
+/// rejected, as each of their thumbnails would be reported for each segment.
+const MAX_TILE_COUNT: u32 = 10_000;
+
+/// Layout of the thumbnails in each image segment of a thumbnail track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileGrid {
+    pub columns: u32,
+    pub rows: u32,
+}
+
+/// A thumbnail, as a part of an image segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thumbnail {
+    /// Number of the segment containing it.
+    pub segment_number: u64,
+
+    /// Start of the time range it illustrates, in seconds, relative to the
+    /// start of its Period.
+    pub start: f64,
+
+    /// End of that time range, in seconds, relative to the start of its
+    /// Period.
+    pub end: f64,
+
+    /// Crop rectangle of the thumbnail in the segment's image, in pixels.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TileGrid {
+    /// Decodes an `<EssentialProperty>` element with the given scheme and
+    /// value, returning `None` if it does not describe a tile grid.
+    pub fn from_descriptor(scheme_id_uri: &str, value: Option<&str>) -> Option<Result<TileGrid>> {
+        if !THUMBNAIL_TILE_SCHEMES.contains(&scheme_id_uri.trim()) {
+            return None;
+        }
+        Some(match value {
+            Some(value) => TileGrid::parse(value),
+            None => Err(ParsingError(
+                "Thumbnail tile EssentialProperty without a value".to_owned(),
+            )),
+        })
+    }
+
+    /// Parse a grid written as its number of columns and rows separated by an
+    /// `x`, e.g. `10x5`.
+    ///
+    /// Grids of more than `MAX_TILE_COUNT` thumbnails are rejected.
+    pub fn parse(value: &str) -> Result<TileGrid> {
+        let invalid = || ParsingError(format!("Invalid thumbnail tile grid: \"{}\"", value));
+        let (columns, rows) = value.trim().split_once(['x', 'X']).ok_or_else(invalid)?;
+        let columns = columns.trim().parse::<u32>().map_err(|_| invalid())?;
+        let rows = rows.trim().parse::<u32>().map_err(|_| invalid())?;
+        if columns == 0 || rows == 0 {
+            return Err(invalid());
+        }
+        match columns.checked_mul(rows) {
+            Some(count) if count <= MAX_TILE_COUNT => Ok(TileGrid { columns, rows }),
+            _ => Err(ParsingError(format!(
+                "Thumbnail tile grid \"{}\" has more than {} tiles",
+                value, MAX_TILE_COUNT
+            ))),
+        }
+    }
+
+    /// Returns the thumbnails contained in the given image segment, in
+    /// display order, for images of the given size in pixels.
+    ///
+    /// Thumbnails are laid out row by row, each illustrating an equal part of
+    /// the segment's duration.
+    pub fn thumbnails(&self, segment: &Segment, width: u32, height: u32) -> Vec<Thumbnail> {
+        let count = self.columns * self.rows;
+        let tile_width = width / self.columns;
+        let tile_height = height / self.rows;
+        let tile_duration = (segment.end - segment.start) / count as f64;
+        (0..count)
+            .map(|i| Thumbnail {
+                segment_number: segment.number,
+                start: segment.start + tile_duration * i as f64,
+                end: if i + 1 == count {
+                    segment.end
+                } else {
+                    segment.start + tile_duration * (i + 1) as f64
+                },
+                x: (i % self.columns) * tile_width,
+                y: (i / self.columns) * tile_height,
+                width: tile_width,
+                height: tile_height,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        assert_eq!(
+            TileGrid::parse("10x5").unwrap(),
+            TileGrid {
+                columns: 10,
+                rows: 5
+            }
+        );
+        assert_eq!(
+            TileGrid::from_descriptor(THUMBNAIL_TILE_SCHEMES[1], Some(" 2X1 "))
+                .unwrap()
+                .unwrap(),
+            TileGrid {
+                columns: 2,
+                rows: 1
+            }
+        );
+        assert!(TileGrid::parse("10").is_err());
+        assert!(TileGrid::parse("0x5").is_err());
+        assert!(TileGrid::parse("100x100").is_ok());
+        assert!(TileGrid::parse("100x101").is_err());
+        assert!(TileGrid::parse("50000x50000").is_err());
+        assert!(TileGrid::parse("100000x100000").is_err());
+        assert!(TileGrid::from_descriptor(THUMBNAIL_TILE_SCHEMES[0], None)
+            .unwrap()
+            .is_err());
+        assert!(TileGrid::from_descriptor("urn:foo", Some("10x5")).is_none());
+    }
+
+    #[test]
+    fn test_thumbnails() {
+        let grid = TileGrid {
+            columns: 2,
+            rows: 2,
+        };
+        let segment = Segment {
+            number: 3,
+            time: 0,
+            start: 20.,
+            end: 30.,
+            url: None,
+        };
+        let thumbnails = grid.thumbnails(&segment, 640, 360);
+        assert_eq!(thumbnails.len(), 4);
+        assert_eq!(
+            thumbnails[3],
+            Thumbnail {
+                segment_number: 3,
+                start: 27.5,
+                end: 30.,
+                x: 320,
+                y: 180,
+                width: 320,
+                height: 180,
+            }
+        );
+        assert_eq!((thumbnails[1].x, thumbnails[1].y), (320, 0));
+        assert_eq!((thumbnails[2].x, thumbnails[2].y), (0, 180));
+        assert_eq!(thumbnails[0].end, 22.5);
+    }
+}
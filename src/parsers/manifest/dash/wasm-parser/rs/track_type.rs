@@ -0,0 +1,349 @@
+use crate::codecs::{Codec, CodecFamily};
+
+/// `schemeIdUri` of the `<Role>` and `<Accessibility>` descriptors defined by
+/// the DASH specification.
+pub const DASH_ROLE_SCHEME: &str = "urn:mpeg:dash:role:2011";
+
+/// Type of the media contained in an `<AdaptationSet>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum TrackType {
+    Audio = 0,
+    Video = 1,
+    Text = 2,
+    Image = 3,
+}
+
+impl TrackType {
+    fn name(self) -> &'static str {
+        match self {
+            TrackType::Audio => "audio",
+            TrackType::Video => "video",
+            TrackType::Text => "text",
+            TrackType::Image => "image",
+        }
+    }
+
+    /// Type announced by a `contentType` attribute, or by the top-level type
+    /// of a media type.
+    fn from_content_type(content_type: &str) -> Option<TrackType> {
+        match content_type.trim() {
+            "audio" => Some(TrackType::Audio),
+            "video" => Some(TrackType::Video),
+            "text" => Some(TrackType::Text),
+            "image" => Some(TrackType::Image),
+            _ => None,
+        }
+    }
+
+    /// Type announced by a `mimeType` attribute.
+    ///
+    /// `application/mp4` is only considered as text when a `subtitle` or
+    /// `caption` role is present, as DASH-IF recommends for subtitles
+    /// embedded in ISOBMFF segments.
+    fn from_mime_type(mime_type: &str, roles: &[Role]) -> Option<TrackType> {
+        let essence = mime_type.split(';').next().unwrap_or_default().trim();
+        let essence = essence.to_ascii_lowercase();
+        if let Some(track_type) = TrackType::from_content_type(essence.split('/').next()?) {
+            return Some(track_type);
+        }
+        match essence.as_str() {
+            "application/ttml+xml" => Some(TrackType::Text),
+            "application/mp4"
+                if roles
+                    .iter()
+                    .any(|r| matches!(r, Role::Subtitle | Role::Caption)) =>
+            {
+                Some(TrackType::Text)
+            }
+            _ => None,
+        }
+    }
+
+    /// Type of the media encoded with the given codec, if known.
+    fn from_codec(codec: &Codec) -> Option<TrackType> {
+        match codec.family {
+            family if family.is_video() => Some(TrackType::Video),
+            CodecFamily::Mp4a
+            | CodecFamily::Ac3
+            | CodecFamily::Ec3
+            | CodecFamily::Ac4
+            | CodecFamily::Opus
+            | CodecFamily::Flac => Some(TrackType::Audio),
+            CodecFamily::Stpp | CodecFamily::Wvtt => Some(TrackType::Text),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a `<Role>` or `<Accessibility>` descriptor with the
+/// `urn:mpeg:dash:role:2011` scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Role {
+    Main = 0,
+    Alternate = 1,
+    Supplementary = 2,
+    Commentary = 3,
+    Dub = 4,
+    Emergency = 5,
+    Caption = 6,
+    Subtitle = 7,
+    Sign = 8,
+    Description = 9,
+    EnhancedAudioIntelligibility = 10,
+    ForcedSubtitle = 11,
+    Easyreader = 12,
+    Karaoke = 13,
+    Metadata = 14,
+}
+
+impl Role {
+    /// Parse the value of a descriptor with the DASH role scheme, ignoring
+    /// its case and white spaces around it. `forced_subtitle`, seen in some
+    /// MPDs, is also understood.
+    ///
+    /// Returns `None` for unknown values.
+    pub fn parse(value: &str) -> Option<Role> {
+        let role = match value.trim().to_ascii_lowercase().as_str() {
+            "main" => Role::Main,
+            "alternate" => Role::Alternate,
+            "supplementary" => Role::Supplementary,
+            "commentary" => Role::Commentary,
+            "dub" => Role::Dub,
+            "emergency" => Role::Emergency,
+            "caption" => Role::Caption,
+            "subtitle" => Role::Subtitle,
+            "sign" => Role::Sign,
+            "description" => Role::Description,
+            "enhanced-audio-intelligibility" => Role::EnhancedAudioIntelligibility,
+            "forced-subtitle" | "forced_subtitle" => Role::ForcedSubtitle,
+            "easyreader" => Role::Easyreader,
+            "karaoke" => Role::Karaoke,
+            "metadata" => Role::Metadata,
+            _ => return None,
+        };
+        Some(role)
+    }
+}
+
+/// `mimeType` and `codecs` attributes of an `<AdaptationSet>` or of one of
+/// its `<Representation>` children.
+#[derive(Debug, Clone, Default)]
+pub struct ElementHints {
+    pub mime_type: Option<String>,
+    pub codecs: Vec<Codec>,
+}
+
+/// Information found in an `<AdaptationSet>` and its children from which the
+/// type of its media can be deduced.
+///
+/// The `contentType` attributes of `<ContentComponent>` elements are not part
+/// of it: they describe each component of a multiplexed `<AdaptationSet>`,
+/// which may then legitimately have different types.
+#[derive(Debug, Clone, Default)]
+pub struct TrackTypeHints {
+    /// `contentType` attribute of the `<AdaptationSet>`.
+    pub content_type: Option<String>,
+
+    /// Hints of the `<AdaptationSet>` then of its `<Representation>`
+    /// children, in document order.
+    pub elements: Vec<ElementHints>,
+
+    /// Roles it has, sorted and without duplicates.
+    pub roles: Vec<Role>,
+}
+
+/// Type inferred for an `<AdaptationSet>`, with the hints which contradict
+/// that type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackTypeInference {
+    /// `None` if no hint allowed to deduce it.
+    pub track_type: Option<TrackType>,
+
+    /// Description of each hint announcing another type.
+    pub contradictions: Vec<String>,
+}
+
+impl TrackTypeHints {
+    /// Add a role to the set of roles, if not already present.
+    pub fn add_role(&mut self, role: Role) {
+        if let Err(pos) = self.roles.binary_search(&role) {
+            self.roles.insert(pos, role);
+        }
+    }
+
+    /// Infer the type of the `<AdaptationSet>` in the same order than the
+    /// DASH-IF IOP recommends (and than the JS parser does): for the
+    /// `<AdaptationSet>` then for each `<Representation>`, its `mimeType`
+    /// (taking roles into account) and then its codecs.
+    ///
+    /// The `contentType` attribute is only relied on if none of those allowed
+    /// to deduce it.
+    pub fn infer(&self) -> TrackTypeInference {
+        let mut hints: Vec<(TrackType, String)> = Vec::new();
+        for element in &self.elements {
+            if let Some(mime_type) = element.mime_type.as_ref() {
+                if let Some(track_type) = TrackType::from_mime_type(mime_type, &self.roles) {
+                    hints.push((track_type, format!("mimeType \"{}\"", mime_type)));
+                }
+            }
+            for codec in &element.codecs {
+                if let Some(track_type) = TrackType::from_codec(codec) {
+                    hints.push((track_type, format!("codec \"{}\"", codec.codec)));
+                }
+            }
+        }
+        if let Some(content_type) = self.content_type.as_ref() {
+            if let Some(track_type) = TrackType::from_content_type(content_type) {
+                hints.push((track_type, format!("contentType \"{}\"", content_type)));
+            }
+        }
+
+        let (track_type, source) = match hints.first() {
+            Some((track_type, source)) => (*track_type, source),
+            None => {
+                return TrackTypeInference {
+                    track_type: None,
+                    contradictions: Vec::new(),
+                }
+            }
+        };
+        let contradictions = hints
+            .iter()
+            .filter(|(other_type, _)| *other_type != track_type)
+            .map(|(other_type, other_source)| {
+                format!(
+                    "{} announces {} content but {} announces {} content",
+                    source,
+                    track_type.name(),
+                    other_source,
+                    other_type.name()
+                )
+            })
+            .collect();
+        TrackTypeInference {
+            track_type: Some(track_type),
+            contradictions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs;
+
+    fn hints(content_type: &str, mime_type: &str, codecs: &str) -> TrackTypeHints {
+        let non_empty = |val: &str| {
+            if val.is_empty() {
+                None
+            } else {
+                Some(val.to_owned())
+            }
+        };
+        TrackTypeHints {
+            content_type: non_empty(content_type),
+            elements: vec![ElementHints {
+                mime_type: non_empty(mime_type),
+                codecs: codecs::parse_codecs(codecs).0,
+            }],
+            roles: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(Role::parse(" Main "), Some(Role::Main));
+        assert_eq!(Role::parse("forced_subtitle"), Some(Role::ForcedSubtitle));
+        assert_eq!(
+            Role::parse("enhanced-audio-intelligibility"),
+            Some(Role::EnhancedAudioIntelligibility)
+        );
+        assert_eq!(Role::parse("foo"), None);
+
+        let mut hints = TrackTypeHints::default();
+        hints.add_role(Role::Subtitle);
+        hints.add_role(Role::Main);
+        hints.add_role(Role::Subtitle);
+        assert_eq!(hints.roles, vec![Role::Main, Role::Subtitle]);
+    }
+
+    #[test]
+    fn test_infer_track_type() {
+        let inferred = hints("", "video/mp4", "avc1.64001f").infer();
+        assert_eq!(inferred.track_type, Some(TrackType::Video));
+        assert!(inferred.contradictions.is_empty());
+
+        assert_eq!(
+            hints("", "", "ec-3").infer().track_type,
+            Some(TrackType::Audio)
+        );
+        assert_eq!(
+            hints("", "image/jpeg", "").infer().track_type,
+            Some(TrackType::Image)
+        );
+        assert_eq!(
+            hints("", "application/ttml+xml", "").infer().track_type,
+            Some(TrackType::Text)
+        );
+        assert_eq!(hints("", "application/mp4", "").infer().track_type, None);
+        assert_eq!(
+            hints("", "application/mp4", "stpp.ttml.im1t")
+                .infer()
+                .track_type,
+            Some(TrackType::Text)
+        );
+
+        let mut subtitles = hints("", "application/mp4", "");
+        subtitles.add_role(Role::Subtitle);
+        assert_eq!(subtitles.infer().track_type, Some(TrackType::Text));
+    }
+
+    #[test]
+    fn test_contradictions() {
+        let inferred = hints("audio", "audio/mp4", "avc1.64001f").infer();
+        assert_eq!(inferred.track_type, Some(TrackType::Audio));
+        assert_eq!(
+            inferred.contradictions,
+            vec![
+                "mimeType \"audio/mp4\" announces audio content but codec \"avc1.64001f\" \
+                 announces video content"
+            ]
+        );
+        assert_eq!(
+            hints("text", "video/mp4", "").infer().contradictions,
+            vec![
+                "mimeType \"video/mp4\" announces video content but contentType \"text\" \
+                 announces text content"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inference_order() {
+        // The mimeType prevails over the contentType, which is only a fallback
+        assert_eq!(
+            hints("audio", "video/mp4", "").infer().track_type,
+            Some(TrackType::Video)
+        );
+        assert_eq!(
+            hints("audio", "application/mp4", "").infer().track_type,
+            Some(TrackType::Audio)
+        );
+
+        // The roles are considered before the codecs
+        let mut subtitles = hints("", "application/mp4", "avc1.64001f");
+        subtitles.add_role(Role::Caption);
+        assert_eq!(subtitles.infer().track_type, Some(TrackType::Text));
+
+        // The AdaptationSet's codecs are considered before the Representations'
+        // mimeType
+        let mut adaptation = hints("", "", "mp4a.40.2");
+        adaptation.elements.push(ElementHints {
+            mime_type: Some("video/mp4".to_owned()),
+            codecs: Vec::new(),
+        });
+        assert_eq!(adaptation.infer().track_type, Some(TrackType::Audio));
+    }
+}
@@ -0,0 +1,274 @@
+/// Components of a URI reference, as defined by RFC 3986.
+///
+/// Each optional component is `None` when its delimiter is absent, which is
+/// different from it being present but empty (e.g. `http://a/b?` has an empty
+/// query).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UriParts<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UriParts<'a> {
+    /// Split a URI reference into its components, following the regular
+    /// expression given in Appendix B of RFC 3986.
+    fn parse(uri: &'a str) -> UriParts<'a> {
+        let (rest, fragment) = match uri.find('#') {
+            Some(idx) => (&uri[..idx], Some(&uri[idx + 1..])),
+            None => (uri, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.find(':') {
+            Some(idx) if is_valid_scheme(&rest[..idx]) => (Some(&rest[..idx]), &rest[idx + 1..]),
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(after_slashes) => {
+                let end = after_slashes.find('/').unwrap_or(after_slashes.len());
+                (Some(&after_slashes[..end]), &after_slashes[end..])
+            }
+            None => (None, rest),
+        };
+        UriParts {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Returns `true` if `scheme` is a syntactically valid URI scheme: a letter
+/// followed by any number of letters, digits, `+`, `-` or `.`.
+fn is_valid_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if `uri` is an absolute URI, i.e. if it has a scheme.
+pub fn is_absolute(uri: &str) -> bool {
+    UriParts::parse(uri).scheme.is_some()
+}
+
+/// Resolve the URI reference `reference` against the base URI `base`, as
+/// defined in section 5.2 of RFC 3986.
+///
+/// `base` may itself be relative, in which case the result is relative too.
+pub fn resolve(base: &str, reference: &str) -> String {
+    let base = UriParts::parse(base);
+    let reference = UriParts::parse(reference);
+
+    let mut merged_path = String::new();
+    let (scheme, authority, path, query) = if reference.scheme.is_some() {
+        (
+            reference.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.authority.is_some() {
+        (
+            base.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.path.is_empty() {
+        (
+            base.scheme,
+            base.authority,
+            base.path.to_owned(),
+            reference.query.or(base.query),
+        )
+    } else if reference.path.starts_with('/') {
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else {
+        if base.authority.is_some() && base.path.is_empty() {
+            merged_path.push('/');
+        } else if let Some(last_slash) = base.path.rfind('/') {
+            merged_path.push_str(&base.path[..=last_slash]);
+        }
+        merged_path.push_str(reference.path);
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(&merged_path),
+            reference.query,
+        )
+    };
+
+    let mut result = String::with_capacity(path.len() + 32);
+    if let Some(scheme) = scheme {
+        result.push_str(scheme);
+        result.push(':');
+    }
+    if let Some(authority) = authority {
+        result.push_str("//");
+        result.push_str(authority);
+    }
+    result.push_str(&path);
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
+
+/// Interpret and remove the `.` and `..` segments of a path, as defined in
+/// section 5.2.4 of RFC 3986.
+fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::new();
+    let mut input = path;
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../") {
+            input = rest;
+        } else if let Some(rest) = input.strip_prefix("./") {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input.len() == 3 { "/" } else { &input[3..] };
+            output.pop();
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            // Move the first path segment, with its initial `/` if one, to
+            // the output
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..]
+                .find('/')
+                .map(|idx| idx + start)
+                .unwrap_or(input.len());
+            output.push(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_rfc_3986_normal_examples() {
+        let base = "http://a/b/c/d;p?q";
+        let examples = [
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../", "http://a/"),
+            ("../../g", "http://a/g"),
+        ];
+        for (reference, expected) in examples {
+            assert_eq!(
+                resolve(base, reference),
+                expected,
+                "reference: {}",
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_rfc_3986_abnormal_examples() {
+        let base = "http://a/b/c/d;p?q";
+        let examples = [
+            ("../../../g", "http://a/g"),
+            ("../../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"),
+            ("g#s/../x", "http://a/b/c/g#s/../x"),
+        ];
+        for (reference, expected) in examples {
+            assert_eq!(
+                resolve(base, reference),
+                expected,
+                "reference: {}",
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_absolute() {
+        assert!(is_absolute("https://cdn.example.com/live/"));
+        assert!(is_absolute("urn:mpeg:dash:profile"));
+        assert!(!is_absolute("//cdn.example.com/live/"));
+        assert!(!is_absolute("/live/manifest.mpd"));
+        assert!(!is_absolute("../seg/"));
+        assert!(!is_absolute("1a:b/"));
+    }
+
+    #[test]
+    fn test_resolve_deep_relative_paths() {
+        assert_eq!(
+            resolve("https://cdn.example.com/live/", "../../vod/a/./b/../seg/"),
+            "https://cdn.example.com/vod/a/seg/"
+        );
+        assert_eq!(
+            resolve("https://example.com", "a/b"),
+            "https://example.com/a/b"
+        );
+        assert_eq!(resolve("dash/", "video/"), "dash/video/");
+        assert_eq!(resolve("", "video/"), "video/");
+        assert_eq!(
+            resolve("https://example.com/a/", "/b:c"),
+            "https://example.com/b:c"
+        );
+        assert_eq!(
+            resolve("https://example.com/a/", "1http://b/"),
+            "https://example.com/a/1http://b/"
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::errors::{ParsingError, Result};
+use std::fmt::Write;
+
+/// Element of a compiled `UrlTemplate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateToken {
+    /// Part of the template which is kept as is. `$$` escapes are already
+    /// converted to a single `$` in it.
+    Literal(String),
+
+    /// The `$RepresentationID$` identifier.
+    RepresentationId,
+
+    /// The `$Number$` identifier, with its minimum width (`1` if no format
+    /// tag was set).
+    Number(usize),
+
+    /// The `$Time$` identifier, with its minimum width.
+    Time(usize),
+
+    /// The `$Bandwidth$` identifier, with its minimum width.
+    Bandwidth(usize),
+
+    /// The `$SubNumber$` identifier, with its minimum width.
+    SubNumber(usize),
+}
+
+/// Values replacing the identifiers of a `UrlTemplate`.
+///
+/// Identifiers whose value is `None` are kept as is in the expanded URL.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TemplateValues<'a> {
+    pub representation_id: Option<&'a str>,
+    pub number: Option<u64>,
+    pub time: Option<u64>,
+    pub bandwidth: Option<u64>,
+    pub sub_number: Option<u64>,
+}
+
+/// A `SegmentTemplate@media`, `@initialization` or `@index` URL template,
+/// parsed once into a list of tokens so it can be quickly expanded for each
+/// segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlTemplate {
+    tokens: Vec<TemplateToken>,
+}
+
+impl UrlTemplate {
+    /// Parse and validate the given URL template.
+    ///
+    /// Returns an error if it contains an unknown identifier, an unterminated
+    /// identifier, an invalid format tag or both the `$Time$` and the
+    /// `$Number$` identifiers.
+    pub fn compile(template: &str) -> Result<UrlTemplate> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('$') {
+            literal.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let end = after.find('$').ok_or_else(|| {
+                ParsingError(format!(
+                    "Invalid URL template \"{}\": unterminated identifier",
+                    template
+                ))
+            })?;
+            let identifier = &after[..end];
+            rest = &after[end + 1..];
+            if identifier.is_empty() {
+                literal.push('$');
+                continue;
+            }
+            let (name, width) = match identifier.find('%') {
+                Some(fmt_start) => {
+                    let width = parse_format_tag(&identifier[fmt_start..]).ok_or_else(|| {
+                        ParsingError(format!(
+                            "Invalid URL template \"{}\": invalid format tag in ${}$",
+                            template, identifier
+                        ))
+                    })?;
+                    (&identifier[..fmt_start], Some(width))
+                }
+                None => (identifier, None),
+            };
+            let token = match name {
+                "RepresentationID" if width.is_none() => TemplateToken::RepresentationId,
+                "RepresentationID" => {
+                    return Err(ParsingError(format!(
+                        "Invalid URL template \"{}\": $RepresentationID$ cannot have a format tag",
+                        template
+                    )))
+                }
+                "Number" => TemplateToken::Number(width.unwrap_or(1)),
+                "Time" => TemplateToken::Time(width.unwrap_or(1)),
+                "Bandwidth" => TemplateToken::Bandwidth(width.unwrap_or(1)),
+                "SubNumber" => TemplateToken::SubNumber(width.unwrap_or(1)),
+                _ => {
+                    return Err(ParsingError(format!(
+                        "Invalid URL template \"{}\": unknown identifier ${}$",
+                        template, identifier
+                    )))
+                }
+            };
+            if !literal.is_empty() {
+                tokens.push(TemplateToken::Literal(std::mem::take(&mut literal)));
+            }
+            tokens.push(token);
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            tokens.push(TemplateToken::Literal(literal));
+        }
+
+        let compiled = UrlTemplate { tokens };
+        if compiled.uses_number() && compiled.uses_time() {
+            return Err(ParsingError(format!(
+                "Invalid URL template \"{}\": $Number$ and $Time$ cannot be used together",
+                template
+            )));
+        }
+        Ok(compiled)
+    }
+
+    /// Returns `true` if the template contains the `$Number$` identifier.
+    pub fn uses_number(&self) -> bool {
+        self.tokens
+            .iter()
+            .any(|t| matches!(t, TemplateToken::Number(_)))
+    }
+
+    /// Returns `true` if the template contains the `$Time$` identifier.
+    pub fn uses_time(&self) -> bool {
+        self.tokens
+            .iter()
+            .any(|t| matches!(t, TemplateToken::Time(_)))
+    }
+
+    /// Returns the URL obtained by replacing each identifier of the template by
+    /// its value.
+    pub fn expand(&self, values: &TemplateValues) -> String {
+        let mut result = String::new();
+        for token in self.tokens.iter() {
+            match *token {
+                TemplateToken::Literal(ref literal) => result.push_str(literal),
+                TemplateToken::RepresentationId => {
+                    result.push_str(values.representation_id.unwrap_or("$RepresentationID$"))
+                }
+                TemplateToken::Number(width) => {
+                    write_identifier(&mut result, "Number", values.number, width)
+                }
+                TemplateToken::Time(width) => {
+                    write_identifier(&mut result, "Time", values.time, width)
+                }
+                TemplateToken::Bandwidth(width) => {
+                    write_identifier(&mut result, "Bandwidth", values.bandwidth, width)
+                }
+                TemplateToken::SubNumber(width) => {
+                    write_identifier(&mut result, "SubNumber", values.sub_number, width)
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Write the value of a numeric identifier padded with zeros to `width`, or
+/// the identifier itself if its value is unknown.
+fn write_identifier(result: &mut String, name: &str, value: Option<u64>, width: usize) {
+    // Writing into a `String` cannot fail
+    let _ = match value {
+        Some(value) => write!(result, "{:0width$}", value, width = width),
+        None if width == 1 => write!(result, "${}$", name),
+        None => write!(result, "${}%0{}d$", name, width),
+    };
+}
+
+/// Parse a `%0[width]d` format tag, returning its width.
+fn parse_format_tag(tag: &str) -> Option<usize> {
+    let width = tag.strip_prefix("%0")?.strip_suffix('d')?;
+    if width.is_empty() || !width.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    width.parse::<usize>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile() {
+        let template = UrlTemplate::compile("a$$b/$RepresentationID$/$Number%05d$.m4s").unwrap();
+        assert_eq!(
+            template.tokens,
+            vec![
+                TemplateToken::Literal("a$b/".to_owned()),
+                TemplateToken::RepresentationId,
+                TemplateToken::Literal("/".to_owned()),
+                TemplateToken::Number(5),
+                TemplateToken::Literal(".m4s".to_owned()),
+            ]
+        );
+        assert!(template.uses_number());
+        assert!(!template.uses_time());
+
+        let template = UrlTemplate::compile("no-identifier.mp4").unwrap();
+        assert_eq!(
+            template.tokens,
+            vec![TemplateToken::Literal("no-identifier.mp4".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert!(UrlTemplate::compile("$Unknown$.m4s").is_err());
+        assert!(UrlTemplate::compile("$Number.m4s").is_err());
+        assert!(UrlTemplate::compile("$Number%5d$.m4s").is_err());
+        assert!(UrlTemplate::compile("$Number%05x$.m4s").is_err());
+        assert!(UrlTemplate::compile("$Number%0d$.m4s").is_err());
+        assert!(UrlTemplate::compile("$RepresentationID%02d$.m4s").is_err());
+        assert!(UrlTemplate::compile("$Time$-$Number$.m4s").is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let template =
+            UrlTemplate::compile("$RepresentationID$/$Bandwidth$/$Time%08d$-$SubNumber$$$")
+                .unwrap();
+        let values = TemplateValues {
+            representation_id: Some("video-1"),
+            time: Some(9000),
+            bandwidth: Some(500000),
+            sub_number: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(template.expand(&values), "video-1/500000/00009000-3$");
+        assert_eq!(
+            UrlTemplate::compile("$Number%03d$")
+                .unwrap()
+                .expand(&TemplateValues {
+                    number: Some(12345),
+                    ..Default::default()
+                }),
+            "12345"
+        );
+        assert_eq!(
+            template.expand(&TemplateValues::default()),
+            "$RepresentationID$/$Bandwidth$/$Time%08d$-$SubNumber$$"
+        );
+    }
+}
@@ -60,151 +60,576 @@ pub fn parse_bool(value: &[u8]) -> Result<bool> {
     }
 }
 
-// TODO Is something like 5- also valid here?
-// It seems to be but it's not yet handled here
-// (We could use e.g. the `INFINITY` float value)
-pub fn parse_byte_range(value: &[u8]) -> Result<(f64, f64)> {
-    let mut cursor = 0usize;
-    let start;
-    loop {
-        if cursor >= value.len() {
-            let e = ParsingError("Invalid byte-range: end encountered too soon".to_owned());
-            return Err(e);
+/// Biggest integer which can be exactly represented as an f64, and thus
+/// communicated to the JS-side.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// A range of bytes, as found in `indexRange`, `mediaRange` and
+/// `Initialization@range` attributes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByteRange {
+    /// Offset of the first byte in the range.
+    pub start: u64,
+
+    /// Offset of the last byte in the range, included, or `None` if the range
+    /// goes until the end of the resource.
+    pub end: Option<u64>,
+}
+
+/// Parse a byte range, either in the `start-end` form, both being included, or
+/// in the open-ended `start-` form.
+///
+/// Suffix ranges (`-length`) are rejected as they are not allowed in an MPD,
+/// as are ranges whose end comes before their start or whose offsets cannot be
+/// represented exactly on the JS-side.
+pub fn parse_byte_range(value: &[u8]) -> Result<ByteRange> {
+    let invalid = |reason: &str| {
+        ParsingError(format!(
+            "Invalid byte range \"{}\": {}",
+            String::from_utf8_lossy(value),
+            reason
+        ))
+    };
+    let trimmed = value.trim_ascii();
+    let separator = match trimmed.iter().position(|c| *c == b'-') {
+        Some(0) => return Err(invalid("suffix ranges are not supported")),
+        Some(separator) => separator,
+        None => return Err(invalid("no \"-\" separator")),
+    };
+    let parse_offset = |offset: &[u8]| {
+        if offset.is_empty() || !offset.iter().all(u8::is_ascii_digit) {
+            return Err(invalid("offsets should be unsigned integers"));
         }
-        if value[cursor] == b'-' {
-            start = parse_u64(&value[0..cursor])?;
-            break;
+        match parse_u64(offset) {
+            Ok(offset) if offset <= MAX_SAFE_INTEGER => Ok(offset),
+            _ => Err(invalid("offset too large")),
         }
-        cursor += 1;
+    };
+    let start = parse_offset(&trimmed[..separator])?;
+    let end = &trimmed[separator + 1..];
+    if end.is_empty() {
+        return Ok(ByteRange { start, end: None });
     }
-
-    let end = parse_u64(&value[cursor + 1..value.len()])?;
-    Ok((start as f64, end as f64))
+    let end = parse_offset(end)?;
+    if end < start {
+        return Err(invalid("its end comes before its start"));
+    }
+    Ok(ByteRange {
+        start,
+        end: Some(end),
+    })
 }
 
-/// Parse ISO 8601 duration format (e.g. P5Y10M43HT22H8M3S) into the
-/// corresponding seconds in a float format.
+/// Designators of the components of an ISO 8601 duration, in the order in
+/// which they have to appear, with whether they belong to its time part (after
+/// the `T` designator) and the number of seconds they correspond to.
+///
+/// Years and months have no fixed length: it depends on the date the
+/// duration is added to, which is not known for the durations of the MPD.
+/// Like the JS parser, we thus approximate a year to 365 days and a month to
+/// 30 days.
+const DURATION_COMPONENTS: [(u8, bool, f64); 7] = [
+    (b'Y', false, 365. * 24. * 60. * 60.),
+    (b'M', false, 30. * 24. * 60. * 60.),
+    (b'W', false, 7. * 24. * 60. * 60.),
+    (b'D', false, 24. * 60. * 60.),
+    (b'H', true, 60. * 60.),
+    (b'M', true, 60.),
+    (b'S', true, 1.),
+];
+
+/// Parse an ISO 8601 duration (e.g. `P1Y10M43DT22H8M3.5S`), in the format of
+/// the `xs:duration` type, into the corresponding seconds in a float format.
+///
+/// The duration can be negative (e.g. `-PT5S`) and be expressed in weeks, in
+/// which case it has no other component (e.g. `P2W`).
+/// Each component can only appear once and in the order of
+/// `DURATION_COMPONENTS`, and only the last one can have a fractional part.
+///
+/// Years and months are converted with a fixed length, so the result is only
+/// an approximation for durations using them (@see `DURATION_COMPONENTS`).
+///
 /// This code could be much simpler if it was RegExp-based but I preferred not
 /// to, mainly because I didn't want to incur the size cost of importing regex
 /// code in here
 pub fn parse_iso_8601_duration(value: &[u8]) -> Result<f64> {
-    if value.is_empty() || value[0] != b'P' {
-        let err = ParsingError("Unexpected duration. Should start with \"P\"".to_owned());
-        return Err(err);
+    let invalid = |reason: &str| {
+        ParsingError(format!(
+            "Invalid ISO 8601 duration \"{}\": {}",
+            String::from_utf8_lossy(value),
+            reason
+        ))
+    };
+    let (is_negative, mut i) = match value.first() {
+        Some(b'-') => (true, 1),
+        _ => (false, 0),
+    };
+    if value.get(i) != Some(&b'P') {
+        return Err(invalid("should start with \"P\""));
     }
-    let mut base = 1;
+    i += 1;
+
     let mut result = 0.;
-    if value[1] != b'T' {
-        loop {
-            let (number, i) = read_next_float(value, base)?;
-            if i == value.len() {
-                let e =
-                    ParsingError("Invalid ISO 8601 duration: end encountered too soon".to_owned());
-                return Err(e);
+    let mut in_time_part = false;
+    let mut next_component = 0;
+    let mut components_nb = 0;
+    let mut has_weeks = false;
+    let mut has_fractional_part = false;
+    while i < value.len() {
+        if value[i] == b'T' {
+            if in_time_part {
+                return Err(invalid("\"T\" found twice"));
             }
-            let factor = match value[i] {
-                b'Y' => 365 * 24 * 60 * 60,
-                b'M' => 30 * 24 * 60 * 60,
-                b'D' => 24 * 60 * 60,
-                _ => {
-                    let e = ParsingError("Invalid duration: unexpected unit.".to_owned());
-                    return Err(e);
-                }
-            };
-            result += number * (factor as f64);
-            base = i + 1;
-            if base == value.len() {
-                return Ok(result);
-            }
-            if value[base] == b'T' {
-                break;
+            in_time_part = true;
+            i += 1;
+            if i == value.len() {
+                return Err(invalid("no time component after \"T\""));
             }
+            continue;
         }
-    }
-    base += 1;
-    loop {
-        let (number, i) = read_next_float(value, base)?;
-        if i == value.len() {
-            let e = ParsingError("Invalid ISO 8601 duration: end encountered too soon".to_owned());
-            return Err(e);
+        if has_fractional_part {
+            return Err(invalid(
+                "only the last component can have a fractional part",
+            ));
         }
-        let factor = match value[i] {
-            b'H' => 60 * 60,
-            b'M' => 60,
-            b'S' => 1,
-            _ => {
-                let e = ParsingError("Invalid duration: unexpected unit.".to_owned());
-                return Err(e);
+
+        let number_start = i;
+        while i < value.len() && value[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == number_start {
+            return Err(invalid("expected a number"));
+        }
+        let mut number_str = String::from_utf8_lossy(&value[number_start..i]).into_owned();
+        if i < value.len() && (value[i] == b'.' || value[i] == b',') {
+            i += 1;
+            let fraction_start = i;
+            while i < value.len() && value[i].is_ascii_digit() {
+                i += 1;
             }
+            if i == fraction_start {
+                return Err(invalid("expected digits after the decimal separator"));
+            }
+            number_str.push('.');
+            number_str.push_str(&String::from_utf8_lossy(&value[fraction_start..i]));
+            has_fractional_part = true;
+        }
+        let number = number_str.parse::<f64>()?;
+
+        let designator = match value.get(i) {
+            Some(designator) => *designator,
+            None => return Err(invalid("end encountered before the last unit")),
         };
-        result += number * (factor as f64);
-        base = i + 1;
-        if base == value.len() {
-            return Ok(result);
+        let component = DURATION_COMPONENTS
+            .iter()
+            .position(|(d, is_time, _)| *d == designator && *is_time == in_time_part)
+            .ok_or_else(|| invalid(&format!("unexpected unit \"{}\"", designator as char)))?;
+        if component < next_component {
+            return Err(invalid(&format!(
+                "unit \"{}\" repeated or not in the right order",
+                designator as char
+            )));
         }
+        result += number * DURATION_COMPONENTS[component].2;
+        next_component = component + 1;
+        components_nb += 1;
+        has_weeks = has_weeks || designator == b'W';
+        i += 1;
+    }
+    if components_nb == 0 {
+        return Err(invalid("no component"));
     }
+    if has_weeks && components_nb > 1 {
+        return Err(invalid("weeks cannot be combined with other units"));
+    }
+    Ok(if is_negative { -result } else { result })
 }
 
-/// Some values in the MPD can be expressed as divisions of integers (e.g. frame
-/// rates).
-/// This function tries to convert it to a floating point value.
-pub fn parse_maybe_division(value: &[u8]) -> Result<f64> {
+/// Parse an ISO 8601 date-time, in the extended format used by the
+/// `xs:dateTime` type of the MPD (e.g. `2023-04-02T10:30:00.25+02:00`), into
+/// the corresponding number of seconds since the Unix epoch.
+///
+/// Years can have more than four digits and be negative, seconds can have a
+/// fractional part and the timezone can be either `Z` or an `±hh:mm` offset.
+/// Date-times without timezone are considered to be in UTC.
+///
+/// This is done here rather than through `Date.parse` on the JS-side, whose
+/// behavior with those formats differs between JS engines.
+pub fn parse_date_time(value: &[u8]) -> Result<f64> {
+    let value = value.trim_ascii();
+    let invalid = |reason: &str| {
+        ParsingError(format!(
+            "Invalid date-time \"{}\": {}",
+            String::from_utf8_lossy(value),
+            reason
+        ))
+    };
+
     let mut i = 0;
-    while value.len() > i && value[i] == b' ' {
+    let is_negative = value.first() == Some(&b'-');
+    if is_negative {
         i += 1;
     }
-    if i == value.len() {
-        let e = ParsingError("Invalid number found in the MPD.".to_owned());
-        return Err(e);
-    }
-    let (number1, mut i) = read_next_float(value, i)?;
-    while value.len() > i && value[i] == b' ' {
+    let year_start = i;
+    while i < value.len() && value[i].is_ascii_digit() {
         i += 1;
     }
-    while value.len() > i && value[i] == b'/' {
-        i += 1;
+    let year_digits = &value[year_start..i];
+    if year_digits.len() < 4 || (year_digits.len() > 4 && year_digits[0] == b'0') {
+        return Err(invalid("invalid year"));
     }
-    while value.len() > i && value[i] == b' ' {
-        i += 1;
+    let year = match parse_i64(year_digits) {
+        Ok(year) if year <= MAX_DATE_TIME_YEAR => year,
+        _ => return Err(invalid("year out of range")),
+    };
+    let year = if is_negative { -year } else { year };
+
+    let month = read_date_time_component(value, &mut i, b'-')
+        .filter(|month| (1..=12).contains(month))
+        .ok_or_else(|| invalid("invalid month"))?;
+    let day = read_date_time_component(value, &mut i, b'-')
+        .filter(|day| *day >= 1 && *day <= days_in_month(year, month))
+        .ok_or_else(|| invalid("invalid day"))?;
+    let hours =
+        read_date_time_component(value, &mut i, b'T').ok_or_else(|| invalid("invalid hours"))?;
+    let minutes = read_date_time_component(value, &mut i, b':')
+        .filter(|minutes| *minutes <= 59)
+        .ok_or_else(|| invalid("invalid minutes"))?;
+
+    // Seconds are optional in ISO 8601, though not in `xs:dateTime`
+    let mut seconds = 0.;
+    if value.get(i) == Some(&b':') {
+        let integer_part = read_date_time_component(value, &mut i, b':')
+            .filter(|seconds| *seconds <= 59)
+            .ok_or_else(|| invalid("invalid seconds"))?;
+        seconds = integer_part as f64;
+        if matches!(value.get(i), Some(b'.') | Some(b',')) {
+            i += 1;
+            let fraction_start = i;
+            while i < value.len() && value[i].is_ascii_digit() {
+                i += 1;
+            }
+            if i == fraction_start {
+                return Err(invalid("invalid fractional seconds"));
+            }
+            // UNSAFE: We already checked that those are ASCII digits
+            let digits = unsafe { std::str::from_utf8_unchecked(&value[fraction_start..i]) };
+            seconds += format!("0.{}", digits).parse::<f64>()?;
+        }
     }
-    if i == value.len() {
-        return Ok(number1);
+    if hours > 24 || (hours == 24 && (minutes != 0 || seconds != 0.)) {
+        return Err(invalid("invalid hours"));
     }
-    let (number2, _) = read_next_float(value, i)?;
-    if number2 == 0. {
-        let e = ParsingError("Invalid value MPD in the MPD: Denominator set to `0`.".to_owned());
-        return Err(e);
+
+    let offset_minutes = match value.get(i) {
+        None => 0,
+        Some(b'Z') => {
+            i += 1;
+            0
+        }
+        Some(&sign) if sign == b'+' || sign == b'-' => {
+            let offset_hours = read_date_time_component(value, &mut i, sign)
+                .filter(|hours| *hours <= 14)
+                .ok_or_else(|| invalid("invalid timezone offset"))?;
+            let offset_minutes = match value.get(i) {
+                Some(b':') => read_date_time_component(value, &mut i, b':')
+                    .filter(|minutes| *minutes <= 59)
+                    .ok_or_else(|| invalid("invalid timezone offset"))?,
+                _ => 0,
+            };
+            let offset_minutes = (offset_hours * 60 + offset_minutes) as i64;
+            if sign == b'-' {
+                -offset_minutes
+            } else {
+                offset_minutes
+            }
+        }
+        Some(_) => return Err(invalid("unexpected character")),
+    };
+    if i != value.len() {
+        return Err(invalid("unexpected character"));
     }
-    return Ok(number1 / number2);
+
+    let days = days_from_civil(year, month, day);
+    let seconds_in_day = (hours * 3600 + minutes * 60) as i64 - offset_minutes * 60;
+    Ok((days * 86400 + seconds_in_day) as f64 + seconds)
 }
 
-/// Parse a floating point number, represented by `value` in ASCII, starting at
-/// the position `base_offset`.
-/// The decimal separator can either a be a point ('.') or a colon (',').
+/// Biggest year, in absolute value, accepted in a date-time.
 ///
-/// If it succeeds, returns both the floating point number as an f64 and the
-/// offset coming just after that float in `value` (or the length of `value` if
-/// it ended with a float).
-fn read_next_float(value: &[u8], base_offset: usize) -> Result<(f64, usize)> {
-    let mut i = base_offset;
-    while i < value.len() && value[i] >= b'0' && value[i] <= b'9' {
-        i += 1;
+/// It could be much larger, but such dates make no sense in an MPD and this
+/// prevents overflows when computing the corresponding timestamp.
+const MAX_DATE_TIME_YEAR: i64 = 999_999_999;
+
+/// Read a two-digit component of a date-time, such as its month, preceded by
+/// the `separator` character, starting at the offset `i` in `value`.
+///
+/// Returns `None` if it is not found, else the component and moves `i` after
+/// it.
+fn read_date_time_component(value: &[u8], i: &mut usize, separator: u8) -> Option<u32> {
+    match value.get(*i..*i + 3) {
+        Some([sep, tens, units])
+            if *sep == separator && tens.is_ascii_digit() && units.is_ascii_digit() =>
+        {
+            *i += 3;
+            Some(((tens - b'0') * 10 + (units - b'0')) as u32)
+        }
+        _ => None,
     }
-    if i == value.len() || (value[i] != b'.' && value[i] != b',') {
-        // UNSAFE: We already checked that this string represents a valid integer
-        let val_str = unsafe { std::str::from_utf8_unchecked(&value[base_offset..i]) };
-        let val_u64 = val_str.parse::<u64>()?;
-        return Ok((val_u64 as f64, i));
+}
+
+/// Returns the number of days in the given month (from `1` to `12`) of the
+/// given year of the proleptic Gregorian calendar.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
     }
+}
 
-    i += 1;
-    while i < value.len() && value[i] >= b'0' && value[i] <= b'9' {
-        i += 1;
+/// Returns the number of days between the Unix epoch and the given date of the
+/// proleptic Gregorian calendar.
+///
+/// Based on the `days_from_civil` algorithm by Howard Hinnant.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// A ratio of two strictly positive integers, such as a frame rate (e.g.
+/// `30000/1001`) or an aspect ratio (e.g. `16:9`), kept as written in the MPD
+/// so it can be compared exactly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ratio {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+impl Ratio {
+    /// Returns the value of that ratio as a float.
+    pub fn as_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+/// Parse a frame rate, either as an integer, as a division of two integers
+/// (e.g. `30000/1001`) or as a decimal number, with either a `.` or a `,`
+/// decimal separator, which is then converted into a division by a power of
+/// ten (e.g. `29.97` becomes `2997/100`).
+pub fn parse_frame_rate(value: &[u8]) -> Result<Ratio> {
+    let invalid = |reason: &str| {
+        ParsingError(format!(
+            "Invalid frame rate \"{}\": {}",
+            String::from_utf8_lossy(value),
+            reason
+        ))
+    };
+    let (numerator, denominator) = match value.iter().position(|c| *c == b'/') {
+        Some(separator) => (
+            parse_ratio_term(&value[..separator]).map_err(invalid)?,
+            parse_ratio_term(&value[separator + 1..]).map_err(invalid)?,
+        ),
+        None => {
+            let value = value.trim_ascii();
+            match value.iter().position(|c| *c == b'.' || *c == b',') {
+                None => (parse_ratio_term(value).map_err(invalid)?, 1),
+                Some(separator) => {
+                    let decimals = value.len() - separator - 1;
+                    let mut digits = value[..separator].to_vec();
+                    digits.extend_from_slice(&value[separator + 1..]);
+                    let denominator = 10u64
+                        .checked_pow(decimals as u32)
+                        .filter(|d| *d <= MAX_SAFE_INTEGER)
+                        .ok_or_else(|| invalid("too many decimals"))?;
+                    if decimals == 0 || separator == 0 {
+                        return Err(invalid("invalid decimal number"));
+                    }
+                    (parse_ratio_term(&digits).map_err(invalid)?, denominator)
+                }
+            }
+        }
+    };
+    if numerator == 0 {
+        return Err(invalid("it should be strictly positive"));
+    }
+    if denominator == 0 {
+        return Err(invalid("its denominator is set to `0`"));
+    }
+    Ok(Ratio {
+        numerator,
+        denominator,
+    })
+}
+
+/// Parse an aspect ratio, such as the `par` and `sar` attributes, in the `W:H`
+/// form (e.g. `16:9`).
+pub fn parse_aspect_ratio(value: &[u8]) -> Result<Ratio> {
+    let invalid = |reason: &str| {
+        ParsingError(format!(
+            "Invalid aspect ratio \"{}\": {}",
+            String::from_utf8_lossy(value),
+            reason
+        ))
+    };
+    let separator = value
+        .iter()
+        .position(|c| *c == b':')
+        .ok_or_else(|| invalid("it should be in the `W:H` form"))?;
+    let width = parse_ratio_term(&value[..separator]).map_err(invalid)?;
+    let height = parse_ratio_term(&value[separator + 1..]).map_err(invalid)?;
+    if width == 0 || height == 0 {
+        return Err(invalid("both terms should be strictly positive"));
+    }
+    Ok(Ratio {
+        numerator: width,
+        denominator: height,
+    })
+}
+
+/// Parse one of the terms of a ratio, which has to be an unsigned integer
+/// which can be represented exactly on the JS-side, optionally surrounded by
+/// whitespace.
+///
+/// Returns the reason why it is invalid if it isn't.
+fn parse_ratio_term(term: &[u8]) -> std::result::Result<u64, &'static str> {
+    let term = term.trim_ascii();
+    if term.first() == Some(&b'-') {
+        return Err("it should be strictly positive");
     }
-    // UNSAFE: We already checked that this string represents a valid float
-    let val_str = unsafe { std::str::from_utf8_unchecked(&value[base_offset..i]) };
-    let val_f64 = val_str.parse::<f64>()?;
-    Ok((val_f64, i))
+    if term.is_empty() || !term.iter().all(u8::is_ascii_digit) {
+        return Err("expected an integer");
+    }
+    match parse_u64(term) {
+        Ok(term) if term <= MAX_SAFE_INTEGER => Ok(term),
+        _ => Err("number too large"),
+    }
+}
+
+/// Parse an UUID, such as a `cenc:default_KID` attribute, into its 16 bytes.
+///
+/// It can either be in its canonical form of 32 hexadecimal digits separated
+/// into five groups by dashes (`8-4-4-4-12`) or be made of those 32 digits
+/// only. Both lower and upper case digits are accepted, as well as leading and
+/// trailing whitespace.
+pub fn parse_uuid(value: &[u8]) -> Result<[u8; 16]> {
+    let trimmed = value.trim_ascii();
+    let hex_digits: Vec<u8> = match trimmed.len() {
+        32 => trimmed.to_vec(),
+        36 => {
+            for (i, c) in trimmed.iter().enumerate() {
+                let is_dash_pos = matches!(i, 8 | 13 | 18 | 23);
+                if is_dash_pos != (*c == b'-') {
+                    return Err(ParsingError(format!(
+                        "Invalid UUID \"{}\": unexpected character at offset {}",
+                        String::from_utf8_lossy(trimmed),
+                        i
+                    )));
+                }
+            }
+            trimmed.iter().copied().filter(|c| *c != b'-').collect()
+        }
+        len => {
+            return Err(ParsingError(format!(
+                "Invalid UUID \"{}\": expected 32 hexadecimal digits, with or without \
+                 dashes, but the value has a length of {}",
+                String::from_utf8_lossy(trimmed),
+                len
+            )))
+        }
+    };
+
+    let mut uuid = [0u8; 16];
+    for (i, byte) in uuid.iter_mut().enumerate() {
+        match (
+            hex_value(hex_digits[i * 2]),
+            hex_value(hex_digits[i * 2 + 1]),
+        ) {
+            (Some(high), Some(low)) => *byte = (high << 4) | low,
+            _ => {
+                return Err(ParsingError(format!(
+                    "Invalid UUID \"{}\": non-hexadecimal character",
+                    String::from_utf8_lossy(trimmed)
+                )))
+            }
+        }
+    }
+    Ok(uuid)
+}
+
+/// Returns the value of the given ASCII hexadecimal digit, or `None` if it is
+/// not one.
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode the given base64 string (with the standard alphabet), ignoring any
+/// ASCII whitespace in it.
+///
+/// Padding characters are optional but, when present, they have to be at the
+/// end of the string and complete its length to a multiple of 4 characters.
+pub fn decode_base64(value: &[u8]) -> Result<Vec<u8>> {
+    let mut result = Vec::with_capacity(value.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut acc_bits = 0u8;
+    let mut data_len = 0usize;
+    let mut padding = 0usize;
+    for (i, c) in value.iter().enumerate() {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                padding += 1;
+                continue;
+            }
+            c if c.is_ascii_whitespace() => continue,
+            _ => {
+                return Err(ParsingError(format!(
+                    "Invalid base64: unexpected character at offset {}",
+                    i
+                )))
+            }
+        };
+        if padding > 0 {
+            return Err(ParsingError(format!(
+                "Invalid base64: data found after padding at offset {}",
+                i
+            )));
+        }
+        data_len += 1;
+        acc = (acc << 6) | sextet as u32;
+        acc_bits += 6;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            result.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    // A single base64 character at the end cannot encode a whole byte
+    if acc_bits >= 6 {
+        return Err(ParsingError("Invalid base64: unexpected length".to_owned()));
+    }
+    if padding > 0 && padding != (4 - data_len % 4) % 4 {
+        return Err(ParsingError(
+            "Invalid base64: unexpected number of padding characters".to_owned(),
+        ));
+    }
+    Ok(result)
 }
 
 pub fn u32_to_u8_slice_be(x: u32) -> [u8; 4] {
@@ -220,47 +645,213 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_maybe_division() {
-        assert_eq!(parse_maybe_division(b" 100 / 50 ").unwrap(), 100. / 50.);
-        assert_eq!(parse_maybe_division(b" 100 /50 ").unwrap(), 100. / 50.);
-        assert_eq!(parse_maybe_division(b"100 / 50 ").unwrap(), 100. / 50.);
-        assert_eq!(parse_maybe_division(b"100/ 50").unwrap(), 100. / 50.);
-        assert_eq!(parse_maybe_division(b"100/2").unwrap(), 100. / 2.);
-        assert_eq!(parse_maybe_division(b" 50 ").unwrap(), 50.);
-        assert_eq!(parse_maybe_division(b"50 ").unwrap(), 50.);
-        assert_eq!(parse_maybe_division(b"50").unwrap(), 50.);
+    fn test_parse_frame_rate() {
+        let ratio = |numerator, denominator| Ratio {
+            numerator,
+            denominator,
+        };
+        assert_eq!(parse_frame_rate(b" 100 / 50 ").unwrap(), ratio(100, 50));
+        assert_eq!(parse_frame_rate(b" 100 /50 ").unwrap(), ratio(100, 50));
+        assert_eq!(parse_frame_rate(b"100 / 50 ").unwrap(), ratio(100, 50));
+        assert_eq!(parse_frame_rate(b"100/ 50").unwrap(), ratio(100, 50));
+        assert_eq!(parse_frame_rate(b"30000/1001").unwrap(), ratio(30000, 1001));
+        assert_eq!(parse_frame_rate(b" 50 ").unwrap(), ratio(50, 1));
+        assert_eq!(parse_frame_rate(b"50").unwrap(), ratio(50, 1));
+        assert_eq!(parse_frame_rate(b"29.97").unwrap(), ratio(2997, 100));
+        assert_eq!(parse_frame_rate(b"29,97").unwrap(), ratio(2997, 100));
+        assert_eq!(parse_frame_rate(b"25.0").unwrap().as_f64(), 25.);
+        assert_eq!(parse_frame_rate(b"100/2").unwrap().as_f64(), 50.);
 
-        assert!(parse_maybe_division(b"").err().is_some());
-        assert!(parse_maybe_division(b"A").err().is_some());
-        assert!(parse_maybe_division(b"15/B A").err().is_some());
+        assert!(parse_frame_rate(b"").is_err());
+        assert!(parse_frame_rate(b"A").is_err());
+        assert!(parse_frame_rate(b"15/B A").is_err());
+        assert!(parse_frame_rate(b"0").is_err());
+        assert!(parse_frame_rate(b"-30").is_err());
+        assert!(parse_frame_rate(b"30/0").is_err());
+        assert!(parse_frame_rate(b"30/-1").is_err());
+        assert!(parse_frame_rate(b"30.").is_err());
+        assert!(parse_frame_rate(b".5").is_err());
+        assert!(parse_frame_rate(b"29.97/2").is_err());
+        assert!(parse_frame_rate(b"29,9.7").is_err());
+        assert!(parse_frame_rate(b"1.00000000000000000001").is_err());
+    }
+
+    #[test]
+    fn test_parse_aspect_ratio() {
+        let ratio = |numerator, denominator| Ratio {
+            numerator,
+            denominator,
+        };
+        assert_eq!(parse_aspect_ratio(b"16:9").unwrap(), ratio(16, 9));
+        assert_eq!(parse_aspect_ratio(b" 1 : 1 ").unwrap(), ratio(1, 1));
+
+        assert!(parse_aspect_ratio(b"").is_err());
+        assert!(parse_aspect_ratio(b"16/9").is_err());
+        assert!(parse_aspect_ratio(b"16:").is_err());
+        assert!(parse_aspect_ratio(b"0:1").is_err());
+        assert!(parse_aspect_ratio(b"16:0").is_err());
+        assert!(parse_aspect_ratio(b"-16:9").is_err());
+        assert!(parse_aspect_ratio(b"16.5:9").is_err());
     }
 
     #[test]
     fn test_parse_8601_duration() {
-        assert_eq!(
-            parse_iso_8601_duration(b"P1Y10M43DT22H8M3S").unwrap(),
-            61250883.
-        );
         assert_eq!(
             parse_iso_8601_duration(b"P1Y10M43DT22H8M3S").unwrap(),
             61250883.
         );
         assert_eq!(parse_iso_8601_duration(b"PT3S").unwrap(), 3.);
         assert_eq!(parse_iso_8601_duration(b"PT1M3.4S").unwrap(), 63.4);
+        assert_eq!(parse_iso_8601_duration(b"PT1,5M").unwrap(), 90.);
+        assert_eq!(parse_iso_8601_duration(b"P0D").unwrap(), 0.);
+        assert_eq!(parse_iso_8601_duration(b"PT0S").unwrap(), 0.);
+        assert_eq!(parse_iso_8601_duration(b"P2W").unwrap(), 1209600.);
+        assert_eq!(parse_iso_8601_duration(b"P1DT1H").unwrap(), 90000.);
+        assert_eq!(parse_iso_8601_duration(b"P1.5D").unwrap(), 129600.);
+        assert_eq!(parse_iso_8601_duration(b"-PT1.5S").unwrap(), -1.5);
+        assert_eq!(parse_iso_8601_duration(b"-P1Y").unwrap(), -31536000.);
+
+        assert!(parse_iso_8601_duration(b"").is_err());
+        assert!(parse_iso_8601_duration(b"P").is_err());
+        assert!(parse_iso_8601_duration(b"-P").is_err());
+        assert!(parse_iso_8601_duration(b"PT").is_err());
+        assert!(parse_iso_8601_duration(b"P1DT").is_err());
+        assert!(parse_iso_8601_duration(b"3S").is_err());
+        assert!(parse_iso_8601_duration(b"T3S").is_err());
+        assert!(parse_iso_8601_duration(b"P3S").is_err());
+        assert!(parse_iso_8601_duration(b"P3H").is_err());
+        assert!(parse_iso_8601_duration(b"P-1D").is_err());
+        assert!(parse_iso_8601_duration(b"P1").is_err());
+        assert!(parse_iso_8601_duration(b"PT1S1M").is_err());
+        assert!(parse_iso_8601_duration(b"P1D1D").is_err());
+        assert!(parse_iso_8601_duration(b"P1DT1HT1M").is_err());
+        assert!(parse_iso_8601_duration(b"PT1.5M3S").is_err());
+        assert!(parse_iso_8601_duration(b"PT1.S").is_err());
+        assert!(parse_iso_8601_duration(b"PT.5S").is_err());
+        assert!(parse_iso_8601_duration(b"P1M1W").is_err());
+        assert!(parse_iso_8601_duration(b"P1W1D").is_err());
+        assert!(parse_iso_8601_duration(b"P1WT1H").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_time() {
+        assert_eq!(parse_date_time(b"1970-01-01T00:00:00Z").unwrap(), 0.);
+        assert_eq!(
+            parse_date_time(b"2023-04-02T10:30:00Z").unwrap(),
+            1680431400.
+        );
+        assert_eq!(
+            parse_date_time(b"2023-04-02T10:30:00").unwrap(),
+            1680431400.
+        );
+        assert_eq!(
+            parse_date_time(b" 2023-04-02T12:30:00.25+02:00 ").unwrap(),
+            1680431400.25
+        );
+        assert_eq!(
+            parse_date_time(b"2023-04-02T07:00:00,5-03:30").unwrap(),
+            1680431400.5
+        );
+        assert_eq!(parse_date_time(b"2023-04-02T10:30Z").unwrap(), 1680431400.);
+        assert_eq!(
+            parse_date_time(b"2000-02-29T24:00:00Z").unwrap(),
+            parse_date_time(b"2000-03-01T00:00:00Z").unwrap()
+        );
+        assert_eq!(parse_date_time(b"1969-12-31T23:59:59Z").unwrap(), -1.);
+        assert_eq!(
+            parse_date_time(b"10000-01-01T00:00:00Z").unwrap(),
+            253402300800.
+        );
+        assert_eq!(
+            parse_date_time(b"-0001-01-01T00:00:00Z").unwrap(),
+            -62198755200.
+        );
 
-        assert!(parse_iso_8601_duration(b"").err().is_some());
-        assert!(parse_iso_8601_duration(b"3S").err().is_some());
-        assert!(parse_iso_8601_duration(b"T3S").err().is_some());
-        assert!(parse_iso_8601_duration(b"P3S").err().is_some());
+        assert!(parse_date_time(b"").is_err());
+        assert!(parse_date_time(b"2023-04-02").is_err());
+        assert!(parse_date_time(b"23-04-02T10:30:00Z").is_err());
+        assert!(parse_date_time(b"02023-04-02T10:30:00Z").is_err());
+        assert!(parse_date_time(b"2023-13-02T10:30:00Z").is_err());
+        assert!(parse_date_time(b"2023-02-29T10:30:00Z").is_err());
+        assert!(parse_date_time(b"2023-04-02T24:30:00Z").is_err());
+        assert!(parse_date_time(b"2023-04-02T10:60:00Z").is_err());
+        assert!(parse_date_time(b"2023-04-02T10:30:00.Z").is_err());
+        assert!(parse_date_time(b"2023-04-02T10:30:00+15:00").is_err());
+        assert!(parse_date_time(b"2023-04-02T10:30:00ZZ").is_err());
+        assert!(parse_date_time(b"9999999999-04-02T10:30:00Z").is_err());
     }
 
     #[test]
     fn test_parse_byte_range() {
-        assert_eq!(parse_byte_range(b"1-2").unwrap(), (1., 2.));
-        assert_eq!(parse_byte_range(b"100-200").unwrap(), (100., 200.));
+        let closed = |start, end| ByteRange {
+            start,
+            end: Some(end),
+        };
+        assert_eq!(parse_byte_range(b"1-2").unwrap(), closed(1, 2));
+        assert_eq!(parse_byte_range(b"100-200").unwrap(), closed(100, 200));
+        assert_eq!(parse_byte_range(b"5-5").unwrap(), closed(5, 5));
+        assert_eq!(parse_byte_range(b" 0-9 ").unwrap(), closed(0, 9));
+        assert_eq!(
+            parse_byte_range(b"1234-").unwrap(),
+            ByteRange {
+                start: 1234,
+                end: None
+            }
+        );
+        assert_eq!(
+            parse_byte_range(b"9007199254740991-").unwrap().start,
+            MAX_SAFE_INTEGER
+        );
+
+        assert!(parse_byte_range(b"").is_err());
+        assert!(parse_byte_range(b"A").is_err());
+        assert!(parse_byte_range(b"15").is_err());
+        assert!(parse_byte_range(b"15-A").is_err());
+        assert!(parse_byte_range(b"-500").is_err());
+        assert!(parse_byte_range(b"-").is_err());
+        assert!(parse_byte_range(b"200-100").is_err());
+        assert!(parse_byte_range(b"+1-2").is_err());
+        assert!(parse_byte_range(b"1-2-3").is_err());
+        assert!(parse_byte_range(b"9007199254740992-").is_err());
+        assert!(parse_byte_range(b"0-99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn test_parse_uuid() {
+        let expected = [
+            0x10, 0x77, 0xef, 0xec, 0xc0, 0xb2, 0x4d, 0x02, 0xac, 0xe3, 0x3c, 0x1e, 0x52, 0xe2,
+            0xfb, 0x4b,
+        ];
+        assert_eq!(
+            parse_uuid(b"1077efec-c0b2-4d02-ace3-3c1e52e2fb4b").unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_uuid(b" 1077EFECC0B24D02ACE33C1E52E2FB4B ").unwrap(),
+            expected
+        );
+
+        assert!(parse_uuid(b"").is_err());
+        assert!(parse_uuid(b"1077efec-c0b24d02-ace3-3c1e52e2fb4b").is_err());
+        assert!(parse_uuid(b"1077efec-c0b2-4d02-ace3-3c1e52e2fb4").is_err());
+        assert!(parse_uuid(b"1077efecc0b24d02ace33c1e52e2fb4g").is_err());
+        assert!(parse_uuid(b"1077efec-c0b2-4d02-ace3-3c1e52e2-b4b").is_err());
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64(b"").unwrap(), b"");
+        assert_eq!(decode_base64(b"Zm9vYmFy").unwrap(), b"foobar");
+        assert_eq!(decode_base64(b"Zm9vYg==").unwrap(), b"foob");
+        assert_eq!(decode_base64(b" Zm9v\n YmE= ").unwrap(), b"fooba");
+        assert_eq!(decode_base64(b"Zm9vYg").unwrap(), b"foob");
 
-        assert!(parse_byte_range(b"").err().is_some());
-        assert!(parse_byte_range(b"A").err().is_some());
-        assert!(parse_byte_range(b"15-A").err().is_some());
+        assert!(decode_base64(b"Zm9vY").is_err());
+        assert!(decode_base64(b"Zm9v*mFy").is_err());
+        assert!(decode_base64(b"Zg==Zg==").is_err());
+        assert!(decode_base64(b"Zg=").is_err());
+        assert!(decode_base64(b"Zm9v=").is_err());
+        assert!(decode_base64(b"Zm8==").is_err());
+        assert!(decode_base64(b"Zg===").is_err());
     }
 }
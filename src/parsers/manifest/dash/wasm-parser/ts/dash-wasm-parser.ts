@@ -24,6 +24,7 @@ import parseMpdIr from "../../common";
 import type {
   IMPDIntermediateRepresentation,
   IPeriodIntermediateRepresentation,
+  ISegmentTimelineElement,
 } from "../../node_parser_types";
 import type {
   IDashParserResponse,
@@ -33,7 +34,8 @@ import type {
 import { generateRootChildrenParser } from "./generators";
 import { generateXLinkChildrenParser } from "./generators/XLink";
 import ParsersStack from "./parsers_stack";
-import type { AttributeName, TagName } from "./types";
+import applyTimelineUpdates from "./timeline_updates";
+import type { AttributeName, ParserOption, TagName } from "./types";
 import { CustomEventType } from "./types";
 
 const MAX_READ_SIZE = 15e3;
@@ -103,6 +105,14 @@ export default class DashWasmParser {
    */
   private _isParsing: boolean;
 
+  /**
+   * Timelines communicated through `addPreviousTimeline` for the next parsing
+   * operation, by key.
+   * Used to rebuild the complete timelines the parser only reports updates
+   * for. Emptied after each parsing operation, like on the parser's side.
+   */
+  private _previousTimelines: Map<string, ISegmentTimelineElement[]>;
+
   /**
    * Create a new `DashWasmParser`.
    */
@@ -115,6 +125,7 @@ export default class DashWasmParser {
     this._initProm = null;
     this._warnings = [];
     this._isParsing = false;
+    this._previousTimelines = new Map();
   }
 
   /**
@@ -320,6 +331,158 @@ export default class DashWasmParser {
     return this._processParserReturnValue(ret);
   }
 
+  /**
+   * Configure one of the optional processing steps of the WebAssembly parser,
+   * for all subsequent parsing operations.
+   *
+   * For options which can only be enabled or disabled, `0` disables it and
+   * any other value enables it.
+   * @param {number} option
+   * @param {number} value
+   */
+  public setParserOption(option: ParserOption, value: number): void {
+    if (this._instance === null) {
+      throw new Error("DashWasmParser not initialized");
+    }
+    // TODO better type this
+    (
+      this._instance.instance.exports.set_parser_option as (
+        option: ParserOption,
+        value: number,
+      ) => void
+    )(option, value);
+  }
+
+  /**
+   * Ask for the segments of each `<Representation>` relying on a
+   * `<SegmentTemplate>` to be reported for all subsequent parsing operations,
+   * as well as the thumbnails they contain. Thumbnails are not computed
+   * without it.
+   *
+   * Only segments overlapping the given time range, in seconds relative to
+   * the start of the Period, are reported.
+   * Setting `from` to a value greater than `to` (or to `NaN`) disables it.
+   *
+   * Segments depending on the end of a `<Period>` without `duration`
+   * attribute are only set on their Representation once the whole MPD has
+   * been parsed.
+   * @param {number} from
+   * @param {number} to
+   */
+  public setSegmentRange(from: number, to: number): void {
+    if (this._instance === null) {
+      throw new Error("DashWasmParser not initialized");
+    }
+    // TODO better type this
+    (
+      this._instance.instance.exports.set_segment_range as (
+        from: number,
+        to: number,
+      ) => void
+    )(from, to);
+  }
+
+  /**
+   * Communicate the timeline obtained for a `<SegmentTimeline>` when parsing
+   * the previous version of the MPD, so the next parsing operation only
+   * reports the entries which changed since for it.
+   *
+   * The `key` identifying that `<SegmentTimeline>` is made of the identifiers
+   * of its parent `<Period>`, `<AdaptationSet>` and `<Representation>`
+   * elements separated by a `/` character. Each is the `id` attribute of the
+   * element or, if not set, its index among the elements of the same type in
+   * its parent prefixed by a `#` character (e.g. `#0`). It is empty for
+   * elements which are not a parent of the `<SegmentTimeline>`.
+   * @param {string} key
+   * @param {Array.<Object>} timeline
+   */
+  public addPreviousTimeline(
+    key: string,
+    timeline: Array<{ start: number; duration: number; repeatCount: number }>,
+  ): void {
+    if (this._instance === null) {
+      throw new Error("DashWasmParser not initialized");
+    }
+    const exports = this._instance.instance.exports;
+    const encodedKey = new TextEncoder().encode(key);
+    const len = encodedKey.byteLength + timeline.length * 24;
+
+    // TODO better type this
+    const ptr = (exports.reserve_input_buffer as (len: number) => number)(len);
+    const linearMemory = this._linearMemory as WebAssembly.Memory;
+    new Uint8Array(linearMemory.buffer, ptr, encodedKey.byteLength).set(encodedKey);
+    const dataView = new DataView(linearMemory.buffer);
+    let base = ptr + encodedKey.byteLength;
+    for (const elt of timeline) {
+      dataView.setFloat64(base, elt.start, true);
+      dataView.setFloat64(base + 8, elt.duration, true);
+      dataView.setFloat64(base + 16, elt.repeatCount, true);
+      base += 24;
+    }
+    (exports.add_previous_timeline as (keyLen: number) => void)(encodedKey.byteLength);
+    this._previousTimelines.set(key, timeline);
+  }
+
+  /**
+   * Communicate the URL at which the MPD parsed by the next parsing operation
+   * was fetched, against which its `<BaseURL>` and `<Location>` elements are
+   * resolved when the `ResolveBaseUrls` parser option is enabled.
+   * @param {string} url
+   */
+  public setMpdUrl(url: string): void {
+    if (this._instance === null) {
+      throw new Error("DashWasmParser not initialized");
+    }
+    const exports = this._instance.instance.exports;
+    const encodedUrl = new TextEncoder().encode(url);
+
+    // TODO better type this
+    const ptr = (exports.reserve_input_buffer as (len: number) => number)(
+      encodedUrl.byteLength,
+    );
+    const linearMemory = this._linearMemory as WebAssembly.Memory;
+    new Uint8Array(linearMemory.buffer, ptr, encodedUrl.byteLength).set(encodedUrl);
+    (exports.set_mpd_url as () => void)();
+  }
+
+  /**
+   * Register the `<EssentialProperty>` schemes supported by the player, for
+   * all subsequent parsing operations.
+   *
+   * `<AdaptationSet>` and `<Representation>` elements having an
+   * `<EssentialProperty>` with another scheme are then flagged as unsupported,
+   * or skipped if the `SkipUnsupportedSets` parser option is enabled.
+   * Setting it to `null` disables that evaluation, whereas an empty array
+   * means that only the schemes handled by the parser itself are supported.
+   *
+   * The DASH-IF trick mode and thumbnail tile schemes, which are handled by
+   * the parser itself, are always considered as supported and do not need to
+   * be registered.
+   * @param {Array.<string>|null} schemes
+   */
+  public setSupportedEssentialProperties(schemes: string[] | null): void {
+    if (this._instance === null) {
+      throw new Error("DashWasmParser not initialized");
+    }
+    const exports = this._instance.instance.exports;
+    if (schemes === null) {
+      // TODO better type this
+      (exports.disable_essential_property_evaluation as () => void)();
+      return;
+    }
+    const encodedSchemes = new TextEncoder().encode(schemes.join("\n"));
+
+    // TODO better type this
+    const ptr = (exports.reserve_input_buffer as (len: number) => number)(
+      encodedSchemes.byteLength,
+    );
+    const linearMemory = this._linearMemory as WebAssembly.Memory;
+    new Uint8Array(linearMemory.buffer, ptr, encodedSchemes.byteLength).set(
+      encodedSchemes,
+    );
+    (exports.set_supported_essential_properties as () => void)();
+  }
+
   /**
    * Return `true` if the current plaform is compatible with WebAssembly and the
    * TextDecoder interface (for faster UTF-8 parsing), which are needed features
@@ -358,15 +521,20 @@ export default class DashWasmParser {
     } catch (err) {
       this._parsersStack.reset();
       this._warnings = [];
+      this._previousTimelines.clear();
       this._isParsing = false;
       throw err;
     }
 
     const parsed = rootObj.mpd ?? null;
     const warnings = this._warnings;
+    if (parsed !== null) {
+      applyTimelineUpdates(parsed.children.periods, this._previousTimelines);
+    }
 
     this._parsersStack.reset();
     this._warnings = [];
+    this._previousTimelines.clear();
     this._isParsing = false;
 
     return [parsed, warnings];
@@ -404,14 +572,17 @@ export default class DashWasmParser {
     } catch (err) {
       this._parsersStack.reset();
       this._warnings = [];
+      this._previousTimelines.clear();
       this._isParsing = false;
       throw err;
     }
 
     const { periods } = rootObj;
     const warnings = this._warnings;
+    applyTimelineUpdates(periods, this._previousTimelines);
     this._parsersStack.reset();
     this._warnings = [];
+    this._previousTimelines.clear();
     this._isParsing = false;
     return [periods, warnings];
   }
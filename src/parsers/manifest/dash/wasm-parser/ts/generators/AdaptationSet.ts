@@ -23,7 +23,16 @@ import type {
 import type { IAttributeParser, IChildrenParser } from "../parsers_stack";
 import type ParsersStack from "../parsers_stack";
 import { AttributeName, TagName } from "../types";
-import { parseFloatOrBool, parseString } from "../utils";
+import {
+  parseCodecs,
+  parseFloatOrBool,
+  parseRatio,
+  parseRoles,
+  parseString,
+  parseStringList,
+  parseStringPairs,
+  parseTrackType,
+} from "../utils";
 import { generateBaseUrlAttrParser } from "./BaseURL";
 import { generateContentComponentAttrParser } from "./ContentComponent";
 import { generateContentProtectionAttrParser } from "./ContentProtection";
@@ -32,7 +41,11 @@ import {
   generateRepresentationAttrParser,
   generateRepresentationChildrenParser,
 } from "./Representation";
-import { generateSchemeAttrParser } from "./Scheme";
+import {
+  generateAccessibilityAttrParser,
+  generateEssentialPropertyAttrParser,
+  generateSchemeAttrParser,
+} from "./Scheme";
 import { generateSegmentBaseAttrParser } from "./SegmentBase";
 import { generateSegmentListChildrenParser } from "./SegmentList";
 import { generateSegmentTemplateAttrParser } from "./SegmentTemplate";
@@ -57,8 +70,8 @@ export function generateAdaptationSetChildrenParser(
           adaptationSetChildren.accessibilities = [];
         }
         adaptationSetChildren.accessibilities.push(accessibility);
-        const schemeAttrParser = generateSchemeAttrParser(accessibility, linearMemory);
-        parsersStack.pushParsers(nodeId, noop, schemeAttrParser);
+        const attrParser = generateAccessibilityAttrParser(accessibility, linearMemory);
+        parsersStack.pushParsers(nodeId, noop, attrParser);
         break;
       }
 
@@ -106,7 +119,10 @@ export function generateAdaptationSetChildrenParser(
         adaptationSetChildren.essentialProperties.push(essentialProperty);
 
         const childrenParser = noop; // EssentialProperty have no sub-element
-        const attributeParser = generateSchemeAttrParser(essentialProperty, linearMemory);
+        const attributeParser = generateEssentialPropertyAttrParser(
+          essentialProperty,
+          linearMemory,
+        );
         parsersStack.pushParsers(nodeId, childrenParser, attributeParser);
         break;
       }
@@ -266,6 +282,12 @@ export function generateAdaptationSetAttrParser(
       case AttributeName.Par:
         adaptationAttrs.par = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
+      case AttributeName.ParRatio:
+        adaptationAttrs.parRatio = parseRatio(linearMemory.buffer, ptr);
+        break;
+      case AttributeName.Sar:
+        adaptationAttrs.sar = parseRatio(linearMemory.buffer, ptr);
+        break;
       case AttributeName.MinBandwidth:
         adaptationAttrs.minBitrate = dataView.getFloat64(ptr, true);
         break;
@@ -290,6 +312,12 @@ export function generateAdaptationSetAttrParser(
       case AttributeName.MaxFrameRate:
         adaptationAttrs.maxFrameRate = dataView.getFloat64(ptr, true);
         break;
+      case AttributeName.MinFrameRateRatio:
+        adaptationAttrs.minFrameRateRatio = parseRatio(linearMemory.buffer, ptr);
+        break;
+      case AttributeName.MaxFrameRateRatio:
+        adaptationAttrs.maxFrameRateRatio = parseRatio(linearMemory.buffer, ptr);
+        break;
       case AttributeName.SelectionPriority:
         adaptationAttrs.selectionPriority = dataView.getFloat64(ptr, true);
         break;
@@ -314,6 +342,30 @@ export function generateAdaptationSetAttrParser(
           len,
         );
         break;
+      case AttributeName.ParsedCodecs:
+        adaptationAttrs.codecsInfo = parseCodecs(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.ParsedSupplementalCodecs:
+        adaptationAttrs.supplementalCodecsInfo = parseCodecs(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.MimeTypeParameters:
+        adaptationAttrs.mimeTypeParameters = parseStringPairs(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
       case AttributeName.Codecs:
         adaptationAttrs.codecs = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
@@ -355,6 +407,9 @@ export function generateAdaptationSetAttrParser(
       case AttributeName.FrameRate:
         adaptationAttrs.frameRate = dataView.getFloat64(ptr, true);
         break;
+      case AttributeName.FrameRateRatio:
+        adaptationAttrs.frameRateRatio = parseRatio(linearMemory.buffer, ptr);
+        break;
       case AttributeName.Height:
         adaptationAttrs.height = dataView.getFloat64(ptr, true);
         break;
@@ -373,6 +428,20 @@ export function generateAdaptationSetAttrParser(
       case AttributeName.AvailabilityTimeComplete:
         adaptationAttrs.availabilityTimeComplete = dataView.getUint8(0) === 0;
         break;
+      case AttributeName.InferredTrackType:
+        adaptationAttrs.inferredType = parseTrackType(linearMemory.buffer, ptr);
+        break;
+      case AttributeName.NormalizedRoles:
+        adaptationAttrs.roles = parseRoles(linearMemory.buffer, ptr, len);
+        break;
+      case AttributeName.UnsupportedEssentialProperties:
+        adaptationAttrs.unsupportedEssentialProperties = parseStringList(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
 
       // TODO
       // case AttributeName.StartsWithSap:
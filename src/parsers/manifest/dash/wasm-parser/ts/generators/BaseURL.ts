@@ -17,7 +17,7 @@
 import type { IBaseUrlIntermediateRepresentation } from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parseString, parseStringList } from "../utils";
 
 /**
  * Generate an "attribute parser" once inside a `BaseURL` node.
@@ -33,6 +33,13 @@ export function generateBaseUrlAttrParser(
   return function onMPDAttribute(attr: AttributeName, ptr: number, len: number) {
     if (attr === AttributeName.Text) {
       baseUrlAttrs.value = parseString(textDecoder, linearMemory.buffer, ptr, len);
+    } else if (attr === AttributeName.ResolvedBaseUrls) {
+      baseUrlAttrs.resolvedUrls = parseStringList(
+        textDecoder,
+        linearMemory.buffer,
+        ptr,
+        len,
+      );
     }
   };
 }
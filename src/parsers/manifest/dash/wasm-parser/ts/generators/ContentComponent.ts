@@ -17,7 +17,7 @@
 import type { IContentComponentAttributes } from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parseRatio, parseString } from "../utils";
 
 /**
  * Generate an "attribute parser" once inside a `BaseURL` node.
@@ -47,6 +47,10 @@ export function generateContentComponentAttrParser(
       case AttributeName.Par:
         ccAttrs.par = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
+
+      case AttributeName.ParRatio:
+        ccAttrs.parRatio = parseRatio(linearMemory.buffer, ptr);
+        break;
     }
   };
 }
@@ -14,12 +14,10 @@
  * limitations under the License.
  */
 
-import { base64ToBytes } from "../../../../../../utils/base64";
-import { hexToBytes } from "../../../../../../utils/string_parsing";
 import type { IContentProtectionIntermediateRepresentation } from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parsePssh, parseString } from "../utils";
 
 /**
  * @param {Object} cp
@@ -33,6 +31,13 @@ export function generateContentProtectionAttrParser(
   const cpAttrs = cp.attributes;
   const cpChildren = cp.children;
   const textDecoder = new TextDecoder();
+
+  /**
+   * License type announced for the next license server URL, if one.
+   * Reported by the parser just before that URL, and reset once it is read so
+   * it never applies to the URL of another element.
+   */
+  let pendingLicenseType: string | undefined;
   return function onContentProtectionAttribute(attr: number, ptr: number, len: number) {
     switch (attr) {
       case AttributeName.SchemeIdUri:
@@ -41,25 +46,65 @@ export function generateContentProtectionAttrParser(
       case AttributeName.ContentProtectionValue:
         cpAttrs.value = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
-      case AttributeName.ContentProtectionKeyId: {
-        const kid = parseString(textDecoder, linearMemory.buffer, ptr, len);
-        cpAttrs.keyId = hexToBytes(kid.replace(/-/g, ""));
+      case AttributeName.ContentProtectionKeyId:
+        // Already decoded into its 16 bytes by the parser
+        cpAttrs.keyId = new Uint8Array(linearMemory.buffer.slice(ptr, ptr + len));
         break;
-      }
       case AttributeName.ContentProtectionCencPSSH:
-        try {
-          const b64 = parseString(textDecoder, linearMemory.buffer, ptr, len);
-          cpChildren.cencPssh.push(base64ToBytes(b64));
-        } catch (_) {
-          /* TODO log error? register as warning? */
+        // Already base64-decoded by the parser
+        cpChildren.cencPssh.push(new Uint8Array(linearMemory.buffer.slice(ptr, ptr + len)));
+        break;
+      case AttributeName.ContentProtectionPssh: {
+        const psshInfo = parsePssh(linearMemory.buffer, ptr);
+        if (cpChildren.cencPsshInfo === undefined) {
+          cpChildren.cencPsshInfo = [psshInfo];
+        } else {
+          cpChildren.cencPsshInfo.push(psshInfo);
+        }
+        break;
+      }
+      case AttributeName.ContentProtectionPlayReadyKeyIds: {
+        const keyIds: Uint8Array[] = [];
+        for (let offset = ptr; offset + 16 <= ptr + len; offset += 16) {
+          keyIds.push(new Uint8Array(linearMemory.buffer.slice(offset, offset + 16)));
+        }
+        cpAttrs.playReadyKeyIds = keyIds;
+        if (cpAttrs.keyId === undefined && keyIds.length > 0) {
+          // PlayReady KIDs are already converted to big endian UUIDs
+          cpAttrs.keyId = keyIds[0];
         }
         break;
+      }
+      case AttributeName.ContentProtectionPlayReadyLaUrl:
+        cpAttrs.playReadyLaUrl = parseString(textDecoder, linearMemory.buffer, ptr, len);
+        break;
       case AttributeName.ContentProtectionRef:
         cpAttrs.ref = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
+      case AttributeName.ContentProtectionRefResolved:
+        // Inherited information was already reported, the reference does
+        // not have to be resolved again
+        cpAttrs.ref = undefined;
+        break;
       case AttributeName.ContentProtectionRefId:
         cpAttrs.refId = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
+      case AttributeName.ContentProtectionLicenseType:
+        pendingLicenseType = parseString(textDecoder, linearMemory.buffer, ptr, len);
+        break;
+      case AttributeName.ContentProtectionLicenseUrl: {
+        const licenseUrl = {
+          url: parseString(textDecoder, linearMemory.buffer, ptr, len),
+          licenseType: pendingLicenseType,
+        };
+        pendingLicenseType = undefined;
+        if (cpAttrs.licenseUrls === undefined) {
+          cpAttrs.licenseUrls = [licenseUrl];
+        } else {
+          cpAttrs.licenseUrls.push(licenseUrl);
+        }
+        break;
+      }
     }
   };
 }
@@ -15,11 +15,19 @@
  */
 
 import noop from "../../../../../../utils/noop";
-import type { IMPDAttributes, IMPDChildren } from "../../../node_parser_types";
+import type {
+  IAdaptationSetIntermediateRepresentation,
+  IMPDAttributes,
+  IMPDChildren,
+  IPeriodIntermediateRepresentation,
+  IRepresentationAttributes,
+  IRepresentationIntermediateRepresentation,
+  IResolvedPeriodTime,
+} from "../../../node_parser_types";
 import type { IAttributeParser, IChildrenParser } from "../parsers_stack";
 import type ParsersStack from "../parsers_stack";
 import { AttributeName, TagName } from "../types";
-import { parseString } from "../utils";
+import { parseSegments, parseString, parseThumbnails } from "../utils";
 import { generateBaseUrlAttrParser } from "./BaseURL";
 import { generateContentProtectionAttrParser } from "./ContentProtection";
 import { generatePeriodAttrParser, generatePeriodChildrenParser } from "./Period";
@@ -95,6 +103,15 @@ export function generateMPDChildrenParser(
         break;
       }
 
+      case TagName.DeferredSegments: {
+        const attributeParser = generateDeferredSegmentsAttrParser(
+          mpdChildren,
+          linearMemory,
+        );
+        parsersStack.pushParsers(nodeId, noop, attributeParser);
+        break;
+      }
+
       default:
         // Allows to make sure we're not mistakenly closing a re-opened
         // tag.
@@ -104,6 +121,46 @@ export function generateMPDChildrenParser(
   };
 }
 
+/**
+ * Generate an "attribute parser" for the segments of a Representation which
+ * are only reported once the whole MPD has been parsed.
+ * @param {Object} mpdChildren
+ * @param {WebAssembly.Memory} linearMemory
+ * @returns {Function}
+ */
+function generateDeferredSegmentsAttrParser(
+  mpdChildren: IMPDChildren,
+  linearMemory: WebAssembly.Memory,
+): IAttributeParser {
+  let target: IRepresentationAttributes | undefined;
+  const textDecoder = new TextDecoder();
+  return function onDeferredSegmentsAttribute(attr: number, ptr: number, len: number) {
+    switch (attr) {
+      case AttributeName.SegmentsTarget: {
+        const dataView = new DataView(linearMemory.buffer);
+        const period: IPeriodIntermediateRepresentation | undefined =
+          mpdChildren.periods[dataView.getUint32(ptr)];
+        const adaptation: IAdaptationSetIntermediateRepresentation | undefined =
+          period?.children.adaptations[dataView.getUint32(ptr + 4)];
+        const representation: IRepresentationIntermediateRepresentation | undefined =
+          adaptation?.children.representations[dataView.getUint32(ptr + 8)];
+        target = representation?.attributes;
+        break;
+      }
+      case AttributeName.Segments:
+        if (target !== undefined) {
+          target.segments = parseSegments(textDecoder, linearMemory.buffer, ptr, len);
+        }
+        break;
+      case AttributeName.Thumbnails:
+        if (target !== undefined) {
+          target.thumbnails = parseThumbnails(linearMemory.buffer, ptr, len);
+        }
+        break;
+    }
+  };
+}
+
 export function generateMPDAttrParser(
   mpdChildren: IMPDChildren,
   mpdAttrs: IMPDAttributes,
@@ -122,21 +179,21 @@ export function generateMPDAttrParser(
       case AttributeName.Type:
         mpdAttrs.type = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
-      case AttributeName.AvailabilityStartTime: {
-        const startTime = parseString(textDecoder, linearMemory.buffer, ptr, len);
-        mpdAttrs.availabilityStartTime = new Date(startTime).getTime() / 1000;
+      case AttributeName.AvailabilityStartTime:
+        // Already converted into seconds since the Unix epoch by the parser
+        dataView = new DataView(linearMemory.buffer);
+        mpdAttrs.availabilityStartTime = dataView.getFloat64(ptr, true);
         break;
-      }
-      case AttributeName.AvailabilityEndTime: {
-        const endTime = parseString(textDecoder, linearMemory.buffer, ptr, len);
-        mpdAttrs.availabilityEndTime = new Date(endTime).getTime() / 1000;
+      case AttributeName.AvailabilityEndTime:
+        // Already converted into seconds since the Unix epoch by the parser
+        dataView = new DataView(linearMemory.buffer);
+        mpdAttrs.availabilityEndTime = dataView.getFloat64(ptr, true);
         break;
-      }
-      case AttributeName.PublishTime: {
-        const publishTime = parseString(textDecoder, linearMemory.buffer, ptr, len);
-        mpdAttrs.publishTime = new Date(publishTime).getTime() / 1000;
+      case AttributeName.PublishTime:
+        // Already converted into seconds since the Unix epoch by the parser
+        dataView = new DataView(linearMemory.buffer);
+        mpdAttrs.publishTime = dataView.getFloat64(ptr, true);
         break;
-      }
       case AttributeName.MediaPresentationDuration:
         dataView = new DataView(linearMemory.buffer);
         mpdAttrs.duration = dataView.getFloat64(ptr, true);
@@ -170,6 +227,14 @@ export function generateMPDAttrParser(
         mpdChildren.locations.push(location);
         break;
       }
+      case AttributeName.ResolvedLocation: {
+        const location = parseString(textDecoder, linearMemory.buffer, ptr, len);
+        if (mpdChildren.resolvedLocations === undefined) {
+          mpdChildren.resolvedLocations = [];
+        }
+        mpdChildren.resolvedLocations.push(location);
+        break;
+      }
       case AttributeName.Namespace: {
         const xmlNs = { key: "", value: "" };
         dataView = new DataView(linearMemory.buffer);
@@ -191,6 +256,20 @@ export function generateMPDAttrParser(
         }
         break;
       }
+      case AttributeName.ResolvedPeriodTimes: {
+        dataView = new DataView(linearMemory.buffer);
+        const periodTimes: IResolvedPeriodTime[] = [];
+        for (let offset = ptr; offset + 24 <= ptr + len; offset += 24) {
+          const end = dataView.getFloat64(offset + 8, true);
+          periodTimes.push({
+            start: dataView.getFloat64(offset, true),
+            end: isNaN(end) ? undefined : end,
+            removed: dataView.getFloat64(offset + 16, true) !== 0,
+          });
+        }
+        mpdAttrs.resolvedPeriodTimes = periodTimes;
+        break;
+      }
     }
   };
 }
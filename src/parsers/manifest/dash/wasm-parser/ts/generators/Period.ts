@@ -204,6 +204,15 @@ export function generatePeriodAttrParser(
         }
         break;
       }
+      case AttributeName.TrickModeLinks: {
+        const dataView = new DataView(linearMemory.buffer);
+        const links: Array<[number, number]> = [];
+        for (let offset = ptr; offset + 8 <= ptr + len; offset += 8) {
+          links.push([dataView.getUint32(offset), dataView.getUint32(offset + 4)]);
+        }
+        periodAttrs.trickModeLinks = links;
+        break;
+      }
     }
   };
 }
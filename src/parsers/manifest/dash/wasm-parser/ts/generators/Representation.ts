@@ -23,10 +23,22 @@ import type {
 import type { IAttributeParser, IChildrenParser } from "../parsers_stack";
 import type ParsersStack from "../parsers_stack";
 import { AttributeName, TagName } from "../types";
-import { parseString } from "../utils";
+import {
+  parseCodecs,
+  parseHdrClassification,
+  parseRatio,
+  parseSegments,
+  parseString,
+  parseStringList,
+  parseStringPairs,
+  parseThumbnails,
+} from "../utils";
 import { generateBaseUrlAttrParser } from "./BaseURL";
 import { generateContentProtectionAttrParser } from "./ContentProtection";
-import { generateSchemeAttrParser } from "./Scheme";
+import {
+  generateEssentialPropertyAttrParser,
+  generateSchemeAttrParser,
+} from "./Scheme";
 import { generateSegmentBaseAttrParser } from "./SegmentBase";
 import { generateSegmentListChildrenParser } from "./SegmentList";
 import { generateSegmentTemplateAttrParser } from "./SegmentTemplate";
@@ -73,6 +85,20 @@ export function generateRepresentationChildrenParser(
         break;
       }
 
+      case TagName.EssentialProperty: {
+        const essentialProperty = {};
+        if (childrenObj.essentialProperties === undefined) {
+          childrenObj.essentialProperties = [];
+        }
+        childrenObj.essentialProperties.push(essentialProperty);
+        const attributeParser = generateEssentialPropertyAttrParser(
+          essentialProperty,
+          linearMemory,
+        );
+        parsersStack.pushParsers(nodeId, noop, attributeParser);
+        break;
+      }
+
       case TagName.InbandEventStream: {
         const inbandEvent = {};
         if (childrenObj.inbandEventStreams === undefined) {
@@ -101,6 +127,25 @@ export function generateRepresentationChildrenParser(
         break;
       }
 
+      case TagName.EffectiveAttributes: {
+        const effectiveObj = {
+          children: { baseURLs: [] },
+          attributes: {},
+        };
+        childrenObj.effective = effectiveObj;
+        const childrenParser = generateRepresentationChildrenParser(
+          effectiveObj.children,
+          linearMemory,
+          parsersStack,
+        );
+        const attributeParser = generateRepresentationAttrParser(
+          effectiveObj.attributes,
+          linearMemory,
+        );
+        parsersStack.pushParsers(nodeId, childrenParser, attributeParser);
+        break;
+      }
+
       case TagName.SegmentBase: {
         const segmentBaseObj = {};
         childrenObj.segmentBase = segmentBaseObj;
@@ -179,6 +224,30 @@ export function generateRepresentationAttrParser(
       case AttributeName.Bitrate:
         representationAttrs.bitrate = dataView.getFloat64(ptr, true);
         break;
+      case AttributeName.ParsedCodecs:
+        representationAttrs.codecsInfo = parseCodecs(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.ParsedSupplementalCodecs:
+        representationAttrs.supplementalCodecsInfo = parseCodecs(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.MimeTypeParameters:
+        representationAttrs.mimeTypeParameters = parseStringPairs(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
       case AttributeName.Codecs:
         representationAttrs.codecs = parseString(
           textDecoder,
@@ -202,6 +271,53 @@ export function generateRepresentationAttrParser(
       case AttributeName.FrameRate:
         representationAttrs.frameRate = dataView.getFloat64(ptr, true);
         break;
+      case AttributeName.FrameRateRatio:
+        representationAttrs.frameRateRatio = parseRatio(linearMemory.buffer, ptr);
+        break;
+      case AttributeName.Sar:
+        representationAttrs.sar = parseRatio(linearMemory.buffer, ptr);
+        break;
+      case AttributeName.CodecLayers: {
+        const layers = parseCodecs(textDecoder, linearMemory.buffer, ptr, len);
+        representationAttrs.codecLayers = [];
+        for (let i = 0; i + 1 < layers.length; i += 2) {
+          representationAttrs.codecLayers.push([layers[i], layers[i + 1]]);
+        }
+        break;
+      }
+      case AttributeName.UnsupportedEssentialProperties:
+        representationAttrs.unsupportedEssentialProperties = parseStringList(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.Segments:
+        representationAttrs.segments = parseSegments(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.SegmentsInitializationUrl:
+        representationAttrs.segmentsInitializationUrl = parseString(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+      case AttributeName.Thumbnails:
+        representationAttrs.thumbnails = parseThumbnails(linearMemory.buffer, ptr, len);
+        break;
+      case AttributeName.HdrInformation:
+        representationAttrs.hdrClassification = parseHdrClassification(
+          linearMemory.buffer,
+          ptr,
+        );
+        break;
       case AttributeName.Height:
         representationAttrs.height = dataView.getFloat64(ptr, true);
         break;
@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 
-import type { IScheme } from "../../../node_parser_types";
+import type {
+  IAccessibilityScheme,
+  IEssentialPropertyScheme,
+  IScheme,
+} from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parseCaptionServices, parseString } from "../utils";
 
 /**
  * Generate an "attribute parser" once inside a `BaseURL` node.
@@ -42,3 +46,67 @@ export function generateSchemeAttrParser(
     }
   };
 }
+
+/**
+ * Generate an "attribute parser" once inside an `Accessibility` node.
+ * @param {Object} accessibilityAttrs
+ * @param {WebAssembly.Memory} linearMemory
+ * @returns {Function}
+ */
+export function generateAccessibilityAttrParser(
+  accessibilityAttrs: IAccessibilityScheme,
+  linearMemory: WebAssembly.Memory,
+): IAttributeParser {
+  const textDecoder = new TextDecoder();
+  const schemeAttrParser = generateSchemeAttrParser(accessibilityAttrs, linearMemory);
+  return function onAccessibilityAttribute(attr: number, ptr: number, len: number) {
+    switch (attr) {
+      case AttributeName.CaptionServices:
+        accessibilityAttrs.captionServices = parseCaptionServices(
+          textDecoder,
+          linearMemory.buffer,
+          ptr,
+          len,
+        );
+        break;
+
+      case AttributeName.AccessibilityPurpose: {
+        const flags = new DataView(linearMemory.buffer).getUint8(ptr);
+        accessibilityAttrs.isAudioDescription = (flags & 1) !== 0;
+        accessibilityAttrs.isHardOfHearing = (flags & 2) !== 0;
+        break;
+      }
+
+      default:
+        schemeAttrParser(attr, ptr, len);
+    }
+  };
+}
+
+/**
+ * Generate an "attribute parser" once inside an `EssentialProperty` node.
+ * @param {Object} propertyAttrs
+ * @param {WebAssembly.Memory} linearMemory
+ * @returns {Function}
+ */
+export function generateEssentialPropertyAttrParser(
+  propertyAttrs: IEssentialPropertyScheme,
+  linearMemory: WebAssembly.Memory,
+): IAttributeParser {
+  const schemeAttrParser = generateSchemeAttrParser(propertyAttrs, linearMemory);
+  return function onEssentialPropertyAttribute(attr: number, ptr: number, len: number) {
+    switch (attr) {
+      case AttributeName.ThumbnailTile: {
+        const dataView = new DataView(linearMemory.buffer);
+        propertyAttrs.thumbnailTile = {
+          columns: dataView.getFloat64(ptr, true),
+          rows: dataView.getFloat64(ptr + 8, true),
+        };
+        break;
+      }
+
+      default:
+        schemeAttrParser(attr, ptr, len);
+    }
+  };
+}
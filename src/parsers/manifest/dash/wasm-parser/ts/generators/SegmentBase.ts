@@ -17,7 +17,7 @@
 import type { ISegmentBaseIntermediateRepresentation } from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parseByteRange, parseString } from "../utils";
 
 export function generateSegmentBaseAttrParser(
   segmentBaseAttrs: ISegmentBaseIntermediateRepresentation,
@@ -26,17 +26,12 @@ export function generateSegmentBaseAttrParser(
   const textDecoder = new TextDecoder();
   return function onSegmentBaseAttribute(attr, ptr, len) {
     switch (attr) {
-      case AttributeName.InitializationRange: {
-        const dataView = new DataView(linearMemory.buffer);
+      case AttributeName.InitializationRange:
         if (segmentBaseAttrs.initialization === undefined) {
           segmentBaseAttrs.initialization = {};
         }
-        segmentBaseAttrs.initialization.range = [
-          dataView.getFloat64(ptr, true),
-          dataView.getFloat64(ptr + 8, true),
-        ];
+        segmentBaseAttrs.initialization.range = parseByteRange(linearMemory.buffer, ptr);
         break;
-      }
 
       case AttributeName.InitializationMedia:
         if (segmentBaseAttrs.initialization === undefined) {
@@ -74,14 +69,9 @@ export function generateSegmentBaseAttrParser(
         break;
       }
 
-      case AttributeName.IndexRange: {
-        const dataView = new DataView(linearMemory.buffer);
-        segmentBaseAttrs.indexRange = [
-          dataView.getFloat64(ptr, true),
-          dataView.getFloat64(ptr + 8, true),
-        ];
+      case AttributeName.IndexRange:
+        segmentBaseAttrs.indexRange = parseByteRange(linearMemory.buffer, ptr);
         break;
-      }
 
       case AttributeName.IndexRangeExact: {
         segmentBaseAttrs.indexRangeExact =
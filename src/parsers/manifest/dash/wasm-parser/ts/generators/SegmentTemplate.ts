@@ -14,10 +14,13 @@
  * limitations under the License.
  */
 
-import type { ISegmentTemplateIntermediateRepresentation } from "../../../node_parser_types";
+import type {
+  ISegmentTemplateIntermediateRepresentation,
+  ISegmentTimelineElement,
+} from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parseByteRange, parseString } from "../utils";
 
 export function generateSegmentTemplateAttrParser(
   segmentTemplateAttrs: ISegmentTemplateIntermediateRepresentation,
@@ -26,21 +29,29 @@ export function generateSegmentTemplateAttrParser(
   const textDecoder = new TextDecoder();
   return function onSegmentTemplateAttribute(attr, ptr, len) {
     switch (attr) {
-      case AttributeName.SegmentTimeline: {
+      case AttributeName.SegmentTimeline:
+        segmentTemplateAttrs.timeline = parseTimeline(linearMemory.buffer, ptr, len);
+        break;
+
+      case AttributeName.SegmentTimelineFirstCommonStart: {
         const dataView = new DataView(linearMemory.buffer);
-        segmentTemplateAttrs.timeline = [];
-        let base = ptr;
-        for (let i = 0; i < len / 24; i++) {
-          segmentTemplateAttrs.timeline.push({
-            start: dataView.getFloat64(base, true),
-            duration: dataView.getFloat64(base + 8, true),
-            repeatCount: dataView.getFloat64(base + 16, true),
-          });
-          base += 24;
-        }
+        segmentTemplateAttrs.timelineUpdate = {
+          firstCommonStart: dataView.getFloat64(ptr, true),
+          entries: [],
+        };
         break;
       }
 
+      case AttributeName.SegmentTimelineUpdate:
+        if (segmentTemplateAttrs.timelineUpdate !== undefined) {
+          segmentTemplateAttrs.timelineUpdate.entries = parseTimeline(
+            linearMemory.buffer,
+            ptr,
+            len,
+          );
+        }
+        break;
+
       case AttributeName.InitializationMedia:
         segmentTemplateAttrs.initialization = {
           media: parseString(textDecoder, linearMemory.buffer, ptr, len),
@@ -80,14 +91,9 @@ export function generateSegmentTemplateAttrParser(
         break;
       }
 
-      case AttributeName.IndexRange: {
-        const dataView = new DataView(linearMemory.buffer);
-        segmentTemplateAttrs.indexRange = [
-          dataView.getFloat64(ptr, true),
-          dataView.getFloat64(ptr + 8, true),
-        ];
+      case AttributeName.IndexRange:
+        segmentTemplateAttrs.indexRange = parseByteRange(linearMemory.buffer, ptr);
         break;
-      }
 
       case AttributeName.IndexRangeExact: {
         segmentTemplateAttrs.indexRangeExact =
@@ -130,3 +136,30 @@ export function generateSegmentTemplateAttrParser(
     }
   };
 }
+
+/**
+ * Parse the entries of a `<SegmentTimeline>` reported by the parser, each
+ * as its start, duration and repeat count as little endian f64.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<Object>}
+ */
+function parseTimeline(
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): ISegmentTimelineElement[] {
+  const dataView = new DataView(buffer);
+  const timeline: ISegmentTimelineElement[] = [];
+  let base = ptr;
+  for (let i = 0; i < len / 24; i++) {
+    timeline.push({
+      start: dataView.getFloat64(base, true),
+      duration: dataView.getFloat64(base + 8, true),
+      repeatCount: dataView.getFloat64(base + 16, true),
+    });
+    base += 24;
+  }
+  return timeline;
+}
@@ -17,7 +17,7 @@
 import type { ISegmentUrlIntermediateRepresentation } from "../../../node_parser_types";
 import type { IAttributeParser } from "../parsers_stack";
 import { AttributeName } from "../types";
-import { parseString } from "../utils";
+import { parseByteRange, parseString } from "../utils";
 
 /**
  * Generate "attribute parser" for an encountered `SegmentURL` opening
@@ -37,27 +37,17 @@ export function generateSegmentUrlAttrParser(
         segmentUrlAttrs.index = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
 
-      case AttributeName.IndexRange: {
-        const dataView = new DataView(linearMemory.buffer);
-        segmentUrlAttrs.indexRange = [
-          dataView.getFloat64(ptr, true),
-          dataView.getFloat64(ptr + 8, true),
-        ];
+      case AttributeName.IndexRange:
+        segmentUrlAttrs.indexRange = parseByteRange(linearMemory.buffer, ptr);
         break;
-      }
 
       case AttributeName.Media:
         segmentUrlAttrs.media = parseString(textDecoder, linearMemory.buffer, ptr, len);
         break;
 
-      case AttributeName.MediaRange: {
-        const dataView = new DataView(linearMemory.buffer);
-        segmentUrlAttrs.mediaRange = [
-          dataView.getFloat64(ptr, true),
-          dataView.getFloat64(ptr + 8, true),
-        ];
+      case AttributeName.MediaRange:
+        segmentUrlAttrs.mediaRange = parseByteRange(linearMemory.buffer, ptr);
         break;
-      }
     }
   };
 }
@@ -0,0 +1,138 @@
+/**
+ * Copyright 2015 CANAL+ Group
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+import type {
+  IPeriodIntermediateRepresentation,
+  ISegmentTemplateIntermediateRepresentation,
+  ISegmentTimelineElement,
+} from "../../node_parser_types";
+
+/**
+ * Construct the key identifying a `<SegmentTimeline>`, in the same way than
+ * the parser does: the identifiers of its parent `<Period>`, `<AdaptationSet>`
+ * and `<Representation>` elements separated by a `/` character.
+ *
+ * Each identifier is the `id` attribute of the element or, if not set, its
+ * index among the elements of the same type in its parent prefixed by a `#`
+ * character. It is an empty string if the `<SegmentTimeline>` is not in such
+ * element.
+ * @param {Array.<Object|undefined>} elements
+ * @returns {string}
+ */
+function timelineKey(
+  elements: Array<{ id: string | undefined; index: number } | undefined>,
+): string {
+  return elements
+    .map((elt) => {
+      if (elt === undefined) {
+        return "";
+      }
+      return elt.id !== undefined && elt.id !== "" ? elt.id : `#${elt.index}`;
+    })
+    .join("/");
+}
+
+/**
+ * Build the complete timeline of a `<SegmentTimeline>` from the one which was
+ * communicated for it when parsing the previous version of the MPD and from
+ * the `SegmentTimelineFirstCommonStart` and `SegmentTimelineUpdate`
+ * attributes reported by the parser.
+ * @param {Array.<Object>} previous
+ * @param {number} firstCommonStart
+ * @param {Array.<Object>} updated
+ * @returns {Array.<Object>}
+ */
+function mergeTimeline(
+  previous: ISegmentTimelineElement[],
+  firstCommonStart: number,
+  updated: ISegmentTimelineElement[],
+): ISegmentTimelineElement[] {
+  const merged: ISegmentTimelineElement[] = [];
+  for (const elt of previous) {
+    const end = elt.start + elt.duration * (elt.repeatCount + 1);
+    if (end <= firstCommonStart) {
+      continue;
+    }
+    if (elt.start < firstCommonStart) {
+      const removedNb = (firstCommonStart - elt.start) / elt.duration;
+      merged.push({
+        start: firstCommonStart,
+        duration: elt.duration,
+        repeatCount: elt.repeatCount - removedNb,
+      });
+    } else {
+      merged.push({ ...elt });
+    }
+  }
+  if (updated.length === 0) {
+    return merged;
+  }
+  const updateStart = updated[0].start;
+  let keptNb = 0;
+  while (keptNb < merged.length && merged[keptNb].start < updateStart) {
+    keptNb++;
+  }
+  return merged.slice(0, keptNb).concat(updated);
+}
+
+/**
+ * Replace, in place, each timeline update reported by the parser in the given
+ * `<Period>` elements by the complete timeline, merged with the one
+ * communicated for that `<SegmentTimeline>` in `previousTimelines`.
+ * @param {Array.<Object>} periods
+ * @param {Map} previousTimelines
+ */
+export default function applyTimelineUpdates(
+  periods: IPeriodIntermediateRepresentation[],
+  previousTimelines: Map<string, ISegmentTimelineElement[]>,
+): void {
+  const applyUpdate = (
+    segmentTemplate: ISegmentTemplateIntermediateRepresentation | undefined,
+    key: string,
+  ) => {
+    if (segmentTemplate?.timelineUpdate === undefined) {
+      return;
+    }
+    const { firstCommonStart, entries } = segmentTemplate.timelineUpdate;
+    const previous = previousTimelines.get(key);
+    delete segmentTemplate.timelineUpdate;
+    if (previous !== undefined) {
+      segmentTemplate.timeline = mergeTimeline(previous, firstCommonStart, entries);
+    }
+  };
+
+  periods.forEach((period, periodIdx) => {
+    const periodInfo = { id: period.attributes.id, index: periodIdx };
+    applyUpdate(
+      period.children.segmentTemplate,
+      timelineKey([periodInfo, undefined, undefined]),
+    );
+    period.children.adaptations.forEach((adaptation, adaptationIdx) => {
+      const adaptationInfo = { id: adaptation.attributes.id, index: adaptationIdx };
+      applyUpdate(
+        adaptation.children.segmentTemplate,
+        timelineKey([periodInfo, adaptationInfo, undefined]),
+      );
+      adaptation.children.representations.forEach((representation, reprIdx) => {
+        const representationInfo = { id: representation.attributes.id, index: reprIdx };
+        applyUpdate(
+          representation.children.segmentTemplate,
+          timelineKey([periodInfo, adaptationInfo, representationInfo]),
+        );
+      });
+    });
+  });
+}
@@ -33,6 +33,61 @@ export const enum CustomEventType {
   Error = 1,
 }
 
+/**
+ * Identify an optional processing step of the parser, which can be configured
+ * through the `set_parser_option` WebAssembly export.
+ *
+ * This enum can simply be copy-pasted from the corresponding Rust file as both
+ * the TypeScript syntax and the rust syntax for them are really close.
+ */
+export const enum ParserOption {
+  /**
+   * When enabled (set to a non-zero value), contiguous `<S>` elements of a
+   * `<SegmentTimeline>` which share the same duration are folded into a
+   * single entry with the corresponding repeat count.
+   *
+   * Gaps and overlaps between consecutive `<S>` elements are then also
+   * reported, through the same channel as parsing errors. Those are only
+   * warnings, which can be told apart by their message, always starting with
+   * either `SegmentTimeline gap` or `SegmentTimeline overlap` and giving the
+   * position in the MPD of the `<S>` element concerned.
+   */
+  NormalizeSegmentTimeline = 0,
+
+  /**
+   * When enabled (set to a non-zero value), the text of each `<BaseURL>` and
+   * `<Location>` element is also resolved against the URL of the MPD and the
+   * `<BaseURL>` elements of its parents, and reported as absolute URLs.
+   * URLs which cannot be made absolute that way, e.g. because the URL of the
+   * MPD is unknown, are not reported.
+   */
+  ResolveBaseUrls = 1,
+
+  /**
+   * When enabled (set to a non-zero value), the effective attributes of each
+   * `<Representation>` are also reported, after taking into account those
+   * inherited from its parent elements. `<SegmentTemplate>` and
+   * `<SegmentBase>` elements are merged attribute by attribute.
+   */
+  ResolveInheritance = 2,
+
+  /**
+   * When enabled (set to a non-zero value), the remaining content of each
+   * `<AdaptationSet>` and `<Representation>` having an `<EssentialProperty>`
+   * whose scheme is not supported is skipped instead of being reported. Those
+   * elements are flagged in both cases.
+   */
+  SkipUnsupportedSets = 3,
+
+  /**
+   * When enabled (set to a non-zero value), the Period time bounds reported
+   * through `ResolvedPeriodTimes` are updated so they do not overlap: a Period
+   * ending after the start of the next one is shortened, and removed if it is
+   * left with no duration. Each update is also reported as an error.
+   */
+  FlattenOverlappingPeriods = 4,
+}
+
 /**
  * Identify the name of a node encountered by the wasm-parser.
  *
@@ -111,6 +166,16 @@ export const enum TagName {
 
   /// Indicate a <Label> node
   Label = 21,
+
+  /// Not an element of the MPD: effective attributes of the parent
+  /// <Representation> and the elements applying to it, reported when the
+  /// `ResolveInheritance` parser option is enabled.
+  EffectiveAttributes = 22,
+
+  /// Not an element of the MPD: segments of a <Representation> depending on
+  /// the end of a <Period> without `duration` attribute, reported just before
+  /// the <MPD> is closed.
+  DeferredSegments = 23,
 }
 
 /**
@@ -139,7 +204,7 @@ export const enum AttributeName {
   // ContentProtection
   ContentProtectionValue = 13, // String
   ContentProtectionKeyId = 14, // ArrayBuffer
-  ContentProtectionCencPSSH = 15, // ArrayBuffer
+  ContentProtectionCencPSSH = 15, // ArrayBuffer (decoded pssh box)
   ContentProtectionRef = 78, // String
   ContentProtectionRefId = 79, // String
 
@@ -150,7 +215,7 @@ export const enum AttributeName {
   SchemeValue = 17, // String
 
   // SegmentURL
-  MediaRange = 18, // [f64, f64]
+  MediaRange = 18, // ByteRange (start f64, end f64, open-ended u8)
 
   // SegmentTimeline
   SegmentTimeline = 19, // Vec<SElement>
@@ -179,20 +244,20 @@ export const enum AttributeName {
   Index = 28, // String
 
   // Initialization
-  InitializationRange = 29, // [f64, f64]
+  InitializationRange = 29, // ByteRange (start f64, end f64, open-ended u8)
 
   // SegmentURL + SegmentTemplate + SegmentBase + Initialization
   Media = 30, // String
-  IndexRange = 31, // [f64, f64]
+  IndexRange = 31, // ByteRange (start f64, end f64, open-ended u8)
 
   // Period + AdaptationSet + SegmentTemplate
   BitstreamSwitching = 32, // u8 (bool)
 
   // MPD
   Type = 33, // String
-  AvailabilityStartTime = 34, // f64
-  AvailabilityEndTime = 35, // f64
-  PublishTime = 36, // f64
+  AvailabilityStartTime = 34, // f64 (seconds since the Unix epoch)
+  AvailabilityEndTime = 35, // f64 (seconds since the Unix epoch)
+  PublishTime = 36, // f64 (seconds since the Unix epoch)
   MinimumUpdatePeriod = 37, // f64
   MinBufferTime = 38, // f64
   TimeShiftBufferDepth = 39, // f64
@@ -298,5 +363,165 @@ export const enum AttributeName {
   // SegmentTemplate
   EndNumber = 76, // f64
 
+  /// Converted into the comma-separated form of RFC 6381.
   SupplementalCodecs = 77, // String
+
+  /// Describes the start, in the corresponding timescale, of the first
+  /// segment in common between a `<SegmentTimeline>` and the timeline
+  /// communicated for it through `add_previous_timeline`.
+  ///
+  /// It is reported as an f64, just before the corresponding
+  /// `SegmentTimelineUpdate` attribute.
+  SegmentTimelineFirstCommonStart = 80,
+
+  /// Reported instead of `SegmentTimeline` when a `<SegmentTimeline>` could
+  /// be merged with the timeline communicated for it through
+  /// `add_previous_timeline`. It never is when it starts before that
+  /// timeline.
+  ///
+  /// It has the same format than `SegmentTimeline` but only contains the
+  /// entries which are new or which changed since the previous timeline.
+  SegmentTimelineUpdate = 81, // Vec<SElement>
+
+  /// Describes the segments of a `<Representation>`, computed from the
+  /// `<SegmentTemplate>` applying to it, in the time range communicated
+  /// through `set_segment_range`.
+  ///
+  /// It is reported just before the `<Representation>` is closed or, when
+  /// they depend on the end of a `<Period>` without `duration` attribute, in a
+  /// `DeferredSegments` tag. It is reported as the concatenation of each
+  /// segment's number, start and end (in seconds relative to the Period's
+  /// start) as f64 values, followed by the length of its URL as a big endian
+  /// u32 and its UTF-8 URL.
+  Segments = 82,
+
+  SegmentsInitializationUrl = 83, // String
+
+  /// Describes the absolute URLs obtained by resolving the text of a
+  /// `<BaseURL>` element against each URL of its parent elements.
+  ///
+  /// It is reported as the concatenation of, for each URL, its length as a
+  /// big endian u32 followed by the UTF-8 URL.
+  ///
+  /// URLs which stay relative, e.g. because the URL of the MPD is unknown,
+  /// are not part of it. It is not reported if none is absolute.
+  ResolvedBaseUrls = 84,
+
+  ResolvedLocation = 85, // String
+
+  /// Describes the fields of the `pssh` box found in a `<cenc:pssh>`
+  /// element: its version as an u8, its SystemID (16 bytes), its number of
+  /// KIDs as a big endian u32, each KID (16 bytes), the size of its data as a
+  /// big endian u32 and that data.
+  ContentProtectionPssh = 86,
+
+  /// Describes the key IDs found in the PlayReady Header of a `<mspr:pro>`
+  /// element, converted to big endian UUIDs, as 16 bytes each.
+  ContentProtectionPlayReadyKeyIds = 87,
+
+  ContentProtectionPlayReadyLaUrl = 88, // String
+
+  /// Describes the URL of a license server announced in a
+  /// `<ContentProtection>` through a `<dashif:Laurl>`, `<clearkey:Laurl>` or
+  /// `<ms:laurl>` element.
+  ContentProtectionLicenseUrl = 89, // String
+
+  /// Type of license served by the license server announced through the
+  /// next `ContentProtectionLicenseUrl` attribute, if known.
+  /// Reported just before that attribute, and only applies to it.
+  ContentProtectionLicenseType = 90, // String
+
+  /// Signals that the information the current `<ContentProtection>` inherits
+  /// from the one it references has already been reported on it.
+  ContentProtectionRefResolved = 91, // Boolean
+
+  /// Frame rates as the ratio they are written as in the MPD, to compare them
+  /// exactly.
+  FrameRateRatio = 92, // [f64, f64] (numerator, denominator)
+  MinFrameRateRatio = 93, // [f64, f64] (numerator, denominator)
+  MaxFrameRateRatio = 94, // [f64, f64] (numerator, denominator)
+
+  /// Aspect ratios, parsed from their `W:H` form.
+  ParRatio = 95, // [f64, f64] (width, height)
+  Sar = 96, // [f64, f64] (width, height)
+
+  /// Codecs of the `codecs` and `scte214:supplementalCodecs` attributes,
+  /// parsed as RFC 6381 codec strings. For each codec: its codec string
+  /// prefixed by its length, its family as an u8, then eleven big endian u32
+  /// set to `0xFFFFFFFF` when unknown.
+  ParsedCodecs = 97,
+  ParsedSupplementalCodecs = 98,
+
+  /// Parameters of the `mimeType` attribute, as names and values each
+  /// prefixed by their length as a big endian u32.
+  MimeTypeParameters = 99,
+
+  /// Dynamic range of a video `<Representation>`, reported just before it is
+  /// closed, as four u8: its dynamic range, color space, bit depth and
+  /// transfer characteristics, each but the first set to `255` if unknown.
+  HdrInformation = 100,
+
+  /// Codecs of a `<Representation>` which can be enhanced by one of its
+  /// supplemental codecs, reported just before it is closed, in the same way
+  /// than `ParsedCodecs`: each base layer's codec followed by the codec of its
+  /// enhancement layer.
+  CodecLayers = 101,
+
+  /// Type inferred for an `<AdaptationSet>`, reported just before it is
+  /// closed, as a single u8: `0` for audio, `1` for video, `2` for text and
+  /// `3` for image. Not reported if no type could be inferred.
+  InferredTrackType = 102,
+
+  /// Roles of an `<AdaptationSet>` with the `urn:mpeg:dash:role:2011` scheme,
+  /// normalized, sorted and deduplicated, reported just before it is closed
+  /// as one u8 per role.
+  NormalizedRoles = 103,
+
+  /// Caption channels of an `<Accessibility>` element with a CEA-608 or
+  /// CEA-708 scheme: the standard as an u8 (`0` for CEA-608, `1` for
+  /// CEA-708), then for each channel its number as an u8 (`0` if unknown), an
+  /// u8 of flags (`1` for easy reader, `2` for wide aspect ratio) and its
+  /// language prefixed by its length as a big endian u32.
+  CaptionServices = 104,
+
+  /// Purpose of an `<Accessibility>` element, as a single u8 of flags: `1`
+  /// for an audio description, `2` for content for the hard of hearing.
+  AccessibilityPurpose = 105,
+
+  /// Thumbnail grid of an `<EssentialProperty>` with the DASH-IF
+  /// `thumbnail_tile` scheme, as its number of columns and rows, each as a
+  /// little endian f64.
+  ThumbnailTile = 106,
+
+  /// Thumbnails of the segments reported through `Segments`, for thumbnail
+  /// Representations, reported just before it is closed: for each, the number
+  /// of its segment, its start, its end, then its crop rectangle as x, y,
+  /// width and height, each as a little endian f64.
+  ///
+  /// Only reported when a segment range is set and a `<SegmentTemplate>`
+  /// applies to that Representation, the grid always being reported through
+  /// `ThumbnailTile`.
+  Thumbnails = 107,
+
+  /// Links between the trick mode `<AdaptationSet>` elements of a `<Period>`
+  /// and the ones they are associated to, reported just before it is closed,
+  /// as couples of their index in the `<Period>`, each as a big endian u32.
+  TrickModeLinks = 108,
+
+  /// Schemes of the `<EssentialProperty>` elements of an `<AdaptationSet>` or
+  /// `<Representation>` not supported by the host, reported just before it is
+  /// closed, each prefixed by its length as a big endian u32. That element
+  /// should then be ignored.
+  UnsupportedEssentialProperties = 109,
+
+  /// Time bounds of each `<Period>`, in document order, reported just before
+  /// the `<MPD>` is closed: for each, its start, its end (NaN if unknown) and
+  /// `1` if it was removed by flattening or else `0`, each as a little endian
+  /// f64. Not reported if they cannot be resolved or if a Period has an xlink.
+  ResolvedPeriodTimes = 110,
+
+  /// The `<Representation>` to which the segments of a `DeferredSegments` tag
+  /// belong, as the indexes of its `<Period>`, `<AdaptationSet>` and itself
+  /// in their parent, each as a big endian u32.
+  SegmentsTarget = 111,
 }
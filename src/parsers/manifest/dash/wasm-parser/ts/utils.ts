@@ -14,6 +14,18 @@
  * limitations under the License.
  */
 
+import type {
+  IAdaptationSetAttributes,
+  ICaptionService,
+  ICaptionServices,
+  ICodecInfo,
+  IDashRole,
+  IHdrClassification,
+  IPsshInfo,
+  ISegmentInfo,
+  IThumbnailInfo,
+} from "../../node_parser_types";
+
 /**
  * @param {TextDecoder} textDecoder
  * @param {ArrayBuffer} buffer
@@ -45,4 +57,405 @@ function parseFloatOrBool(val: number): number | boolean {
   return val;
 }
 
-export { parseString, parseFloatOrBool };
+/**
+ * Parse a byte range reported by the parser: its start and end as little
+ * endian f64, followed by an u8 set to `1` if it is open-ended, in which case
+ * its end is set to `Infinity`.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @returns {Array.<number>}
+ */
+function parseByteRange(buffer: ArrayBuffer, ptr: number): [number, number] {
+  const dataView = new DataView(buffer);
+  const start = dataView.getFloat64(ptr, true);
+  if (dataView.getUint8(ptr + 16) === 1) {
+    return [start, Infinity];
+  }
+  return [start, dataView.getFloat64(ptr + 8, true)];
+}
+
+/**
+ * Parse a ratio reported by the parser, such as a frame rate or an aspect
+ * ratio: its numerator and denominator as little endian f64.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @returns {Array.<number>}
+ */
+function parseRatio(buffer: ArrayBuffer, ptr: number): [number, number] {
+  const dataView = new DataView(buffer);
+  return [dataView.getFloat64(ptr, true), dataView.getFloat64(ptr + 8, true)];
+}
+
+/** Codec families, indexed by the value reported by the parser. */
+const CODEC_FAMILIES: Array<ICodecInfo["family"]> = [
+  "avc",
+  "hevc",
+  "av1",
+  "vp9",
+  "vp8",
+  "mp4a",
+  "ac-3",
+  "ec-3",
+  "ac-4",
+  "dolby-vision",
+  "opus",
+  "flac",
+  "stpp",
+  "wvtt",
+];
+
+const CHROMA_SUBSAMPLINGS: Array<ICodecInfo["chromaSubsampling"]> = [
+  "4:0:0",
+  "4:2:0",
+  "4:2:2",
+  "4:4:4",
+];
+
+/** Value reported for unknown properties of a parsed codec. */
+const UNKNOWN_CODEC_PROPERTY = 0xffffffff;
+
+/**
+ * Parse codecs reported by the parser: for each of them, its codec string
+ * prefixed by its length, its family as an u8, then eleven big endian u32
+ * set to `0xFFFFFFFF` when unknown.
+ * @param {TextDecoder} textDecoder
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<Object>}
+ */
+function parseCodecs(
+  textDecoder: TextDecoder,
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): ICodecInfo[] {
+  const dataView = new DataView(buffer);
+  const codecs: ICodecInfo[] = [];
+  let offset = ptr;
+  while (offset < ptr + len) {
+    const codecLength = dataView.getUint32(offset);
+    offset += 4;
+    const codec = parseString(textDecoder, buffer, offset, codecLength);
+    offset += codecLength;
+    const family = CODEC_FAMILIES[dataView.getUint8(offset)] ?? "unknown";
+    offset += 1;
+    const fields: Array<number | undefined> = [];
+    for (let i = 0; i < 11; i++) {
+      const value = dataView.getUint32(offset);
+      fields.push(value === UNKNOWN_CODEC_PROPERTY ? undefined : value);
+      offset += 4;
+    }
+    const info: ICodecInfo = { codec, family };
+    const [
+      profile,
+      level,
+      tier,
+      bitDepth,
+      chromaSubsampling,
+      colorPrimaries,
+      transferCharacteristics,
+      matrixCoefficients,
+      fullRange,
+      objectTypeIndication,
+      audioObjectType,
+    ] = fields;
+    info.profile = profile;
+    info.level = level;
+    info.tier = tier === undefined ? undefined : tier === 0 ? "main" : "high";
+    info.bitDepth = bitDepth;
+    info.chromaSubsampling =
+      chromaSubsampling === undefined
+        ? undefined
+        : CHROMA_SUBSAMPLINGS[chromaSubsampling];
+    info.colorPrimaries = colorPrimaries;
+    info.transferCharacteristics = transferCharacteristics;
+    info.matrixCoefficients = matrixCoefficients;
+    info.fullRange = fullRange === undefined ? undefined : fullRange === 1;
+    info.objectTypeIndication = objectTypeIndication;
+    info.audioObjectType = audioObjectType;
+    codecs.push(info);
+  }
+  return codecs;
+}
+
+/** Dynamic ranges, indexed by the value reported by the parser. */
+const DYNAMIC_RANGES: Array<IHdrClassification["dynamicRange"]> = [
+  "sdr",
+  "hdr10",
+  "hlg",
+  "dolby-vision",
+  "hdr10+",
+];
+
+const COLOR_SPACES: Array<IHdrClassification["colorSpace"]> = ["rec709", "rec2020"];
+
+/**
+ * Parse the dynamic range of a Representation reported by the parser, as
+ * four u8 set to `255` when unknown.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @returns {Object}
+ */
+function parseHdrClassification(buffer: ArrayBuffer, ptr: number): IHdrClassification {
+  const bytes = new Uint8Array(buffer, ptr, 4);
+  const [dynamicRange, colorSpace, bitDepth, transferCharacteristics] = bytes;
+  return {
+    dynamicRange: DYNAMIC_RANGES[dynamicRange] ?? "sdr",
+    colorSpace: COLOR_SPACES[colorSpace],
+    bitDepth: bitDepth === 255 ? undefined : bitDepth,
+    transferCharacteristics:
+      transferCharacteristics === 255 ? undefined : transferCharacteristics,
+  };
+}
+
+/** Track types, indexed by the value reported by the parser. */
+const TRACK_TYPES: Array<IAdaptationSetAttributes["inferredType"]> = [
+  "audio",
+  "video",
+  "text",
+  "image",
+];
+
+/** DASH roles, indexed by the value reported by the parser. */
+const DASH_ROLES: IDashRole[] = [
+  "main",
+  "alternate",
+  "supplementary",
+  "commentary",
+  "dub",
+  "emergency",
+  "caption",
+  "subtitle",
+  "sign",
+  "description",
+  "enhanced-audio-intelligibility",
+  "forced-subtitle",
+  "easyreader",
+  "karaoke",
+  "metadata",
+];
+
+/**
+ * Parse the type inferred for an AdaptationSet, reported as a single u8.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @returns {string|undefined}
+ */
+function parseTrackType(
+  buffer: ArrayBuffer,
+  ptr: number,
+): IAdaptationSetAttributes["inferredType"] {
+  return TRACK_TYPES[new Uint8Array(buffer, ptr, 1)[0]];
+}
+
+/**
+ * Parse the normalized roles of an AdaptationSet, reported as one u8 each.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<string>}
+ */
+function parseRoles(buffer: ArrayBuffer, ptr: number, len: number): IDashRole[] {
+  const roles: IDashRole[] = [];
+  for (const role of new Uint8Array(buffer, ptr, len)) {
+    if (DASH_ROLES[role] !== undefined) {
+      roles.push(DASH_ROLES[role]);
+    }
+  }
+  return roles;
+}
+
+/**
+ * Parse the caption channels of an Accessibility element, reported as the
+ * caption standard followed, for each channel, by its number, its flags and
+ * its language prefixed by its length as a big endian u32.
+ * @param {TextDecoder} textDecoder
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Object}
+ */
+function parseCaptionServices(
+  textDecoder: TextDecoder,
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): ICaptionServices {
+  const dataView = new DataView(buffer);
+  const standard = dataView.getUint8(ptr) === 0 ? "cea-608" : "cea-708";
+  const services: ICaptionService[] = [];
+  let offset = ptr + 1;
+  while (offset < ptr + len) {
+    const channel = dataView.getUint8(offset);
+    const flags = dataView.getUint8(offset + 1);
+    const languageLength = dataView.getUint32(offset + 2);
+    const language = parseString(textDecoder, buffer, offset + 6, languageLength);
+    offset += 6 + languageLength;
+    services.push({
+      channel: channel === 0 ? undefined : channel,
+      language: language === "" ? undefined : language,
+      easyReader: (flags & 1) !== 0,
+      wideAspectRatio: (flags & 2) !== 0,
+    });
+  }
+  return { standard, services };
+}
+
+/**
+ * Parse the fields of a `pssh` box, reported as its version as an u8, its
+ * SystemID, its number of KIDs as a big endian u32, each KID, the size of its
+ * data as a big endian u32 and that data.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @returns {Object}
+ */
+function parsePssh(buffer: ArrayBuffer, ptr: number): IPsshInfo {
+  const dataView = new DataView(buffer);
+  const version = dataView.getUint8(ptr);
+  const systemId = new Uint8Array(buffer.slice(ptr + 1, ptr + 17));
+  const keyIdsNb = dataView.getUint32(ptr + 17);
+  let offset = ptr + 21;
+  const keyIds: Uint8Array[] = [];
+  for (let i = 0; i < keyIdsNb; i++) {
+    keyIds.push(new Uint8Array(buffer.slice(offset, offset + 16)));
+    offset += 16;
+  }
+  const dataSize = dataView.getUint32(offset);
+  const data = new Uint8Array(buffer.slice(offset + 4, offset + 4 + dataSize));
+  return { version, systemId, keyIds, data };
+}
+
+/**
+ * Parse the segments of a Representation, reported as its number, start and
+ * end as little endian f64 each, followed by the length of its URL as a big
+ * endian u32 and its UTF-8 URL.
+ * @param {TextDecoder} textDecoder
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<Object>}
+ */
+function parseSegments(
+  textDecoder: TextDecoder,
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): ISegmentInfo[] {
+  const dataView = new DataView(buffer);
+  const segments: ISegmentInfo[] = [];
+  let offset = ptr;
+  while (offset + 28 <= ptr + len) {
+    const urlLength = dataView.getUint32(offset + 24);
+    segments.push({
+      number: dataView.getFloat64(offset, true),
+      start: dataView.getFloat64(offset + 8, true),
+      end: dataView.getFloat64(offset + 16, true),
+      url: parseString(textDecoder, buffer, offset + 28, urlLength),
+    });
+    offset += 28 + urlLength;
+  }
+  return segments;
+}
+
+/**
+ * Parse the thumbnails of a Representation, reported as seven little endian
+ * f64 each: its segment number, start, end, x, y, width and height.
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<Object>}
+ */
+function parseThumbnails(
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): IThumbnailInfo[] {
+  const dataView = new DataView(buffer);
+  const thumbnails: IThumbnailInfo[] = [];
+  for (let offset = ptr; offset + 56 <= ptr + len; offset += 56) {
+    thumbnails.push({
+      segmentNumber: dataView.getFloat64(offset, true),
+      start: dataView.getFloat64(offset + 8, true),
+      end: dataView.getFloat64(offset + 16, true),
+      x: dataView.getFloat64(offset + 24, true),
+      y: dataView.getFloat64(offset + 32, true),
+      width: dataView.getFloat64(offset + 40, true),
+      height: dataView.getFloat64(offset + 48, true),
+    });
+  }
+  return thumbnails;
+}
+
+/**
+ * Parse a list of strings reported by the parser, each prefixed by its length
+ * as a big endian u32.
+ * @param {TextDecoder} textDecoder
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<string>}
+ */
+function parseStringList(
+  textDecoder: TextDecoder,
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): string[] {
+  const dataView = new DataView(buffer);
+  const list: string[] = [];
+  let offset = ptr;
+  while (offset < ptr + len) {
+    const length = dataView.getUint32(offset);
+    list.push(parseString(textDecoder, buffer, offset + 4, length));
+    offset += 4 + length;
+  }
+  return list;
+}
+
+/**
+ * Parse `[name, value]` couples reported by the parser, as names and values
+ * each prefixed by their length as a big endian u32.
+ * @param {TextDecoder} textDecoder
+ * @param {ArrayBuffer} buffer
+ * @param {number} ptr
+ * @param {number} len
+ * @returns {Array.<Array.<string>>}
+ */
+function parseStringPairs(
+  textDecoder: TextDecoder,
+  buffer: ArrayBuffer,
+  ptr: number,
+  len: number,
+): Array<[string, string]> {
+  const dataView = new DataView(buffer);
+  const pairs: Array<[string, string]> = [];
+  let offset = ptr;
+  while (offset < ptr + len) {
+    const nameLength = dataView.getUint32(offset);
+    const name = parseString(textDecoder, buffer, offset + 4, nameLength);
+    offset += 4 + nameLength;
+    const valueLength = dataView.getUint32(offset);
+    const value = parseString(textDecoder, buffer, offset + 4, valueLength);
+    offset += 4 + valueLength;
+    pairs.push([name, value]);
+  }
+  return pairs;
+}
+
+export {
+  parseByteRange,
+  parseCaptionServices,
+  parseCodecs,
+  parseHdrClassification,
+  parsePssh,
+  parseRatio,
+  parseRoles,
+  parseSegments,
+  parseString,
+  parseStringList,
+  parseStringPairs,
+  parseThumbnails,
+  parseTrackType,
+  parseFloatOrBool,
+};